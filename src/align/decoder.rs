@@ -1,4 +1,4 @@
-use crate::align::{AxialEnvelope, HorizontalEnvelope, VerticalEnvelope};
+use crate::align::{AxialEnvelope, CornerEnvelope, HorizontalEnvelope, VerticalEnvelope};
 
 pub trait HorizontalDecoder {
     fn aligned<T>(data: &impl HorizontalEnvelope<T>) -> &T;
@@ -11,3 +11,7 @@ pub trait VerticalDecoder {
 pub trait AxialDecoder {
     fn aligned<T>(data: &impl AxialEnvelope<T>) -> &T;
 }
+
+pub trait CornerDecoder {
+    fn aligned<T>(data: &impl CornerEnvelope<T>) -> &T;
+}
@@ -1,3 +1,5 @@
+use std::array;
+
 mod decoder;
 
 pub mod typed;
@@ -42,6 +44,78 @@ pub trait AxialEnvelope<T>: Sized {
     }
 }
 
+pub trait CornerEnvelope<T>: Sized {
+    fn top_left(&self) -> &T;
+
+    fn top_right(&self) -> &T;
+
+    fn bottom_left(&self) -> &T;
+
+    fn bottom_right(&self) -> &T;
+
+    fn cornered_at<K>(&self) -> &T
+    where
+        K: typed::Corner,
+    {
+        K::aligned(self)
+    }
+}
+
+/// Rotation by quarter turns.
+pub trait Rotate: Sized {
+    /// Rotates a quarter turn clockwise, such that the top becomes the right.
+    #[must_use]
+    fn rotate_clockwise(self) -> Self;
+
+    /// Rotates a quarter turn counterclockwise, such that the top becomes the left.
+    #[must_use]
+    fn rotate_counterclockwise(self) -> Self {
+        self.rotate_clockwise()
+            .rotate_clockwise()
+            .rotate_clockwise()
+    }
+}
+
+/// Exchange of values at opposite alignments.
+pub trait Invert: Sized {
+    #[must_use]
+    fn invert(self) -> Self;
+}
+
+impl Invert for valued::Alignment {
+    fn invert(self) -> Self {
+        self.opposite()
+    }
+}
+
+impl Invert for valued::Corner {
+    fn invert(self) -> Self {
+        self.opposite()
+    }
+}
+
+impl Rotate for valued::Alignment {
+    fn rotate_clockwise(self) -> Self {
+        match self {
+            valued::Alignment::LEFT => valued::Alignment::TOP,
+            valued::Alignment::TOP => valued::Alignment::RIGHT,
+            valued::Alignment::RIGHT => valued::Alignment::BOTTOM,
+            valued::Alignment::BOTTOM => valued::Alignment::LEFT,
+        }
+    }
+}
+
+impl Rotate for valued::Corner {
+    fn rotate_clockwise(self) -> Self {
+        match self {
+            valued::Corner::TopLeft => valued::Corner::TopRight,
+            valued::Corner::TopRight => valued::Corner::BottomRight,
+            valued::Corner::BottomRight => valued::Corner::BottomLeft,
+            valued::Corner::BottomLeft => valued::Corner::TopLeft,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Horizontal<T> {
     pub left: T,
@@ -49,6 +123,16 @@ pub struct Horizontal<T> {
 }
 
 impl<T> Horizontal<T> {
+    pub fn uniform(value: T) -> Self
+    where
+        T: Clone,
+    {
+        Horizontal {
+            left: value.clone(),
+            right: value,
+        }
+    }
+
     pub fn aligned(&self, alignment: valued::HorizontalAlignment) -> &T {
         match alignment {
             valued::HorizontalAlignment::Left => &self.left,
@@ -67,6 +151,46 @@ impl<T> Horizontal<T> {
             self.horizontally_aligned_at::<H::Opposite>(),
         )
     }
+
+    pub fn as_ref(&self) -> Horizontal<&T> {
+        Horizontal {
+            left: &self.left,
+            right: &self.right,
+        }
+    }
+
+    pub fn as_mut(&mut self) -> Horizontal<&mut T> {
+        Horizontal {
+            left: &mut self.left,
+            right: &mut self.right,
+        }
+    }
+
+    pub fn map<U, F>(self, mut f: F) -> Horizontal<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        Horizontal {
+            left: f(self.left),
+            right: f(self.right),
+        }
+    }
+
+    pub fn zip<U>(self, other: Horizontal<U>) -> Horizontal<(T, U)> {
+        Horizontal {
+            left: (self.left, other.left),
+            right: (self.right, other.right),
+        }
+    }
+}
+
+impl<T> IntoIterator for Horizontal<T> {
+    type Item = T;
+    type IntoIter = array::IntoIter<T, 2>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter([self.left, self.right])
+    }
 }
 
 impl<T> Horizontal<Vertical<T>> {
@@ -85,6 +209,16 @@ impl<T> Horizontal<Vertical<T>> {
     }
 }
 
+impl<T> Invert for Horizontal<T> {
+    fn invert(self) -> Self {
+        let Horizontal { left, right } = self;
+        Horizontal {
+            left: right,
+            right: left,
+        }
+    }
+}
+
 impl<T> HorizontalEnvelope<T> for Horizontal<T> {
     fn left(&self) -> &T {
         &self.left
@@ -102,6 +236,16 @@ pub struct Vertical<T> {
 }
 
 impl<T> Vertical<T> {
+    pub fn uniform(value: T) -> Self
+    where
+        T: Clone,
+    {
+        Vertical {
+            top: value.clone(),
+            bottom: value,
+        }
+    }
+
     pub fn aligned(&self, alignment: valued::VerticalAlignment) -> &T {
         match alignment {
             valued::VerticalAlignment::Top => &self.top,
@@ -120,6 +264,46 @@ impl<T> Vertical<T> {
             self.vertically_aligned_at::<H::Opposite>(),
         )
     }
+
+    pub fn as_ref(&self) -> Vertical<&T> {
+        Vertical {
+            top: &self.top,
+            bottom: &self.bottom,
+        }
+    }
+
+    pub fn as_mut(&mut self) -> Vertical<&mut T> {
+        Vertical {
+            top: &mut self.top,
+            bottom: &mut self.bottom,
+        }
+    }
+
+    pub fn map<U, F>(self, mut f: F) -> Vertical<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        Vertical {
+            top: f(self.top),
+            bottom: f(self.bottom),
+        }
+    }
+
+    pub fn zip<U>(self, other: Vertical<U>) -> Vertical<(T, U)> {
+        Vertical {
+            top: (self.top, other.top),
+            bottom: (self.bottom, other.bottom),
+        }
+    }
+}
+
+impl<T> IntoIterator for Vertical<T> {
+    type Item = T;
+    type IntoIter = array::IntoIter<T, 2>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter([self.top, self.bottom])
+    }
 }
 
 impl<T> Vertical<Horizontal<T>> {
@@ -138,6 +322,16 @@ impl<T> Vertical<Horizontal<T>> {
     }
 }
 
+impl<T> Invert for Vertical<T> {
+    fn invert(self) -> Self {
+        let Vertical { top, bottom } = self;
+        Vertical {
+            top: bottom,
+            bottom: top,
+        }
+    }
+}
+
 impl<T> VerticalEnvelope<T> for Vertical<T> {
     fn top(&self) -> &T {
         &self.top
@@ -148,6 +342,7 @@ impl<T> VerticalEnvelope<T> for Vertical<T> {
     }
 }
 
+/// Values at each edge.
 #[derive(Clone, Copy, Debug)]
 pub struct Square<T> {
     pub left: T,
@@ -156,7 +351,38 @@ pub struct Square<T> {
     pub bottom: T,
 }
 
+/// Values at each edge, such as the margins or strokes around a block.
+pub type Perimeter<T> = Square<T>;
+
 impl<T> Square<T> {
+    pub fn uniform(value: T) -> Self
+    where
+        T: Clone,
+    {
+        Square::merge(Horizontal::uniform(value.clone()), Vertical::uniform(value))
+    }
+
+    pub fn merge(horizontal: Horizontal<T>, vertical: Vertical<T>) -> Self {
+        let Horizontal { left, right } = horizontal;
+        let Vertical { top, bottom } = vertical;
+        Square {
+            left,
+            right,
+            top,
+            bottom,
+        }
+    }
+
+    pub fn split(self) -> (Horizontal<T>, Vertical<T>) {
+        let Square {
+            left,
+            right,
+            top,
+            bottom,
+        } = self;
+        (Horizontal { left, right }, Vertical { top, bottom })
+    }
+
     pub fn aligned(&self, alignment: valued::Alignment) -> &T {
         match alignment {
             valued::Alignment::LEFT => &self.left,
@@ -165,6 +391,114 @@ impl<T> Square<T> {
             valued::Alignment::BOTTOM => &self.bottom,
         }
     }
+
+    pub fn as_ref(&self) -> Square<&T> {
+        Square {
+            left: &self.left,
+            right: &self.right,
+            top: &self.top,
+            bottom: &self.bottom,
+        }
+    }
+
+    pub fn as_mut(&mut self) -> Square<&mut T> {
+        Square {
+            left: &mut self.left,
+            right: &mut self.right,
+            top: &mut self.top,
+            bottom: &mut self.bottom,
+        }
+    }
+
+    pub fn map<U, F>(self, mut f: F) -> Square<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        Square {
+            left: f(self.left),
+            right: f(self.right),
+            top: f(self.top),
+            bottom: f(self.bottom),
+        }
+    }
+
+    pub fn zip<U>(self, other: Square<U>) -> Square<(T, U)> {
+        Square {
+            left: (self.left, other.left),
+            right: (self.right, other.right),
+            top: (self.top, other.top),
+            bottom: (self.bottom, other.bottom),
+        }
+    }
+}
+
+impl<T> Invert for Square<T> {
+    fn invert(self) -> Self {
+        let Square {
+            left,
+            right,
+            top,
+            bottom,
+        } = self;
+        Square {
+            left: right,
+            right: left,
+            top: bottom,
+            bottom: top,
+        }
+    }
+}
+
+impl<T> Rotate for Square<T> {
+    fn rotate_clockwise(self) -> Self {
+        let Square {
+            left,
+            right,
+            top,
+            bottom,
+        } = self;
+        Square {
+            left: bottom,
+            right: top,
+            top: left,
+            bottom: right,
+        }
+    }
+}
+
+impl<T> IntoIterator for Square<T> {
+    type Item = T;
+    type IntoIter = array::IntoIter<T, 4>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter([self.left, self.right, self.top, self.bottom])
+    }
+}
+
+impl<T> From<(Horizontal<T>, Vertical<T>)> for Square<T> {
+    fn from((horizontal, vertical): (Horizontal<T>, Vertical<T>)) -> Self {
+        Square::merge(horizontal, vertical)
+    }
+}
+
+impl<T> From<Square<T>> for (Horizontal<T>, Vertical<T>) {
+    fn from(square: Square<T>) -> Self {
+        square.split()
+    }
+}
+
+// Each value is duplicated at both edges along its axis.
+impl<T> From<Axial<T>> for Square<T>
+where
+    T: Clone,
+{
+    fn from(axial: Axial<T>) -> Self {
+        let Axial {
+            horizontal,
+            vertical,
+        } = axial;
+        Square::merge(Horizontal::uniform(horizontal), Vertical::uniform(vertical))
+    }
 }
 
 impl<T> HorizontalEnvelope<T> for Square<T> {
@@ -194,6 +528,16 @@ pub struct Quadrant<T> {
 }
 
 impl<T> Quadrant<T> {
+    pub fn uniform(value: T) -> Self
+    where
+        T: Clone,
+    {
+        Quadrant {
+            top: Horizontal::uniform(value.clone()),
+            bottom: Horizontal::uniform(value),
+        }
+    }
+
     pub fn aligned(
         &self,
         vertical: valued::VerticalAlignment,
@@ -204,6 +548,37 @@ impl<T> Quadrant<T> {
             valued::VerticalAlignment::Bottom => self.bottom.aligned(horizontal),
         }
     }
+
+    pub fn as_ref(&self) -> Quadrant<&T> {
+        Quadrant {
+            top: self.top.as_ref(),
+            bottom: self.bottom.as_ref(),
+        }
+    }
+
+    pub fn as_mut(&mut self) -> Quadrant<&mut T> {
+        Quadrant {
+            top: self.top.as_mut(),
+            bottom: self.bottom.as_mut(),
+        }
+    }
+
+    pub fn map<U, F>(self, mut f: F) -> Quadrant<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        Quadrant {
+            top: self.top.map(&mut f),
+            bottom: self.bottom.map(f),
+        }
+    }
+
+    pub fn zip<U>(self, other: Quadrant<U>) -> Quadrant<(T, U)> {
+        Quadrant {
+            top: self.top.zip(other.top),
+            bottom: self.bottom.zip(other.bottom),
+        }
+    }
 }
 
 impl<T> From<Horizontal<Vertical<T>>> for Quadrant<T> {
@@ -219,12 +594,571 @@ impl<T> From<Vertical<Horizontal<T>>> for Quadrant<T> {
     }
 }
 
+// Values are yielded from left to right and then top to bottom.
+impl<T> IntoIterator for Quadrant<T> {
+    type Item = T;
+    type IntoIter = array::IntoIter<T, 4>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Corners::from(self).into_iter()
+    }
+}
+
+impl<T> CornerEnvelope<T> for Quadrant<T> {
+    fn top_left(&self) -> &T {
+        &self.top.left
+    }
+
+    fn top_right(&self) -> &T {
+        &self.top.right
+    }
+
+    fn bottom_left(&self) -> &T {
+        &self.bottom.left
+    }
+
+    fn bottom_right(&self) -> &T {
+        &self.bottom.right
+    }
+}
+
+impl<T> From<Quadrant<T>> for Vertical<Horizontal<T>> {
+    fn from(quadrant: Quadrant<T>) -> Self {
+        let Quadrant { top, bottom } = quadrant;
+        Vertical { top, bottom }
+    }
+}
+
+impl<T> From<Quadrant<T>> for Horizontal<Vertical<T>> {
+    fn from(quadrant: Quadrant<T>) -> Self {
+        Vertical::from(quadrant).transpose()
+    }
+}
+
+impl<T> Invert for Quadrant<T> {
+    fn invert(self) -> Self {
+        Corners::from(self).invert().into()
+    }
+}
+
+impl<T> Rotate for Quadrant<T> {
+    fn rotate_clockwise(self) -> Self {
+        Corners::from(self).rotate_clockwise().into()
+    }
+}
+
+/// Values at each corner.
+#[derive(Clone, Copy, Debug)]
+pub struct Corners<T> {
+    pub top_left: T,
+    pub top_right: T,
+    pub bottom_left: T,
+    pub bottom_right: T,
+}
+
+impl<T> Corners<T> {
+    pub fn aligned(&self, corner: valued::Corner) -> &T {
+        match corner {
+            valued::Corner::TopLeft => &self.top_left,
+            valued::Corner::TopRight => &self.top_right,
+            valued::Corner::BottomLeft => &self.bottom_left,
+            valued::Corner::BottomRight => &self.bottom_right,
+        }
+    }
+
+    pub fn as_ref(&self) -> Corners<&T> {
+        Corners {
+            top_left: &self.top_left,
+            top_right: &self.top_right,
+            bottom_left: &self.bottom_left,
+            bottom_right: &self.bottom_right,
+        }
+    }
+
+    pub fn as_mut(&mut self) -> Corners<&mut T> {
+        Corners {
+            top_left: &mut self.top_left,
+            top_right: &mut self.top_right,
+            bottom_left: &mut self.bottom_left,
+            bottom_right: &mut self.bottom_right,
+        }
+    }
+
+    pub fn map<U, F>(self, mut f: F) -> Corners<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        Corners {
+            top_left: f(self.top_left),
+            top_right: f(self.top_right),
+            bottom_left: f(self.bottom_left),
+            bottom_right: f(self.bottom_right),
+        }
+    }
+
+    pub fn zip<U>(self, other: Corners<U>) -> Corners<(T, U)> {
+        Corners {
+            top_left: (self.top_left, other.top_left),
+            top_right: (self.top_right, other.top_right),
+            bottom_left: (self.bottom_left, other.bottom_left),
+            bottom_right: (self.bottom_right, other.bottom_right),
+        }
+    }
+}
+
+impl<T> IntoIterator for Corners<T> {
+    type Item = T;
+    type IntoIter = array::IntoIter<T, 4>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter([
+            self.top_left,
+            self.top_right,
+            self.bottom_left,
+            self.bottom_right,
+        ])
+    }
+}
+
+impl<T> CornerEnvelope<T> for Corners<T> {
+    fn top_left(&self) -> &T {
+        &self.top_left
+    }
+
+    fn top_right(&self) -> &T {
+        &self.top_right
+    }
+
+    fn bottom_left(&self) -> &T {
+        &self.bottom_left
+    }
+
+    fn bottom_right(&self) -> &T {
+        &self.bottom_right
+    }
+}
+
+impl<T> Rotate for Corners<T> {
+    fn rotate_clockwise(self) -> Self {
+        let Corners {
+            top_left,
+            top_right,
+            bottom_left,
+            bottom_right,
+        } = self;
+        Corners {
+            top_left: bottom_left,
+            top_right: top_left,
+            bottom_left: bottom_right,
+            bottom_right: top_right,
+        }
+    }
+}
+
+impl<T> Invert for Corners<T> {
+    fn invert(self) -> Self {
+        self.rotate_clockwise().rotate_clockwise()
+    }
+}
+
+impl<T> From<Quadrant<T>> for Corners<T> {
+    fn from(quadrant: Quadrant<T>) -> Self {
+        let Quadrant { top, bottom } = quadrant;
+        Corners {
+            top_left: top.left,
+            top_right: top.right,
+            bottom_left: bottom.left,
+            bottom_right: bottom.right,
+        }
+    }
+}
+
+impl<T> From<Corners<T>> for Quadrant<T> {
+    fn from(corners: Corners<T>) -> Self {
+        let Corners {
+            top_left,
+            top_right,
+            bottom_left,
+            bottom_right,
+        } = corners;
+        Quadrant {
+            top: Horizontal {
+                left: top_left,
+                right: top_right,
+            },
+            bottom: Horizontal {
+                left: bottom_left,
+                right: bottom_right,
+            },
+        }
+    }
+}
+
+/// Values at each corner, at the middle of each edge, and at the center.
+#[derive(Clone, Copy, Debug)]
+pub struct Nonant<T> {
+    pub top_left: T,
+    pub top: T,
+    pub top_right: T,
+    pub left: T,
+    pub center: T,
+    pub right: T,
+    pub bottom_left: T,
+    pub bottom: T,
+    pub bottom_right: T,
+}
+
+impl<T> Nonant<T> {
+    /// Constructs a nonant with the same value at each anchor.
+    pub fn uniform(value: T) -> Self
+    where
+        T: Clone,
+    {
+        Nonant {
+            top_left: value.clone(),
+            top: value.clone(),
+            top_right: value.clone(),
+            left: value.clone(),
+            center: value.clone(),
+            right: value.clone(),
+            bottom_left: value.clone(),
+            bottom: value.clone(),
+            bottom_right: value,
+        }
+    }
+
+    /// Constructs a nonant from its corners, the middles of its edges, and its center.
+    pub fn merge(corners: Corners<T>, edges: Square<T>, center: T) -> Self {
+        let Corners {
+            top_left,
+            top_right,
+            bottom_left,
+            bottom_right,
+        } = corners;
+        let Square {
+            left,
+            right,
+            top,
+            bottom,
+        } = edges;
+        Nonant {
+            top_left,
+            top,
+            top_right,
+            left,
+            center,
+            right,
+            bottom_left,
+            bottom,
+            bottom_right,
+        }
+    }
+
+    /// Splits a nonant into its corners, the middles of its edges, and its center.
+    pub fn split(self) -> (Corners<T>, Square<T>, T) {
+        let Nonant {
+            top_left,
+            top,
+            top_right,
+            left,
+            center,
+            right,
+            bottom_left,
+            bottom,
+            bottom_right,
+        } = self;
+        (
+            Corners {
+                top_left,
+                top_right,
+                bottom_left,
+                bottom_right,
+            },
+            Square {
+                left,
+                right,
+                top,
+                bottom,
+            },
+            center,
+        )
+    }
+
+    pub fn aligned(
+        &self,
+        vertical: valued::VerticalAnchor,
+        horizontal: valued::HorizontalAnchor,
+    ) -> &T {
+        use valued::{HorizontalAnchor as H, VerticalAnchor as V};
+
+        match (vertical, horizontal) {
+            (V::Top, H::Left) => &self.top_left,
+            (V::Top, H::Center) => &self.top,
+            (V::Top, H::Right) => &self.top_right,
+            (V::Center, H::Left) => &self.left,
+            (V::Center, H::Center) => &self.center,
+            (V::Center, H::Right) => &self.right,
+            (V::Bottom, H::Left) => &self.bottom_left,
+            (V::Bottom, H::Center) => &self.bottom,
+            (V::Bottom, H::Right) => &self.bottom_right,
+        }
+    }
+
+    pub fn aligned_mut(
+        &mut self,
+        vertical: valued::VerticalAnchor,
+        horizontal: valued::HorizontalAnchor,
+    ) -> &mut T {
+        use valued::{HorizontalAnchor as H, VerticalAnchor as V};
+
+        match (vertical, horizontal) {
+            (V::Top, H::Left) => &mut self.top_left,
+            (V::Top, H::Center) => &mut self.top,
+            (V::Top, H::Right) => &mut self.top_right,
+            (V::Center, H::Left) => &mut self.left,
+            (V::Center, H::Center) => &mut self.center,
+            (V::Center, H::Right) => &mut self.right,
+            (V::Bottom, H::Left) => &mut self.bottom_left,
+            (V::Bottom, H::Center) => &mut self.bottom,
+            (V::Bottom, H::Right) => &mut self.bottom_right,
+        }
+    }
+
+    pub fn anchored_at<V, H>(&self) -> &T
+    where
+        V: typed::VerticalAnchor,
+        H: typed::HorizontalAnchor,
+    {
+        self.aligned(V::VALUE, H::VALUE)
+    }
+
+    pub fn anchored_at_mut<V, H>(&mut self) -> &mut T
+    where
+        V: typed::VerticalAnchor,
+        H: typed::HorizontalAnchor,
+    {
+        self.aligned_mut(V::VALUE, H::VALUE)
+    }
+
+    pub fn as_ref(&self) -> Nonant<&T> {
+        Nonant {
+            top_left: &self.top_left,
+            top: &self.top,
+            top_right: &self.top_right,
+            left: &self.left,
+            center: &self.center,
+            right: &self.right,
+            bottom_left: &self.bottom_left,
+            bottom: &self.bottom,
+            bottom_right: &self.bottom_right,
+        }
+    }
+
+    pub fn as_mut(&mut self) -> Nonant<&mut T> {
+        Nonant {
+            top_left: &mut self.top_left,
+            top: &mut self.top,
+            top_right: &mut self.top_right,
+            left: &mut self.left,
+            center: &mut self.center,
+            right: &mut self.right,
+            bottom_left: &mut self.bottom_left,
+            bottom: &mut self.bottom,
+            bottom_right: &mut self.bottom_right,
+        }
+    }
+
+    pub fn map<U, F>(self, mut f: F) -> Nonant<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        Nonant {
+            top_left: f(self.top_left),
+            top: f(self.top),
+            top_right: f(self.top_right),
+            left: f(self.left),
+            center: f(self.center),
+            right: f(self.right),
+            bottom_left: f(self.bottom_left),
+            bottom: f(self.bottom),
+            bottom_right: f(self.bottom_right),
+        }
+    }
+
+    pub fn zip<U>(self, other: Nonant<U>) -> Nonant<(T, U)> {
+        Nonant {
+            top_left: (self.top_left, other.top_left),
+            top: (self.top, other.top),
+            top_right: (self.top_right, other.top_right),
+            left: (self.left, other.left),
+            center: (self.center, other.center),
+            right: (self.right, other.right),
+            bottom_left: (self.bottom_left, other.bottom_left),
+            bottom: (self.bottom, other.bottom),
+            bottom_right: (self.bottom_right, other.bottom_right),
+        }
+    }
+}
+
+impl<T> IntoIterator for Nonant<T> {
+    type Item = T;
+    type IntoIter = array::IntoIter<T, 9>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter([
+            self.top_left,
+            self.top,
+            self.top_right,
+            self.left,
+            self.center,
+            self.right,
+            self.bottom_left,
+            self.bottom,
+            self.bottom_right,
+        ])
+    }
+}
+
+impl<T> HorizontalEnvelope<T> for Nonant<T> {
+    fn left(&self) -> &T {
+        &self.left
+    }
+
+    fn right(&self) -> &T {
+        &self.right
+    }
+}
+
+impl<T> VerticalEnvelope<T> for Nonant<T> {
+    fn top(&self) -> &T {
+        &self.top
+    }
+
+    fn bottom(&self) -> &T {
+        &self.bottom
+    }
+}
+
+impl<T> CornerEnvelope<T> for Nonant<T> {
+    fn top_left(&self) -> &T {
+        &self.top_left
+    }
+
+    fn top_right(&self) -> &T {
+        &self.top_right
+    }
+
+    fn bottom_left(&self) -> &T {
+        &self.bottom_left
+    }
+
+    fn bottom_right(&self) -> &T {
+        &self.bottom_right
+    }
+}
+
+impl<T> Rotate for Nonant<T> {
+    fn rotate_clockwise(self) -> Self {
+        let Nonant {
+            top_left,
+            top,
+            top_right,
+            left,
+            center,
+            right,
+            bottom_left,
+            bottom,
+            bottom_right,
+        } = self;
+        Nonant {
+            top_left: bottom_left,
+            top: left,
+            top_right: top_left,
+            left: bottom,
+            center,
+            right: top,
+            bottom_left: bottom_right,
+            bottom: right,
+            bottom_right: top_right,
+        }
+    }
+}
+
+impl<T> Invert for Nonant<T> {
+    fn invert(self) -> Self {
+        let Nonant {
+            top_left,
+            top,
+            top_right,
+            left,
+            center,
+            right,
+            bottom_left,
+            bottom,
+            bottom_right,
+        } = self;
+        Nonant {
+            top_left: bottom_right,
+            top: bottom,
+            top_right: bottom_left,
+            left: right,
+            center,
+            right: left,
+            bottom_left: top_right,
+            bottom: top,
+            bottom_right: top_left,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Axial<T> {
     pub horizontal: T,
     pub vertical: T,
 }
 
+impl<T> Axial<T> {
+    pub fn as_ref(&self) -> Axial<&T> {
+        Axial {
+            horizontal: &self.horizontal,
+            vertical: &self.vertical,
+        }
+    }
+
+    pub fn as_mut(&mut self) -> Axial<&mut T> {
+        Axial {
+            horizontal: &mut self.horizontal,
+            vertical: &mut self.vertical,
+        }
+    }
+
+    pub fn map<U, F>(self, mut f: F) -> Axial<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        Axial {
+            horizontal: f(self.horizontal),
+            vertical: f(self.vertical),
+        }
+    }
+
+    pub fn zip<U>(self, other: Axial<U>) -> Axial<(T, U)> {
+        Axial {
+            horizontal: (self.horizontal, other.horizontal),
+            vertical: (self.vertical, other.vertical),
+        }
+    }
+}
+
+impl<T> IntoIterator for Axial<T> {
+    type Item = T;
+    type IntoIter = array::IntoIter<T, 2>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter([self.horizontal, self.vertical])
+    }
+}
+
 impl<T> AxialEnvelope<T> for Axial<T> {
     fn horizontal(&self) -> &T {
         &self.horizontal
@@ -234,3 +1168,46 @@ impl<T> AxialEnvelope<T> for Axial<T> {
         &self.vertical
     }
 }
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Extent {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Extent {
+    pub fn length(&self, axis: valued::Axis) -> usize {
+        match axis {
+            valued::Axis::LeftRight => self.width,
+            valued::Axis::TopBottom => self.height,
+        }
+    }
+}
+
+impl AxialEnvelope<usize> for Extent {
+    fn horizontal(&self) -> &usize {
+        &self.width
+    }
+
+    fn vertical(&self) -> &usize {
+        &self.height
+    }
+}
+
+impl From<Axial<usize>> for Extent {
+    fn from(axial: Axial<usize>) -> Self {
+        Extent {
+            width: axial.horizontal,
+            height: axial.vertical,
+        }
+    }
+}
+
+impl From<Extent> for Axial<usize> {
+    fn from(extent: Extent) -> Self {
+        Axial {
+            horizontal: extent.width,
+            vertical: extent.height,
+        }
+    }
+}
@@ -1,5 +1,5 @@
-use crate::align::decoder::{AxialDecoder, HorizontalDecoder, VerticalDecoder};
-use crate::align::{valued, AxialEnvelope, HorizontalEnvelope, VerticalEnvelope};
+use crate::align::decoder::{AxialDecoder, CornerDecoder, HorizontalDecoder, VerticalDecoder};
+use crate::align::{valued, AxialEnvelope, CornerEnvelope, HorizontalEnvelope, VerticalEnvelope};
 
 pub type OrthogonalOrigin<A> = <<A as Axis>::Orthogonal as Axis>::Origin;
 
@@ -10,6 +10,18 @@ pub trait Axis: AxialDecoder + Sized {
     const VALUE: valued::Axis;
 }
 
+/// A function that is generic over typed axes.
+///
+/// Closures cannot be generic, so code that dispatches a runtime axis to typed APIs implements
+/// this trait instead. See `valued::Axis::with_typed`.
+pub trait AxisFunction {
+    type Output;
+
+    fn call<A>(self) -> Self::Output
+    where
+        A: Axis;
+}
+
 pub enum LeftRight {}
 pub enum TopBottom {}
 
@@ -39,6 +51,28 @@ impl Axis for TopBottom {
     const VALUE: valued::Axis = valued::Axis::TopBottom;
 }
 
+/// A function that is generic over typed alignments.
+///
+/// See `valued::Alignment::with_typed`.
+pub trait AlignmentFunction {
+    type Output;
+
+    fn call<L>(self) -> Self::Output
+    where
+        L: Alignment;
+}
+
+/// A function that is generic over typed corners.
+///
+/// See `valued::Corner::with_typed`.
+pub trait CornerFunction {
+    type Output;
+
+    fn call<K>(self) -> Self::Output
+    where
+        K: Corner;
+}
+
 pub trait Alignment: Sized {
     type Opposite: Coaxial<Self::Axis>;
     type Axis: Axis;
@@ -120,6 +154,113 @@ impl VerticalDecoder for Bottom {
     }
 }
 
+/// The center between alignments along either axis.
+pub enum Center {}
+
+/// A horizontal alignment or the center between the left and right.
+pub trait HorizontalAnchor {
+    const VALUE: valued::HorizontalAnchor;
+}
+
+impl HorizontalAnchor for Left {
+    const VALUE: valued::HorizontalAnchor = valued::HorizontalAnchor::Left;
+}
+
+impl HorizontalAnchor for Center {
+    const VALUE: valued::HorizontalAnchor = valued::HorizontalAnchor::Center;
+}
+
+impl HorizontalAnchor for Right {
+    const VALUE: valued::HorizontalAnchor = valued::HorizontalAnchor::Right;
+}
+
+/// A vertical alignment or the center between the top and bottom.
+pub trait VerticalAnchor {
+    const VALUE: valued::VerticalAnchor;
+}
+
+impl VerticalAnchor for Top {
+    const VALUE: valued::VerticalAnchor = valued::VerticalAnchor::Top;
+}
+
+impl VerticalAnchor for Center {
+    const VALUE: valued::VerticalAnchor = valued::VerticalAnchor::Center;
+}
+
+impl VerticalAnchor for Bottom {
+    const VALUE: valued::VerticalAnchor = valued::VerticalAnchor::Bottom;
+}
+
+/// A corner at which a horizontal and vertical alignment meet.
+pub trait Corner: CornerDecoder + Sized {
+    type Horizontal: HorizontalAlignment;
+    type Vertical: VerticalAlignment;
+    type Opposite: Corner;
+
+    const VALUE: valued::Corner;
+}
+
+pub enum TopLeft {}
+pub enum TopRight {}
+pub enum BottomLeft {}
+pub enum BottomRight {}
+
+impl Corner for TopLeft {
+    type Horizontal = Left;
+    type Vertical = Top;
+    type Opposite = BottomRight;
+
+    const VALUE: valued::Corner = valued::Corner::TopLeft;
+}
+
+impl CornerDecoder for TopLeft {
+    fn aligned<T>(data: &impl CornerEnvelope<T>) -> &T {
+        data.top_left()
+    }
+}
+
+impl Corner for TopRight {
+    type Horizontal = Right;
+    type Vertical = Top;
+    type Opposite = BottomLeft;
+
+    const VALUE: valued::Corner = valued::Corner::TopRight;
+}
+
+impl CornerDecoder for TopRight {
+    fn aligned<T>(data: &impl CornerEnvelope<T>) -> &T {
+        data.top_right()
+    }
+}
+
+impl Corner for BottomLeft {
+    type Horizontal = Left;
+    type Vertical = Bottom;
+    type Opposite = TopRight;
+
+    const VALUE: valued::Corner = valued::Corner::BottomLeft;
+}
+
+impl CornerDecoder for BottomLeft {
+    fn aligned<T>(data: &impl CornerEnvelope<T>) -> &T {
+        data.bottom_left()
+    }
+}
+
+impl Corner for BottomRight {
+    type Horizontal = Right;
+    type Vertical = Bottom;
+    type Opposite = TopLeft;
+
+    const VALUE: valued::Corner = valued::Corner::BottomRight;
+}
+
+impl CornerDecoder for BottomRight {
+    fn aligned<T>(data: &impl CornerEnvelope<T>) -> &T {
+        data.bottom_right()
+    }
+}
+
 pub trait Coaxial<A>: Alignment<Axis = A>
 where
     A: Axis,
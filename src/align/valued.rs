@@ -1,3 +1,5 @@
+use crate::align::typed;
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Axis {
     LeftRight,
@@ -11,6 +13,17 @@ impl Axis {
             Axis::TopBottom => Alignment::TOP,
         }
     }
+
+    /// Calls a function with the typed axis that corresponds to the axis.
+    pub fn with_typed<F>(self, f: F) -> F::Output
+    where
+        F: typed::AxisFunction,
+    {
+        match self {
+            Axis::LeftRight => f.call::<typed::LeftRight>(),
+            Axis::TopBottom => f.call::<typed::TopBottom>(),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -42,6 +55,19 @@ impl Alignment {
         }
     }
 
+    /// Calls a function with the typed alignment that corresponds to the alignment.
+    pub fn with_typed<F>(self, f: F) -> F::Output
+    where
+        F: typed::AlignmentFunction,
+    {
+        match self {
+            Alignment::LEFT => f.call::<typed::Left>(),
+            Alignment::RIGHT => f.call::<typed::Right>(),
+            Alignment::TOP => f.call::<typed::Top>(),
+            Alignment::BOTTOM => f.call::<typed::Bottom>(),
+        }
+    }
+
     pub fn is_left(&self) -> bool {
         matches!(self, Alignment::Horizontal(HorizontalAlignment::Left))
     }
@@ -107,6 +133,114 @@ impl VerticalAlignment {
     }
 }
 
+/// A horizontal alignment or the center between the left and right.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum HorizontalAnchor {
+    Left,
+    Center,
+    Right,
+}
+
+impl HorizontalAnchor {
+    #[must_use]
+    pub const fn opposite(&self) -> Self {
+        match *self {
+            HorizontalAnchor::Left => HorizontalAnchor::Right,
+            HorizontalAnchor::Center => HorizontalAnchor::Center,
+            HorizontalAnchor::Right => HorizontalAnchor::Left,
+        }
+    }
+}
+
+impl From<HorizontalAlignment> for HorizontalAnchor {
+    fn from(alignment: HorizontalAlignment) -> Self {
+        match alignment {
+            HorizontalAlignment::Left => HorizontalAnchor::Left,
+            HorizontalAlignment::Right => HorizontalAnchor::Right,
+        }
+    }
+}
+
+/// A vertical alignment or the center between the top and bottom.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum VerticalAnchor {
+    Top,
+    Center,
+    Bottom,
+}
+
+impl VerticalAnchor {
+    #[must_use]
+    pub const fn opposite(&self) -> Self {
+        match *self {
+            VerticalAnchor::Top => VerticalAnchor::Bottom,
+            VerticalAnchor::Center => VerticalAnchor::Center,
+            VerticalAnchor::Bottom => VerticalAnchor::Top,
+        }
+    }
+}
+
+impl From<VerticalAlignment> for VerticalAnchor {
+    fn from(alignment: VerticalAlignment) -> Self {
+        match alignment {
+            VerticalAlignment::Top => VerticalAnchor::Top,
+            VerticalAlignment::Bottom => VerticalAnchor::Bottom,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Corner {
+    pub const fn new(vertical: VerticalAlignment, horizontal: HorizontalAlignment) -> Self {
+        match (vertical, horizontal) {
+            (VerticalAlignment::Top, HorizontalAlignment::Left) => Corner::TopLeft,
+            (VerticalAlignment::Top, HorizontalAlignment::Right) => Corner::TopRight,
+            (VerticalAlignment::Bottom, HorizontalAlignment::Left) => Corner::BottomLeft,
+            (VerticalAlignment::Bottom, HorizontalAlignment::Right) => Corner::BottomRight,
+        }
+    }
+
+    /// Gets the diagonally opposite corner.
+    #[must_use]
+    pub const fn opposite(&self) -> Self {
+        Corner::new(self.vertical().opposite(), self.horizontal().opposite())
+    }
+
+    /// Calls a function with the typed corner that corresponds to the corner.
+    pub fn with_typed<F>(self, f: F) -> F::Output
+    where
+        F: typed::CornerFunction,
+    {
+        match self {
+            Corner::TopLeft => f.call::<typed::TopLeft>(),
+            Corner::TopRight => f.call::<typed::TopRight>(),
+            Corner::BottomLeft => f.call::<typed::BottomLeft>(),
+            Corner::BottomRight => f.call::<typed::BottomRight>(),
+        }
+    }
+
+    pub const fn horizontal(&self) -> HorizontalAlignment {
+        match *self {
+            Corner::TopLeft | Corner::BottomLeft => HorizontalAlignment::Left,
+            Corner::TopRight | Corner::BottomRight => HorizontalAlignment::Right,
+        }
+    }
+
+    pub const fn vertical(&self) -> VerticalAlignment {
+        match *self {
+            Corner::TopLeft | Corner::TopRight => VerticalAlignment::Top,
+            Corner::BottomLeft | Corner::BottomRight => VerticalAlignment::Bottom,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum AxialAlignment {
     LeftRight(VerticalAlignment),
@@ -0,0 +1,162 @@
+use std::cmp;
+use std::time::Duration;
+
+use crate::block::{self, Block, Fill};
+use crate::content::{Content, FromCell};
+
+/// A position in a sequence of frames given by an index or the time elapsed since the sequence
+/// began.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Tick {
+    Index(usize),
+    Elapsed(Duration),
+}
+
+impl From<usize> for Tick {
+    fn from(index: usize) -> Self {
+        Tick::Index(index)
+    }
+}
+
+impl From<Duration> for Tick {
+    fn from(elapsed: Duration) -> Self {
+        Tick::Elapsed(elapsed)
+    }
+}
+
+/// An ordered and repeating sequence of blocks displayed at a fixed interval.
+///
+/// Frames are padded at the right and bottom to the same dimensions, so any frame can be
+/// overlayed onto the same region of a layout.
+#[derive(Clone, Debug)]
+pub struct Frames<C = String>
+where
+    C: Content,
+{
+    frames: Vec<Block<C>>,
+    interval: Duration,
+}
+
+impl<C> Frames<C>
+where
+    C: Content,
+{
+    pub fn new<I>(frames: I, interval: Duration) -> Self
+    where
+        I: IntoIterator<Item = Block<C>>,
+    {
+        let frames: Vec<_> = frames.into_iter().collect();
+        let (width, height) = frames.iter().fold((0, 0), |(width, height), frame| {
+            (
+                cmp::max(width, frame.width()),
+                cmp::max(height, frame.height()),
+            )
+        });
+        Frames {
+            frames: frames
+                .into_iter()
+                .map(|frame| {
+                    frame
+                        .pad_to_width_at_right(width)
+                        .pad_to_height_at_bottom(height)
+                })
+                .collect(),
+            interval,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Gets the frame at the given tick.
+    ///
+    /// Sequences repeat, so indices and elapsed times beyond the end of the sequence wrap around
+    /// to its beginning. If the interval is zero, then elapsed times always select the first
+    /// frame. Returns `None` if there are no frames.
+    pub fn frame_at(&self, tick: impl Into<Tick>) -> Option<&Block<C>> {
+        let index = match tick.into() {
+            Tick::Index(index) => index,
+            Tick::Elapsed(elapsed) => elapsed
+                .as_nanos()
+                .checked_div(self.interval.as_nanos())
+                .map_or(0, |index| {
+                    (index % self.frames.len().max(1) as u128) as usize
+                }),
+        };
+        self.frames.get(index.checked_rem(self.frames.len())?)
+    }
+
+    pub fn iter(&self) -> impl '_ + ExactSizeIterator<Item = &Block<C>> {
+        self.frames.iter()
+    }
+
+    pub fn into_frames(self) -> Vec<Block<C>> {
+        self.frames
+    }
+}
+
+impl<C> Frames<C>
+where
+    C: Content + FromCell<char>,
+{
+    /// Constructs a sequence of single-column frames from the given glyphs.
+    pub fn from_glyphs(glyphs: &str, interval: Duration) -> Self {
+        Frames::new(
+            glyphs
+                .chars()
+                .map(|point| Block::with_dimensions(1, 1).fill(block::from_fn(|_, _| point))),
+            interval,
+        )
+    }
+
+    /// Constructs a spinner drawn with braille patterns.
+    pub fn spinner() -> Self {
+        Frames::from_glyphs(
+            "\u{280B}\u{2819}\u{2839}\u{2838}\u{283C}\u{2834}\u{2826}\u{2827}\u{2807}\u{280F}",
+            Duration::from_millis(80),
+        )
+    }
+
+    /// Constructs a spinner drawn with ASCII characters.
+    pub fn ascii_spinner() -> Self {
+        Frames::from_glyphs("|/-\\", Duration::from_millis(100))
+    }
+
+    /// Constructs an ellipsis that grows from zero to three periods.
+    pub fn ellipsis() -> Self {
+        Frames::new(
+            (0..=3).map(|n| {
+                Block::with_dimensions(3, 1).fill(block::from_fn(move |x, _| {
+                    if x < n {
+                        '.'
+                    }
+                    else {
+                        ' '
+                    }
+                }))
+            }),
+            Duration::from_millis(300),
+        )
+    }
+}
+
+impl<C> IntoIterator for Frames<C>
+where
+    C: Content,
+{
+    type Item = Block<C>;
+    type IntoIter = std::vec::IntoIter<Block<C>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.frames.into_iter()
+    }
+}
@@ -0,0 +1,213 @@
+use std::io::{self, Write};
+
+use crate::content::Style;
+use crate::{ColorDepth, RenderContext};
+
+// The colors of the basic ANSI palette as presented by xterm.
+const ANSI16: [[u8; 3]; 16] = [
+    [0, 0, 0],
+    [205, 0, 0],
+    [0, 205, 0],
+    [205, 205, 0],
+    [0, 0, 238],
+    [205, 0, 205],
+    [0, 205, 205],
+    [229, 229, 229],
+    [127, 127, 127],
+    [255, 0, 0],
+    [0, 255, 0],
+    [255, 255, 0],
+    [92, 92, 255],
+    [255, 0, 255],
+    [0, 255, 255],
+    [255, 255, 255],
+];
+
+// The levels of each channel in the color cube of the extended ANSI palette.
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance(from: [u8; 3], to: [u8; 3]) -> u32 {
+    from.iter()
+        .zip(to.iter())
+        .map(|(&from, &to)| {
+            let difference = i32::from(from) - i32::from(to);
+            (difference * difference) as u32
+        })
+        .sum()
+}
+
+/// Gets the index of the nearest color in the basic ANSI palette.
+pub fn nearest_ansi16(color: [u8; 3]) -> u8 {
+    (0..16u8)
+        .min_by_key(|&index| distance(color, ANSI16[usize::from(index)]))
+        .unwrap_or(0)
+}
+
+/// Gets the index of the nearest color in the color cube or grayscale ramp of the extended ANSI
+/// palette.
+///
+/// The basic colors at the start of the palette are not used, as terminals often customize them.
+pub fn nearest_ansi256(color: [u8; 3]) -> u8 {
+    let level = |channel: u8| {
+        (0..6u8)
+            .min_by_key(|&index| (i32::from(CUBE[usize::from(index)]) - i32::from(channel)).abs())
+            .unwrap_or(0)
+    };
+    let [r, g, b] = color.map(level);
+    let cube = [r, g, b].map(|index| CUBE[usize::from(index)]);
+    let average = color.iter().map(|&channel| u32::from(channel)).sum::<u32>() / 3;
+    let gray = (average.saturating_sub(3) / 10).min(23) as u8;
+    let level = 8 + (10 * gray);
+    if distance(color, [level; 3]) < distance(color, cube) {
+        232 + gray
+    }
+    else {
+        16 + (36 * r) + (6 * g) + b
+    }
+}
+
+fn write_color(
+    target: &mut impl Write,
+    separator: &str,
+    color: [u8; 3],
+    depth: ColorDepth,
+    is_background: bool,
+) -> io::Result<()> {
+    let base = if is_background { 40 } else { 30 };
+    match depth {
+        ColorDepth::Ansi16 => {
+            let index = nearest_ansi16(color);
+            let code = if index < 8 {
+                base + index
+            }
+            else {
+                base + 60 + (index - 8)
+            };
+            write!(target, "{}{}", separator, code)
+        }
+        ColorDepth::Ansi256 => {
+            write!(
+                target,
+                "{}{};5;{}",
+                separator,
+                base + 8,
+                nearest_ansi256(color)
+            )
+        }
+        ColorDepth::TrueColor => {
+            let [r, g, b] = color;
+            write!(target, "{}{};2;{};{};{}", separator, base + 8, r, g, b)
+        }
+    }
+}
+
+/// A style that applies colors and font attributes using ANSI escape sequences.
+///
+/// Colors are written as 24-bit colors unless the render context advertises a lesser color depth,
+/// in which case each color is written as the nearest color in the 256- or 16-color ANSI palette.
+/// Fully transparent colors are not written.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct AnsiStyle {
+    pub foreground: Option<[u8; 3]>,
+    pub background: Option<[u8; 3]>,
+    pub is_bold: bool,
+    pub is_italic: bool,
+    pub is_underlined: bool,
+}
+
+impl AnsiStyle {
+    // Writes the SGR parameters that change the attributes of this style into those of the next.
+    fn write_difference(
+        &self,
+        next: &Self,
+        target: &mut impl Write,
+        depth: ColorDepth,
+    ) -> io::Result<()> {
+        if next == self {
+            return Ok(());
+        }
+        let mut separator = "";
+        target.write_all(b"\x1b[")?;
+        for (from, to, set, reset) in [
+            (self.is_bold, next.is_bold, "1", "22"),
+            (self.is_italic, next.is_italic, "3", "23"),
+            (self.is_underlined, next.is_underlined, "4", "24"),
+        ] {
+            if from != to {
+                write!(target, "{}{}", separator, if to { set } else { reset })?;
+                separator = ";";
+            }
+        }
+        for (from, to, is_background, reset) in [
+            (self.foreground, next.foreground, false, "39"),
+            (self.background, next.background, true, "49"),
+        ] {
+            if from != to {
+                match to {
+                    Some(color) => write_color(target, separator, color, depth, is_background)?,
+                    None => write!(target, "{}{}", separator, reset)?,
+                }
+                separator = ";";
+            }
+        }
+        target.write_all(b"m")
+    }
+}
+
+impl Style for AnsiStyle {
+    fn write_prefix(&self, target: &mut impl Write) -> io::Result<()> {
+        AnsiStyle::default().write_difference(self, target, ColorDepth::TrueColor)
+    }
+
+    fn write_suffix(&self, target: &mut impl Write) -> io::Result<()> {
+        if *self == AnsiStyle::default() {
+            Ok(())
+        }
+        else {
+            target.write_all(b"\x1b[0m")
+        }
+    }
+
+    // Colors are downgraded to the nearest color in the palette of the context.
+    fn write_prefix_with(
+        &self,
+        target: &mut impl Write,
+        context: &RenderContext,
+    ) -> io::Result<()> {
+        if context.is_styled {
+            AnsiStyle::default().write_difference(self, target, context.color_depth)
+        }
+        else {
+            Ok(())
+        }
+    }
+
+    // Only the attributes that change are written between adjacent fragments and attributes are
+    // reset once after the last fragment.
+    fn write_transition_with(
+        &self,
+        next: Option<&Self>,
+        target: &mut impl Write,
+        context: &RenderContext,
+    ) -> io::Result<()> {
+        if !context.is_styled {
+            return Ok(());
+        }
+        match next {
+            Some(next) if *next != AnsiStyle::default() => {
+                self.write_difference(next, target, context.color_depth)
+            }
+            _ => self.write_suffix(target),
+        }
+    }
+
+    fn compose(&self, over: &Self) -> Self {
+        AnsiStyle {
+            foreground: self.foreground.or(over.foreground),
+            background: self.background.or(over.background),
+            is_bold: self.is_bold || over.is_bold,
+            is_italic: self.is_italic || over.is_italic,
+            is_underlined: self.is_underlined || over.is_underlined,
+        }
+    }
+}
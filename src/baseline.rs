@@ -0,0 +1,69 @@
+use std::cmp;
+
+use crate::block::Block;
+use crate::content::Content;
+
+/// A block with a marked baseline row.
+///
+/// Blocks joined at their baselines are padded such that their baselines coincide. The baseline
+/// may lie beyond the last row of a block, in which case the block is treated as if it were
+/// extended with blank rows.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct BaselineBlock<C = String>
+where
+    C: Content,
+{
+    block: Block<C>,
+    baseline: usize,
+}
+
+impl<C> BaselineBlock<C>
+where
+    C: Content,
+{
+    pub fn new(block: Block<C>, baseline: usize) -> Self {
+        BaselineBlock { block, baseline }
+    }
+
+    pub fn baseline(&self) -> usize {
+        self.baseline
+    }
+
+    pub fn get(&self) -> &Block<C> {
+        &self.block
+    }
+
+    pub fn into_block(self) -> Block<C> {
+        self.block
+    }
+
+    #[must_use]
+    pub fn pad_at_top(self, height: usize) -> Self {
+        BaselineBlock {
+            block: self.block.pad_at_top(height),
+            baseline: self.baseline + height,
+        }
+    }
+
+    #[must_use]
+    pub fn join_left_to_right_at_baseline(self, right: Self) -> Self {
+        let baseline = cmp::max(self.baseline, right.baseline);
+        let (left, right) = (
+            self.block.pad_at_top(baseline - self.baseline),
+            right.block.pad_at_top(baseline - right.baseline),
+        );
+        BaselineBlock {
+            block: left.join_left_to_right_at_top(right),
+            baseline,
+        }
+    }
+}
+
+impl<C> From<BaselineBlock<C>> for Block<C>
+where
+    C: Content,
+{
+    fn from(block: BaselineBlock<C>) -> Self {
+        block.into_block()
+    }
+}
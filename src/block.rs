@@ -1,10 +1,74 @@
 use std::borrow::Cow;
 use std::cmp;
+use std::convert::Infallible;
+use std::fmt::{self, Debug, Display, Formatter};
 use std::io::{self, Write};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::{iter, mem, vec};
 
-use crate::align::{typed, valued};
-use crate::content::{Congruent, Content, ContentSlice as _, Grapheme, Layer, Style, Styled};
-use crate::Render;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::align::{typed, valued, Axial, AxialEnvelope as _, Extent};
+use crate::baseline::BaselineBlock;
+use crate::content::{
+    self, CachedWidth, Cell, Congruent, Content, ContentSlice as _, FromCell, Grapheme,
+    Interpolate, Layer, LineBreakPolicy, LineBuffer, OverlayPolicy, Resolved, SanitizePolicy,
+    Style, Styled,
+};
+use crate::error::{CongruenceError, FillError, PageError};
+use crate::lazy::LazyBlock;
+use crate::mask::Mask;
+use crate::primitive::AxisVector;
+#[cfg(feature = "terminal")]
+use crate::terminal::{self, FitPolicy};
+use crate::wrap::{self, WrapPolicy};
+use crate::{Render, RenderContext};
+
+#[cfg(feature = "bidi")]
+fn is_isolated() -> bool {
+    content::Isolation::get() == content::Isolation::FirstStrong
+}
+
+#[cfg(not(feature = "bidi"))]
+fn is_isolated() -> bool {
+    false
+}
+
+// Gets the vertical presentation form of CJK and general punctuation.
+fn vertical_form(glyph: &str) -> Option<char> {
+    Some(match glyph {
+        "\u{3001}" => '\u{FE11}',
+        "\u{3002}" => '\u{FE12}',
+        "\u{FF0C}" => '\u{FE10}',
+        "\u{FF1A}" => '\u{FE13}',
+        "\u{FF1B}" => '\u{FE14}',
+        "\u{FF01}" => '\u{FE15}',
+        "\u{FF1F}" => '\u{FE16}',
+        "\u{2026}" => '\u{FE19}',
+        "\u{2014}" => '\u{FE31}',
+        "\u{FF08}" => '\u{FE35}',
+        "\u{FF09}" => '\u{FE36}',
+        "\u{FF5B}" => '\u{FE37}',
+        "\u{FF5D}" => '\u{FE38}',
+        "\u{3014}" => '\u{FE39}',
+        "\u{3015}" => '\u{FE3A}',
+        "\u{3010}" => '\u{FE3B}',
+        "\u{3011}" => '\u{FE3C}',
+        "\u{300A}" => '\u{FE3D}',
+        "\u{300B}" => '\u{FE3E}',
+        "\u{3008}" => '\u{FE3F}',
+        "\u{3009}" => '\u{FE40}',
+        "\u{300C}" => '\u{FE41}',
+        "\u{300D}" => '\u{FE42}',
+        "\u{300E}" => '\u{FE43}',
+        "\u{300F}" => '\u{FE44}',
+        _ => {
+            return None;
+        }
+    })
+}
 
 pub trait WithLength<A>: Sized
 where
@@ -13,6 +77,30 @@ where
     fn with_length(length: usize, width: usize) -> Self;
 }
 
+/// Operations along the axis `A` at the origins of the axes.
+///
+/// This trait consolidates the bounds required to compose blocks along an axis, so code can be
+/// written once and used with either axis via functions like `Block::pad_at_origin` and
+/// `Block::join_at_origin`. Blocks are padded at the origin of `A` (the left or top) and joined
+/// along `A` at the origin of the orthogonal axis. This trait is implemented for all types that
+/// implement its supertraits.
+pub trait AxialBlock<A>:
+    Join<A, typed::OrthogonalOrigin<A>> + Pad<A::Origin> + PadToLength<A, A::Origin> + WithLength<A>
+where
+    A: typed::Axis,
+{
+}
+
+impl<T, A> AxialBlock<A> for T
+where
+    T: Join<A, typed::OrthogonalOrigin<A>>
+        + Pad<A::Origin>
+        + PadToLength<A, A::Origin>
+        + WithLength<A>,
+    A: typed::Axis,
+{
+}
+
 pub trait Fill<C, T>
 where
     C: Content,
@@ -22,6 +110,17 @@ where
     fn fill(self, filler: T) -> Self::Output;
 }
 
+#[derive(Clone, Copy, Debug)]
+pub struct FromFn<F>(F);
+
+pub fn from_fn<T, F>(f: F) -> FromFn<F>
+where
+    T: Cell,
+    F: FnMut(usize, usize) -> T,
+{
+    FromFn(f)
+}
+
 pub trait Join<A, L>: Sized
 where
     A: typed::Axis,
@@ -48,21 +147,159 @@ where
     fn pad_to_length(self, length: usize) -> Self;
 }
 
+pub trait Trim<L>: Sized
+where
+    L: typed::Alignment,
+{
+    #[must_use]
+    fn trim(self) -> Self;
+}
+
+pub trait Truncate<A, L>: Sized
+where
+    A: typed::Axis,
+    L: typed::Coaxial<A>,
+{
+    #[must_use]
+    fn truncate(self, length: usize, ellipsis: Option<Grapheme>) -> Self;
+}
+
 // NOTE: These functions are provided by a trait rather than inherent functions to avoid ambiguity
 //       with the statically aligned traits. For example, `Pad::pad` and `DynamicallyAligned::pad`
 //       are ambiguous with non-qualified method syntax. Instead, users must choose which functions
 //       are in scope.
-pub trait DynamicallyAligned: Sized {
+//
+//       This trait is sealed, because its operations cannot be expressed in terms of one another
+//       and so new operations cannot be provided to external implementations.
+pub trait DynamicallyAligned: sealed::Sealed + Sized {
     fn with_length(axis: valued::Axis, length: usize, width: usize) -> Self;
 
+    fn filled_with_length<C, T>(axis: valued::Axis, length: usize, width: usize, filler: T) -> Self
+    where
+        Self: Fill<C, T, Output = Self>,
+        C: Content,
+    {
+        Self::with_length(axis, length, width).fill(filler)
+    }
+
     #[must_use]
     fn pad(self, alignment: impl Into<valued::Alignment>, length: usize) -> Self;
 
     #[must_use]
     fn pad_to_length(self, alignment: impl Into<valued::Alignment>, length: usize) -> Self;
 
+    #[must_use]
+    fn pad_to_length_centered(self, axis: valued::Axis, length: usize) -> Self;
+
+    /// Trims blank lines or columns at the given edge.
+    ///
+    /// This is named apart from the inherent `Block::trim`, which trims every edge.
+    #[must_use]
+    fn trim_edge(self, alignment: impl Into<valued::Alignment>) -> Self;
+
+    #[must_use]
+    fn truncate(
+        self,
+        alignment: impl Into<valued::Alignment>,
+        length: usize,
+        ellipsis: Option<Grapheme>,
+    ) -> Self;
+
     #[must_use]
     fn join(self, alignment: valued::AxialAlignment, other: Self) -> Self;
+
+    /// Joins the blocks with a separator between them.
+    #[must_use]
+    fn join_with_separator(
+        self,
+        alignment: valued::AxialAlignment,
+        separator: Self,
+        other: Self,
+    ) -> Self {
+        self.join(alignment, separator).join(alignment, other)
+    }
+
+    /// Overlays the block onto another block such that their given corners coincide.
+    #[must_use]
+    fn overlay_at(self, corner: valued::Corner, back: Self) -> Self;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+fn is_blank<C>(content: &C) -> bool
+where
+    C: Content,
+{
+    content
+        .to_graphemes()
+        .iter()
+        .all(|glyph| glyph.is_transparent() || *glyph == Grapheme::SPACE)
+}
+
+// Truncates content to at most `width` columns. A wide grapheme that is split by the truncation is
+// replaced by spaces, so content that is at least `width` columns wide is exactly `width` columns
+// wide afterward.
+fn truncate_columns<C>(content: C, width: usize) -> C
+where
+    C: Content,
+{
+    let widths: Vec<_> = content.to_graphemes().iter().map(Grapheme::width).collect();
+    if widths.iter().sum::<usize>() <= width {
+        return content;
+    }
+    let (mut n, mut sum) = (0, 0);
+    for glyph in widths {
+        if sum + glyph > width {
+            break;
+        }
+        sum += glyph;
+        n += 1;
+    }
+    C::concatenate(content.truncate(n), C::space().repeat(width - sum))
+}
+
+// Removes the first `width` columns of content. A wide grapheme that is split by the removal is
+// replaced by spaces.
+fn skip_columns<C>(content: C, width: usize) -> C
+where
+    C: Content,
+{
+    let (mut n, mut sum) = (0, 0);
+    for glyph in content.to_graphemes().iter().map(Grapheme::width) {
+        if sum >= width {
+            break;
+        }
+        sum += glyph;
+        n += 1;
+    }
+    C::concatenate(
+        C::space().repeat(sum.saturating_sub(width)),
+        content.skip(n),
+    )
+}
+
+// Compares rows of cells (see `Block::cells`) grapheme by grapheme.
+//
+// Yields the column and width of each pair of graphemes and whether they differ. The width is
+// that of the wider grapheme and a row that is shorter than the other is compared with no
+// grapheme.
+pub(crate) fn diff_cells<'r, 't>(
+    front: &'r [Grapheme<'t>],
+    back: &'r [Grapheme<'t>],
+) -> impl 'r + Iterator<Item = (usize, usize, bool)> {
+    let mut column = 0usize;
+    (0..cmp::max(front.len(), back.len())).map(move |x| {
+        let (glyph, other) = (front.get(x), back.get(x));
+        let width = cmp::max(
+            glyph.map_or(1, Grapheme::width),
+            other.map_or(1, Grapheme::width),
+        );
+        let cell = (column, width, glyph != other);
+        column += width;
+        cell
+    })
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -118,22 +355,82 @@ impl EmptyBlock {
     }
 }
 
+/// Truncation.
+impl EmptyBlock {
+    pub fn truncate_to_width(self, width: usize) -> Self {
+        EmptyBlock {
+            width: cmp::min(self.width, width),
+            height: self.height,
+        }
+    }
+
+    pub fn truncate_to_height(self, height: usize) -> Self {
+        EmptyBlock {
+            width: self.width,
+            height: cmp::min(self.height, height),
+        }
+    }
+}
+
 impl<C> Fill<C, C> for EmptyBlock
 where
     C: Content,
 {
-    type Output = Result<ContentBlock<C>, Self>;
+    type Output = Result<ContentBlock<C>, FillError>;
 
     fn fill(self, content: C) -> Self::Output {
+        self.tile(content.into_lines())
+    }
+}
+
+impl<'b, C> Fill<C, &'b Block<C>> for EmptyBlock
+where
+    C: Content,
+{
+    type Output = Result<ContentBlock<C>, FillError>;
+
+    fn fill(self, block: &'b Block<C>) -> Self::Output {
+        match block.inner {
+            ModalBlock::Content(ref block) if block.width() > 0 => self.tile(block.lines.to_vec()),
+            _ => self.fill(Grapheme::SPACE),
+        }
+    }
+}
+
+impl EmptyBlock {
+    fn fill_or_empty<C, T>(self, filler: T) -> ModalBlock<C>
+    where
+        C: Content,
+        Self: Fill<C, T, Output = Result<ContentBlock<C>, FillError>>,
+    {
+        self.fill(filler)
+            .map(ModalBlock::from)
+            .unwrap_or_else(|_| self.into())
+    }
+
+    fn tile<C>(self, mut lines: Vec<C>) -> Result<ContentBlock<C>, FillError>
+    where
+        C: Content,
+    {
         fn div_ceiling(a: usize, b: usize) -> usize {
             (0..a).step_by(b).len()
         }
 
+        let width = lines.width();
         if self.height == 0 {
-            Err(self)
+            Err(FillError::ZeroHeight)
+        }
+        else if width == 0 {
+            Err(FillError::EmptyFiller)
         }
         else {
-            let mut lines = content.into_lines();
+            // Narrower lines of the filler are padded so that each line can be repeated.
+            for line in lines.iter_mut() {
+                let n = width - line.width();
+                if n > 0 {
+                    *line = C::concatenate(line.clone(), C::space().repeat(n));
+                }
+            }
             let n = lines.len();
             if n < self.height {
                 let mut i = 0usize;
@@ -151,7 +448,7 @@ where
                     let n = div_ceiling(self.width, line.width());
                     *line = line.clone().repeat(n);
                 }
-                *line = line.clone().truncate(self.width);
+                *line = truncate_columns(line.clone(), self.width);
             }
             Ok(lines.into())
         }
@@ -162,26 +459,129 @@ impl<'t, C> Fill<C, Grapheme<'t>> for EmptyBlock
 where
     C: Content,
 {
-    type Output = Result<ContentBlock<C>, Self>;
+    type Output = Result<ContentBlock<C>, FillError>;
 
     fn fill(self, glyph: Grapheme<'t>) -> Self::Output {
         if self.height == 0 {
-            Err(self)
+            Err(FillError::ZeroHeight)
         }
         else {
+            let line = C::grapheme(glyph).repeat(self.width);
             Ok(ContentBlock {
-                lines: vec![C::grapheme(glyph).repeat(self.width); self.height],
+                width: CachedWidth::new(line.width()),
+                lines: vec![line; self.height].into(),
             })
         }
     }
 }
 
+impl<C, T, F> Fill<C, FromFn<F>> for EmptyBlock
+where
+    C: Content + FromCell<T>,
+    T: Cell,
+    F: FnMut(usize, usize) -> T,
+{
+    type Output = Result<ContentBlock<C>, FillError>;
+
+    fn fill(self, filler: FromFn<F>) -> Self::Output {
+        let FromFn(mut f) = filler;
+        if self.height == 0 {
+            Err(FillError::ZeroHeight)
+        }
+        else {
+            let lines: Vec<_> = (0..self.height)
+                .map(|y| {
+                    let mut line = C::empty();
+                    let mut x = 0usize;
+                    while x < self.width {
+                        let cell = C::from_cell(f(x, y));
+                        // Cells that do not fit within the remaining width are replaced with
+                        // spaces. Zero-width cells are treated as if they occupy a column.
+                        let width = cmp::max(cell.width(), 1);
+                        if x + width > self.width {
+                            line = C::concatenate(line, C::space().repeat(self.width - x));
+                            break;
+                        }
+                        line = C::concatenate(line, cell);
+                        x += width;
+                    }
+                    line
+                })
+                .collect();
+            Ok(lines.into())
+        }
+    }
+}
+
+// Lines are shared among clones of a block and are only copied when a shared block is modified.
+// Lines are shared as a whole rather than individually, so modifying or joining a shared block
+// clones all of its lines. When lines are themselves cheap to clone (e.g., `Arc<str>`), this only
+// copies the pointers of unaffected lines.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct SharedLines<C>(Arc<Vec<C>>);
+
+impl<C> SharedLines<C>
+where
+    C: Clone,
+{
+    pub fn into_vec(self) -> Vec<C> {
+        Arc::try_unwrap(self.0).unwrap_or_else(|lines| lines.as_ref().clone())
+    }
+}
+
+impl<C> Deref for SharedLines<C> {
+    type Target = Vec<C>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl<C> DerefMut for SharedLines<C>
+where
+    C: Clone,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        Arc::make_mut(&mut self.0)
+    }
+}
+
+impl<C> From<Vec<C>> for SharedLines<C> {
+    fn from(lines: Vec<C>) -> Self {
+        SharedLines(Arc::new(lines))
+    }
+}
+
+impl<C> FromIterator<C> for SharedLines<C> {
+    fn from_iter<I>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+    {
+        SharedLines(Arc::new(lines.into_iter().collect()))
+    }
+}
+
+impl<C> IntoIterator for SharedLines<C>
+where
+    C: Clone,
+{
+    type Item = C;
+    type IntoIter = vec::IntoIter<C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_vec().into_iter()
+    }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 struct ContentBlock<C>
 where
     C: Content,
 {
-    lines: Vec<C>,
+    lines: SharedLines<C>,
+    // Lines are normalized to the same width, so the width of the block is cached rather than
+    // measured from each line on demand.
+    width: CachedWidth,
 }
 
 impl<C> ContentBlock<C>
@@ -189,13 +589,13 @@ where
     C: Content,
 {
     pub fn push(self, content: impl Into<C>) -> Self {
-        let ContentBlock { mut lines } = self;
+        let mut lines = self.lines.into_vec();
         lines.extend(content.into().into_lines());
         ContentBlock::from(lines)
     }
 
     pub fn width(&self) -> usize {
-        self.lines.width()
+        self.width.get_or_measure(|| self.lines.width())
     }
 
     pub fn height(&self) -> usize {
@@ -204,39 +604,47 @@ where
 
     fn normalize(self, width: usize) -> Self {
         ContentBlock {
+            width: CachedWidth::new(width),
             lines: self
                 .lines
                 .into_iter()
-                .map(|line| {
-                    let n = width.saturating_sub(line.width());
-                    if n > 0 {
-                        Content::concatenate(line, C::grapheme(Grapheme::SPACE).repeat(n))
-                    }
-                    else {
-                        line
-                    }
-                })
+                .map(|line| normalize_line(line, width))
                 .collect(),
         }
     }
 }
 
+// Pads a line at the right to the given width.
+fn normalize_line<C>(line: C, width: usize) -> C
+where
+    C: Content,
+{
+    let n = width.saturating_sub(line.width());
+    if n > 0 {
+        Content::concatenate(line, C::grapheme(Grapheme::SPACE).repeat(n))
+    }
+    else {
+        line
+    }
+}
+
 /// Fundamental operations.
 impl<C> ContentBlock<C>
 where
     C: Content,
 {
     pub fn pad_to_width_at_right(self, width: usize) -> Self {
-        let width = width.saturating_sub(self.width());
-        if width > 0 {
+        let n = width.saturating_sub(self.width());
+        if n > 0 {
             ContentBlock {
+                width: CachedWidth::new(width),
                 lines: self
                     .lines
                     .into_iter()
                     .map(|line| {
                         // This assumes that lines are properly padded such that they have equal
                         // width (and so no per-line width must be computed).
-                        Content::concatenate(line, C::space().repeat(width))
+                        Content::concatenate(line, C::space().repeat(n))
                     })
                     .collect(),
             }
@@ -246,14 +654,40 @@ where
         }
     }
 
-    pub fn pad_to_height_at_bottom(self, height: usize) -> Self {
-        let height = height.saturating_sub(self.height());
+    pub fn pad_to_height_at_bottom(mut self, height: usize) -> Self {
+        let n = height.saturating_sub(self.height());
+        if n > 0 {
+            let line = C::space().repeat(self.width());
+            self.lines.extend(iter::repeat_n(line, n));
+        }
+        self
+    }
+
+    // Padding at the left and top is used to join content with empty blocks without filling
+    // the empty blocks first.
+    fn pad_at_left(self, width: usize) -> Self {
+        if width > 0 {
+            ContentBlock {
+                width: CachedWidth::new(self.width() + width),
+                lines: self
+                    .lines
+                    .into_iter()
+                    .map(|line| Content::concatenate(C::space().repeat(width), line))
+                    .collect(),
+            }
+        }
+        else {
+            self
+        }
+    }
+
+    fn pad_at_top(self, height: usize) -> Self {
         if height > 0 {
-            // Because `height` is greater than zero, the fill cannot fail.
-            let padding = EmptyBlock::new(self.width(), height)
-                .fill(Grapheme::SPACE)
-                .unwrap();
-            self.join_top_to_bottom_at_left(padding)
+            let line = C::space().repeat(self.width());
+            ContentBlock {
+                width: self.width,
+                lines: iter::repeat_n(line, height).chain(self.lines).collect(),
+            }
         }
         else {
             self
@@ -265,6 +699,7 @@ where
         let left = self.pad_to_height_at_bottom(height);
         let right = right.pad_to_height_at_bottom(height);
         ContentBlock {
+            width: CachedWidth::new(left.width() + right.width()),
             lines: left
                 .lines
                 .into_iter()
@@ -279,15 +714,19 @@ where
         let top = self.pad_to_width_at_right(width);
         let bottom = bottom.pad_to_width_at_right(width);
         ContentBlock {
+            width: CachedWidth::new(width),
             lines: top.lines.into_iter().chain(bottom.lines).collect(),
         }
     }
 
-    pub fn overlay_with(
+    // Pads the blocks to the same dimensions and combines their lines. The row of the lines is
+    // given to the function. Lines that are not congruent are given to the fallback function.
+    fn combine_or_else<E>(
         self,
         back: Self,
-        mut f: impl FnMut(&Grapheme, &Grapheme) -> Layer,
-    ) -> Self {
+        mut f: impl FnMut(usize, Congruent<C>) -> C,
+        mut fallback: impl FnMut(CongruenceError, C, C) -> Result<C, E>,
+    ) -> Result<Self, E> {
         let width = cmp::max(self.width(), back.width());
         let height = cmp::max(self.height(), back.height());
         let front = self
@@ -296,809 +735,5779 @@ where
         let back = back
             .pad_to_height_at_bottom(height)
             .pad_to_width_at_right(width);
-        let lines: Vec<_> = front
+        let lines = front
             .lines
             .into_iter()
             .zip(back.lines)
-            .map(|(front, back)| {
-                Content::overlay_with(Congruent::try_from((front, back)).unwrap(), &mut f)
+            .enumerate()
+            .map(|(y, (front, back))| match Congruent::try_new(front, back) {
+                Ok(content) => Ok(f(y, content)),
+                Err((error, front, back)) => fallback(error, front, back),
             })
-            .collect();
-        lines.into()
-    }
-}
-
-impl<'t> ContentBlock<Cow<'t, str>> {
-    pub fn into_owned(self) -> ContentBlock<Cow<'static, str>> {
-        ContentBlock {
-            lines: self
-                .lines
-                .into_iter()
-                .map(|line| line.into_owned().into())
-                .collect(),
-        }
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(lines.into())
     }
-}
 
-impl<C, S> ContentBlock<Styled<C, S>>
-where
-    C: AsRef<str> + Content + From<String>,
-    S: Default + Style,
-{
-    pub fn restyle(self, style: S) -> Self {
-        ContentBlock {
-            lines: self
-                .lines
-                .into_iter()
-                .map(|line| line.restyle(style.clone()))
-                .collect(),
+    // Padding and joins assume that all lines have the same width as the block.
+    fn validate(&self) -> Result<(), CongruenceError> {
+        match self.lines.iter().find(|line| line.width() != self.width()) {
+            Some(line) => Err(CongruenceError::LineWidthMismatch {
+                expected: self.width(),
+                actual: line.width(),
+            }),
+            _ => Ok(()),
         }
     }
 }
 
-impl<C> From<Vec<C>> for ContentBlock<C>
+/// In-place operations.
+impl<C> ContentBlock<C>
 where
     C: Content,
 {
-    fn from(lines: Vec<C>) -> Self {
-        let width = lines.width();
-        ContentBlock { lines }.normalize(width)
+    pub fn push_mut(&mut self, content: impl Into<C>) {
+        let lines = content.into().into_lines();
+        let width = cmp::max(self.width(), lines.width());
+        self.pad_to_width_at_right_mut(width);
+        self.lines
+            .extend(ContentBlock::from(lines).pad_to_width_at_right(width).lines);
     }
-}
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
-enum ModalBlock<C>
-where
-    C: Content,
-{
-    Empty(EmptyBlock),
-    Content(ContentBlock<C>),
-}
+    pub fn pad_to_width_at_right_mut(&mut self, width: usize) {
+        let n = width.saturating_sub(self.width());
+        if n > 0 {
+            let padding = C::space().repeat(n);
+            for line in self.lines.iter_mut() {
+                let left = mem::replace(line, C::empty());
+                *line = C::concatenate(left, padding.clone());
+            }
+            self.width = CachedWidth::new(width);
+        }
+    }
 
-impl<C> ModalBlock<C>
-where
-    C: Content,
-{
-    pub fn width(&self) -> usize {
-        match self {
-            ModalBlock::Empty(ref block) => block.width,
-            ModalBlock::Content(ref block) => block.width(),
+    pub fn pad_to_height_at_bottom_mut(&mut self, height: usize) {
+        let n = height.saturating_sub(self.height());
+        if n > 0 {
+            let padding = C::space().repeat(self.width());
+            self.lines.extend(iter::repeat_n(padding, n));
         }
     }
 
-    pub fn height(&self) -> usize {
-        match self {
-            ModalBlock::Empty(ref block) => block.height,
-            ModalBlock::Content(ref block) => block.height(),
+    pub fn join_left_to_right_at_top_mut(&mut self, right: Self) {
+        let height = cmp::max(self.height(), right.height());
+        self.pad_to_height_at_bottom_mut(height);
+        let right = right.pad_to_height_at_bottom(height);
+        let width = self.width() + right.width();
+        for (line, right) in self.lines.iter_mut().zip(right.lines) {
+            let left = mem::replace(line, C::empty());
+            *line = C::concatenate(left, right);
         }
+        self.width = CachedWidth::new(width);
     }
 
-    pub fn is_empty(&self) -> bool {
-        matches!(self, ModalBlock::Empty(_))
+    pub fn join_top_to_bottom_at_left_mut(&mut self, bottom: Self) {
+        let width = cmp::max(self.width(), bottom.width());
+        self.pad_to_width_at_right_mut(width);
+        self.lines.extend(bottom.pad_to_width_at_right(width).lines);
     }
 }
 
-// TODO: Generalize and share code for joins and overlays.
-// TODO: Only pre-pad the heights of empty blocks to allow filling.
-/// Fundamental operations.
-impl<C> ModalBlock<C>
+/// Parallel operations.
+#[cfg(feature = "rayon")]
+impl<C> ContentBlock<C>
 where
-    C: Content,
+    C: Content + Send + Sync,
 {
-    pub fn pad_to_width_at_right(self, width: usize) -> Self {
-        match self {
-            ModalBlock::Empty(block) => block.pad_to_width_at_right(width).into(),
-            ModalBlock::Content(block) => block.pad_to_width_at_right(width).into(),
+    // Like `ContentBlock::from`, but lines are measured and normalized in parallel.
+    fn par_from(lines: Vec<C>) -> Self {
+        let width = lines.par_iter().map(Content::width).max().unwrap_or(0);
+        let lines: Vec<_> = lines
+            .into_par_iter()
+            .map(|line| normalize_line(line, width))
+            .collect();
+        ContentBlock {
+            lines: lines.into(),
+            width: CachedWidth::new(width),
         }
     }
 
-    pub fn pad_to_height_at_bottom(self, height: usize) -> Self {
-        match self {
-            ModalBlock::Empty(block) => block.pad_to_height_at_bottom(height).into(),
-            ModalBlock::Content(block) => block.pad_to_height_at_bottom(height).into(),
+    pub fn par_pad_to_width_at_right(self, width: usize) -> Self {
+        let n = width.saturating_sub(self.width());
+        if n > 0 {
+            let padding = C::space().repeat(n);
+            let lines: Vec<_> = self
+                .lines
+                .into_vec()
+                .into_par_iter()
+                .map(|line| Content::concatenate(line, padding.clone()))
+                .collect();
+            ContentBlock {
+                width: CachedWidth::new(width),
+                lines: lines.into(),
+            }
+        }
+        else {
+            self
         }
     }
 
-    pub fn join_left_to_right_at_top(self, right: Self) -> Self {
-        match (self, right) {
-            (ModalBlock::Empty(left), ModalBlock::Empty(right)) => {
-                left.join_left_to_right_at_top(right).into()
-            }
-            (ModalBlock::Content(left), ModalBlock::Content(right)) => {
-                left.join_left_to_right_at_top(right).into()
-            }
-            (ModalBlock::Empty(left), ModalBlock::Content(right)) => {
-                if left.width == 0 {
-                    right
-                }
-                else {
-                    // Pad eagerly to expand the height of the empty block beyond zero.
-                    let height = cmp::max(left.height, right.height());
-                    let left = left.pad_to_height_at_bottom(height);
-                    let right = right.pad_to_height_at_bottom(height);
-                    // Neither the width nor height of the empty block can be zero here, so the
-                    // fill cannot fail.
-                    left.fill(Grapheme::SPACE)
-                        .unwrap()
-                        .join_left_to_right_at_top(right)
-                }
-                .into()
-            }
-            (ModalBlock::Content(left), ModalBlock::Empty(right)) => {
-                if right.width == 0 {
-                    left
-                }
-                else {
-                    // Pad eagerly to expand the height of the empty block beyond zero.
-                    let height = cmp::max(left.height(), right.height);
-                    let left = left.pad_to_height_at_bottom(height);
-                    let right = right.pad_to_height_at_bottom(height);
-                    // Neither the width nor height of the empty block can be zero here, so the
-                    // fill cannot fail.
-                    left.join_left_to_right_at_top(right.fill(Grapheme::SPACE).unwrap())
-                }
-                .into()
-            }
+    pub fn par_join_left_to_right_at_top(self, right: Self) -> Self {
+        let height = cmp::max(self.height(), right.height());
+        let left = self.pad_to_height_at_bottom(height);
+        let right = right.pad_to_height_at_bottom(height);
+        let width = left.width() + right.width();
+        let lines: Vec<_> = left
+            .lines
+            .into_vec()
+            .into_par_iter()
+            .zip(right.lines.into_vec())
+            .map(|(left, right)| C::concatenate(left, right))
+            .collect();
+        ContentBlock {
+            width: CachedWidth::new(width),
+            lines: lines.into(),
         }
     }
 
-    pub fn join_top_to_bottom_at_left(self, bottom: Self) -> Self {
-        match (self, bottom) {
-            (ModalBlock::Empty(top), ModalBlock::Empty(bottom)) => {
-                top.join_top_to_bottom_at_left(bottom).into()
-            }
-            (ModalBlock::Content(top), ModalBlock::Content(bottom)) => {
-                top.join_top_to_bottom_at_left(bottom).into()
-            }
-            (ModalBlock::Empty(top), ModalBlock::Content(bottom)) => {
-                if top.height == 0 {
-                    bottom
-                }
-                else {
-                    // Pad eagerly to expand the width of the empty block beyond zero.
-                    let width = cmp::max(top.width, bottom.width());
-                    let top = top.pad_to_width_at_right(width);
-                    let bottom = bottom.pad_to_width_at_right(width);
-                    // Neither the width nor height of the empty block can be zero here, so the
-                    // fill cannot fail.
-                    top.fill(Grapheme::SPACE)
-                        .unwrap()
-                        .join_top_to_bottom_at_left(bottom)
-                }
-                .into()
-            }
-            (ModalBlock::Content(top), ModalBlock::Empty(bottom)) => {
-                if bottom.height == 0 {
-                    top
-                }
-                else {
-                    // Pad eagerly to expand the width of the empty block beyond zero.
-                    let width = cmp::max(top.width(), bottom.width);
-                    let top = top.pad_to_width_at_right(width);
-                    let bottom = bottom.pad_to_width_at_right(width);
-                    // Neither the width nor height of the empty block can be zero here, so the
-                    // fill cannot fail.
-                    top.join_top_to_bottom_at_left(bottom.fill(Grapheme::SPACE).unwrap())
+    pub fn par_join_top_to_bottom_at_left(self, bottom: Self) -> Self {
+        let width = cmp::max(self.width(), bottom.width());
+        let top = self.par_pad_to_width_at_right(width);
+        let bottom = bottom.par_pad_to_width_at_right(width);
+        ContentBlock {
+            width: CachedWidth::new(width),
+            lines: top.lines.into_iter().chain(bottom.lines).collect(),
+        }
+    }
+
+    pub fn par_overlay_with(
+        self,
+        back: Self,
+        f: impl Fn(&Grapheme, &Grapheme) -> Layer + Sync,
+    ) -> Self {
+        let width = cmp::max(self.width(), back.width());
+        let height = cmp::max(self.height(), back.height());
+        let front = self
+            .pad_to_height_at_bottom(height)
+            .par_pad_to_width_at_right(width);
+        let back = back
+            .pad_to_height_at_bottom(height)
+            .par_pad_to_width_at_right(width);
+        let lines: Vec<_> = front
+            .lines
+            .into_vec()
+            .into_par_iter()
+            .zip(back.lines.into_vec())
+            // Lines that are not congruent are taken from the front block.
+            .map(|(front, back)| match Congruent::try_new(front, back) {
+                Ok(content) => Content::overlay_with(content, &f),
+                Err((_, front, _)) => front,
+            })
+            .collect();
+        ContentBlock::par_from(lines)
+    }
+}
+
+/// Truncation.
+impl<C> ContentBlock<C>
+where
+    C: Content,
+{
+    pub fn truncate_to_width_at_right(self, width: usize, ellipsis: Option<Grapheme>) -> Self {
+        if self.width() <= width {
+            return self;
+        }
+        let lines: Vec<_> = self
+            .lines
+            .into_iter()
+            .map(|line| match ellipsis {
+                Some(ref glyph)
+                    if glyph.width() <= width && !is_blank(&skip_columns(line.clone(), width)) =>
+                {
+                    let line = truncate_columns(line, width - glyph.width());
+                    C::concatenate(line, C::grapheme(glyph.clone()))
                 }
-                .into()
-            }
+                _ => truncate_columns(line, width),
+            })
+            .collect();
+        lines.into()
+    }
+
+    pub fn truncate_to_width_at_left(self, width: usize, ellipsis: Option<Grapheme>) -> Self {
+        let n = self.width().saturating_sub(width);
+        if n == 0 {
+            return self;
         }
+        let lines: Vec<_> = self
+            .lines
+            .into_iter()
+            .map(|line| match ellipsis {
+                Some(ref glyph)
+                    if glyph.width() <= width && !is_blank(&truncate_columns(line.clone(), n)) =>
+                {
+                    let line = skip_columns(line, n + glyph.width());
+                    C::concatenate(C::grapheme(glyph.clone()), line)
+                }
+                _ => skip_columns(line, n),
+            })
+            .collect();
+        lines.into()
     }
 
-    pub fn overlay(self, back: Self) -> Self {
-        self.overlay_with(back, |front, _| {
-            if *front == Grapheme::SPACE {
-                Layer::Back(())
-            }
-            else {
-                Layer::Front(())
+    pub fn truncate_to_height_at_bottom(self, height: usize, ellipsis: Option<Grapheme>) -> Self {
+        if self.height() <= height {
+            return self;
+        }
+        let width = self.width();
+        let mut lines = self.lines.into_vec();
+        let clipped = lines.split_off(height);
+        if let Some(glyph) = ellipsis {
+            if clipped.iter().any(|line| !is_blank(line)) && lines.pop().is_some() {
+                lines.push(C::grapheme(glyph));
             }
-        })
+        }
+        ContentBlock::from(lines).pad_to_width_at_right(width)
     }
 
-    pub fn overlay_with(self, back: Self, f: impl FnMut(&Grapheme, &Grapheme) -> Layer) -> Self {
-        match (self, back) {
-            (ModalBlock::Empty(front), ModalBlock::Empty(back)) => front.overlay(back).into(),
-            (ModalBlock::Content(front), ModalBlock::Content(back)) => {
-                front.overlay_with(back, f).into()
-            }
-            (ModalBlock::Empty(front), ModalBlock::Content(back)) => {
-                let width = cmp::max(front.width, back.width());
-                let height = cmp::max(front.height, back.height());
-                let front = front
-                    .pad_to_width_at_right(width)
-                    .pad_to_height_at_bottom(height);
-                let back = back
-                    .pad_to_width_at_right(width)
-                    .pad_to_height_at_bottom(height);
-                // The height of the empty block cannot be zero here, so the fill cannot fail.
-                front
-                    .fill(Grapheme::SPACE)
-                    .unwrap()
-                    .overlay_with(back, f)
-                    .into()
-            }
-            (ModalBlock::Content(front), ModalBlock::Empty(back)) => {
-                let width = cmp::max(front.width(), back.width);
-                let height = cmp::max(front.height(), back.height);
-                let front = front
-                    .pad_to_width_at_right(width)
-                    .pad_to_height_at_bottom(height);
-                let back = back
-                    .pad_to_width_at_right(width)
-                    .pad_to_height_at_bottom(height);
-                // The height of the empty block cannot be zero here, so the fill cannot fail.
-                front
-                    .overlay_with(back.fill(Grapheme::SPACE).unwrap(), f)
-                    .into()
+    pub fn truncate_to_height_at_top(self, height: usize, ellipsis: Option<Grapheme>) -> Self {
+        let n = self.height().saturating_sub(height);
+        if n == 0 {
+            return self;
+        }
+        let width = self.width();
+        let mut clipped = self.lines.into_vec();
+        let mut lines = clipped.split_off(n);
+        if let Some(glyph) = ellipsis {
+            if clipped.iter().any(|line| !is_blank(line)) && !lines.is_empty() {
+                lines[0] = C::grapheme(glyph);
             }
         }
+        ContentBlock::from(lines).pad_to_width_at_right(width)
     }
 }
 
-impl<'t> ModalBlock<Cow<'t, str>> {
-    pub fn into_owned(self) -> ModalBlock<Cow<'static, str>> {
-        match self {
-            ModalBlock::Empty(block) => ModalBlock::Empty(block),
-            ModalBlock::Content(block) => ModalBlock::Content(block.into_owned()),
+impl<C> ContentBlock<C>
+where
+    C: Content,
+{
+    // The mapping must preserve the width of each line.
+    fn map_lines<D, F>(self, f: F) -> ContentBlock<D>
+    where
+        D: Content,
+        F: FnMut(C) -> D,
+    {
+        ContentBlock {
+            width: self.width,
+            lines: self.lines.into_iter().map(f).collect(),
         }
     }
 }
 
-impl<C, S> ModalBlock<Styled<C, S>>
+impl<C, S> ContentBlock<Styled<C, S>>
 where
     C: AsRef<str> + Content + From<String>,
-    S: Default + Style,
+    S: PartialEq + Style,
 {
     pub fn restyle(self, style: S) -> Self {
-        match self {
-            ModalBlock::Empty(block) => ModalBlock::Empty(block),
-            ModalBlock::Content(block) => ModalBlock::Content(block.restyle(style)),
+        ContentBlock {
+            width: self.width,
+            lines: self
+                .lines
+                .into_iter()
+                .map(|line| line.restyle(style.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn restyle_masked(self, mask: &Mask, style: S) -> Self {
+        ContentBlock {
+            width: self.width,
+            lines: self
+                .lines
+                .into_iter()
+                .enumerate()
+                .map(|(y, line)| line.restyle_selected(style.clone(), |x| mask.get(x, y)))
+                .collect(),
+        }
+    }
+
+    pub fn restyle_gradient(self, from: S, to: S, axis: valued::Axis) -> Self
+    where
+        S: Interpolate,
+    {
+        fn parameter(n: usize, m: usize) -> f64 {
+            if m > 1 {
+                n as f64 / (m - 1) as f64
+            }
+            else {
+                0.0
+            }
+        }
+
+        let (width, height) = (self.width(), self.height());
+        ContentBlock {
+            width: CachedWidth::new(width),
+            lines: self
+                .lines
+                .into_iter()
+                .enumerate()
+                .map(|(y, line)| match axis {
+                    valued::Axis::LeftRight => {
+                        line.restyle_with(|x| from.interpolate(&to, parameter(x, width)))
+                    }
+                    valued::Axis::TopBottom => {
+                        line.restyle(from.interpolate(&to, parameter(y, height)))
+                    }
+                })
+                .collect(),
         }
     }
 }
 
-impl<C> From<ContentBlock<C>> for ModalBlock<C>
+impl<C> From<Vec<C>> for ContentBlock<C>
 where
     C: Content,
 {
-    fn from(block: ContentBlock<C>) -> Self {
-        ModalBlock::Content(block)
+    fn from(lines: Vec<C>) -> Self {
+        let width = lines.width();
+        ContentBlock {
+            lines: lines.into(),
+            width: CachedWidth::new(width),
+        }
+        .normalize(width)
     }
 }
 
-impl<C> From<EmptyBlock> for ModalBlock<C>
-where
-    C: Content,
-{
-    fn from(block: EmptyBlock) -> Self {
-        ModalBlock::Empty(block)
+// Gets the layer of a cell selected by a mask.
+fn mask_layer(mask: &Mask, x: usize, y: usize) -> Layer {
+    if mask.get(x, y) {
+        Layer::Front(())
+    }
+    else {
+        Layer::Back(())
     }
 }
 
-impl<C> From<Result<ContentBlock<C>, EmptyBlock>> for ModalBlock<C>
+// Overlays congruent lines with a function that is given the column and row of the front grapheme.
+fn overlay_at<C>(
+    mut f: impl FnMut(usize, usize, &Grapheme, &Grapheme) -> Layer,
+) -> impl FnMut(usize, Congruent<C>) -> C
 where
     C: Content,
 {
-    fn from(result: Result<ContentBlock<C>, EmptyBlock>) -> Self {
-        match result {
-            Ok(block) => ModalBlock::Content(block),
-            Err(block) => ModalBlock::Empty(block),
-        }
+    move |y, content| {
+        let mut x = 0usize;
+        Content::overlay_with(content, |front, back| {
+            let layer = f(x, y, front, back);
+            x += front.width();
+            layer
+        })
     }
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
-pub struct Block<C = String>
+enum ModalBlock<C>
 where
     C: Content,
 {
-    inner: ModalBlock<C>,
+    Empty(EmptyBlock),
+    Content(ContentBlock<C>),
 }
 
-impl<C> Block<C>
+impl<C> ModalBlock<C>
 where
     C: Content,
 {
-    pub fn zero() -> Self {
-        Self::with_dimensions(0, 0)
-    }
-
-    pub fn with_content(content: impl Into<C>) -> Self {
-        Block {
-            inner: ContentBlock::from(content.into().into_lines()).into(),
-        }
-    }
-
-    pub fn with_dimensions(width: usize, height: usize) -> Self {
-        Block {
-            inner: EmptyBlock { width, height }.into(),
+    pub fn width(&self) -> usize {
+        match self {
+            ModalBlock::Empty(ref block) => block.width,
+            ModalBlock::Content(ref block) => block.width(),
         }
     }
 
-    pub fn with_height(height: usize) -> Self {
-        Self::with_dimensions(0, height)
-    }
-
-    pub fn with_width(width: usize) -> Self {
-        Self::with_dimensions(width, 0)
-    }
-
-    pub fn filled<T>(width: usize, height: usize, filler: T) -> Self
-    where
-        Self: Fill<C, T, Output = Self>,
-    {
-        Self::with_dimensions(width, height).fill(filler)
-    }
-
     pub fn height(&self) -> usize {
-        self.inner.height()
-    }
-
-    pub fn width(&self) -> usize {
-        self.inner.width()
+        match self {
+            ModalBlock::Empty(ref block) => block.height,
+            ModalBlock::Content(ref block) => block.height(),
+        }
     }
 
     pub fn is_empty(&self) -> bool {
-        self.inner.is_empty()
-    }
-
-    fn into_content_or_fill(self, glyph: Grapheme) -> Result<ContentBlock<C>, EmptyBlock> {
-        match self.inner {
-            ModalBlock::Empty(block) => block.fill(glyph),
-            ModalBlock::Content(block) => Ok(block),
-        }
+        matches!(self, ModalBlock::Empty(_))
     }
 }
 
-impl<C> Block<C>
+/// Fundamental operations.
+impl<C> ModalBlock<C>
 where
     C: Content,
 {
-    #[must_use]
-    pub fn push(self, content: impl Into<C>) -> Self {
-        Block {
-            inner: self
-                .into_content_or_fill(Grapheme::SPACE)
-                .unwrap_or_else(|block| {
-                    ContentBlock { lines: vec![] }.pad_to_width_at_right(block.width)
-                })
-                .push(content)
-                .into(),
-        }
-    }
-}
-
-impl<C> Block<C>
-where
-    C: Content,
-{
-    #[must_use]
     pub fn pad_to_width_at_right(self, width: usize) -> Self {
-        self.inner.pad_to_width_at_right(width).into()
+        match self {
+            ModalBlock::Empty(block) => block.pad_to_width_at_right(width).into(),
+            ModalBlock::Content(block) => block.pad_to_width_at_right(width).into(),
+        }
     }
 
-    #[must_use]
     pub fn pad_to_height_at_bottom(self, height: usize) -> Self {
-        self.inner.pad_to_height_at_bottom(height).into()
+        match self {
+            ModalBlock::Empty(block) => block.pad_to_height_at_bottom(height).into(),
+            ModalBlock::Content(block) => block.pad_to_height_at_bottom(height).into(),
+        }
     }
 
-    #[must_use]
     pub fn join_left_to_right_at_top(self, right: Self) -> Self {
-        self.inner.join_left_to_right_at_top(right.inner).into()
+        match (self, right) {
+            (ModalBlock::Empty(left), ModalBlock::Empty(right)) => {
+                left.join_left_to_right_at_top(right).into()
+            }
+            (ModalBlock::Content(left), ModalBlock::Content(right)) => {
+                left.join_left_to_right_at_top(right).into()
+            }
+            // Empty blocks are joined to content by padding the content rather than filling the
+            // empty blocks with spaces.
+            (ModalBlock::Empty(left), ModalBlock::Content(right)) => if left.width == 0 {
+                right
+            }
+            else {
+                let height = cmp::max(left.height, right.height());
+                right
+                    .pad_to_height_at_bottom(height)
+                    .pad_at_left(left.width)
+            }
+            .into(),
+            (ModalBlock::Content(left), ModalBlock::Empty(right)) => if right.width == 0 {
+                left
+            }
+            else {
+                let (width, height) = (left.width() + right.width, left.height());
+                left.pad_to_height_at_bottom(cmp::max(height, right.height))
+                    .pad_to_width_at_right(width)
+            }
+            .into(),
+        }
     }
 
-    #[must_use]
     pub fn join_top_to_bottom_at_left(self, bottom: Self) -> Self {
-        self.inner.join_top_to_bottom_at_left(bottom.inner).into()
+        match (self, bottom) {
+            (ModalBlock::Empty(top), ModalBlock::Empty(bottom)) => {
+                top.join_top_to_bottom_at_left(bottom).into()
+            }
+            (ModalBlock::Content(top), ModalBlock::Content(bottom)) => {
+                top.join_top_to_bottom_at_left(bottom).into()
+            }
+            (ModalBlock::Empty(top), ModalBlock::Content(bottom)) => if top.height == 0 {
+                bottom
+            }
+            else {
+                let width = cmp::max(top.width, bottom.width());
+                bottom.pad_to_width_at_right(width).pad_at_top(top.height)
+            }
+            .into(),
+            (ModalBlock::Content(top), ModalBlock::Empty(bottom)) => if bottom.height == 0 {
+                top
+            }
+            else {
+                let (width, height) = (top.width(), top.height() + bottom.height);
+                top.pad_to_width_at_right(cmp::max(width, bottom.width))
+                    .pad_to_height_at_bottom(height)
+            }
+            .into(),
+        }
     }
 
-    #[must_use]
     pub fn overlay(self, back: Self) -> Self {
-        self.inner.overlay(back.inner).into()
+        self.overlay_with(back, |front, back| {
+            OverlayPolicy::default().layer(front, back)
+        })
+    }
+
+    pub fn overlay_with(
+        self,
+        back: Self,
+        mut f: impl FnMut(&Grapheme, &Grapheme) -> Layer,
+    ) -> Self {
+        self.overlay_at_with(back, |_, _, front, back| f(front, back))
+    }
+
+    // The column and row of the front grapheme are given to the function.
+    fn overlay_at_with(
+        self,
+        back: Self,
+        f: impl FnMut(usize, usize, &Grapheme, &Grapheme) -> Layer,
+    ) -> Self {
+        self.combine_with(back, overlay_at(f))
+    }
+
+    // Lines that are not congruent are taken from the front block. Lines can only be incongruent
+    // if an implementation of `Content` reports inconsistent widths.
+    fn combine_with(self, back: Self, f: impl FnMut(usize, Congruent<C>) -> C) -> Self {
+        let Ok(block) = self.combine_or_else(back, f, |_, front, _| Ok::<_, Infallible>(front));
+        block
     }
 }
 
-impl<C> Block<C>
+/// Fallible operations.
+impl<C> ModalBlock<C>
 where
     C: Content,
 {
-    #[must_use]
-    pub fn pad_at_left(self, width: usize) -> Self {
-        let padding = Block::filled(width, self.height(), Grapheme::SPACE);
-        padding.join_left_to_right_at_top(self)
+    pub fn try_join_left_to_right_at_top(self, right: Self) -> Result<Self, CongruenceError> {
+        self.validate()?;
+        right.validate()?;
+        Ok(self.join_left_to_right_at_top(right))
     }
 
-    #[must_use]
-    pub fn pad_at_right(self, width: usize) -> Self {
-        let padding = Block::filled(width, self.height(), Grapheme::SPACE);
-        self.join_left_to_right_at_top(padding)
+    pub fn try_join_top_to_bottom_at_left(self, bottom: Self) -> Result<Self, CongruenceError> {
+        self.validate()?;
+        bottom.validate()?;
+        Ok(self.join_top_to_bottom_at_left(bottom))
     }
 
-    #[must_use]
-    pub fn pad_at_top(self, height: usize) -> Self {
-        let padding = Block::filled(self.width(), height, Grapheme::SPACE);
-        padding.join_top_to_bottom_at_left(self)
+    pub fn try_overlay(self, back: Self) -> Result<Self, CongruenceError> {
+        self.try_overlay_with(back, |front, back| {
+            OverlayPolicy::default().layer(front, back)
+        })
     }
 
-    #[must_use]
-    pub fn pad_at_bottom(self, height: usize) -> Self {
-        let padding = Block::filled(self.width(), height, Grapheme::SPACE);
-        self.join_top_to_bottom_at_left(padding)
+    pub fn try_overlay_with(
+        self,
+        back: Self,
+        mut f: impl FnMut(&Grapheme, &Grapheme) -> Layer,
+    ) -> Result<Self, CongruenceError> {
+        self.try_overlay_at_with(back, |_, _, front, back| f(front, back))
     }
 
-    #[must_use]
-    pub fn pad_to_width_at_left(self, width: usize) -> Self {
-        let width = width.saturating_sub(self.width());
-        self.pad_at_left(width)
+    // The column and row of the front grapheme are given to the function.
+    fn try_overlay_at_with(
+        self,
+        back: Self,
+        f: impl FnMut(usize, usize, &Grapheme, &Grapheme) -> Layer,
+    ) -> Result<Self, CongruenceError> {
+        self.try_combine_with(back, overlay_at(f))
     }
 
-    #[must_use]
-    pub fn pad_to_height_at_top(self, height: usize) -> Self {
-        let height = height.saturating_sub(self.height());
-        self.pad_at_top(height)
+    fn try_combine_with(
+        self,
+        back: Self,
+        f: impl FnMut(usize, Congruent<C>) -> C,
+    ) -> Result<Self, CongruenceError> {
+        self.combine_or_else(back, f, |error, _, _| Err(error))
     }
 
-    #[must_use]
-    pub fn join_left_to_right_at_bottom(self, right: Self) -> Self {
-        let height = cmp::max(self.height(), right.height());
-        self.pad_to_height_at_top(height)
-            .join_left_to_right_at_top(right.pad_to_height_at_top(height))
+    fn combine_or_else<E>(
+        self,
+        back: Self,
+        f: impl FnMut(usize, Congruent<C>) -> C,
+        fallback: impl FnMut(CongruenceError, C, C) -> Result<C, E>,
+    ) -> Result<Self, E> {
+        match (self, back) {
+            (ModalBlock::Empty(front), ModalBlock::Empty(back)) => Ok(front.overlay(back).into()),
+            (ModalBlock::Content(front), ModalBlock::Content(back)) => {
+                front.combine_or_else(back, f, fallback).map(From::from)
+            }
+            (ModalBlock::Empty(front), ModalBlock::Content(back)) => {
+                let width = cmp::max(front.width, back.width());
+                let height = cmp::max(front.height, back.height());
+                let front = front
+                    .pad_to_width_at_right(width)
+                    .pad_to_height_at_bottom(height);
+                let back = back
+                    .pad_to_width_at_right(width)
+                    .pad_to_height_at_bottom(height);
+                match front.fill(Grapheme::SPACE) {
+                    Ok(front) => front.combine_or_else(back, f, fallback).map(From::from),
+                    // The fill fails only if the combined height is zero.
+                    Err(_) => Ok(EmptyBlock::new(width, 0).into()),
+                }
+            }
+            (ModalBlock::Content(front), ModalBlock::Empty(back)) => {
+                let width = cmp::max(front.width(), back.width);
+                let height = cmp::max(front.height(), back.height);
+                let front = front
+                    .pad_to_width_at_right(width)
+                    .pad_to_height_at_bottom(height);
+                let back = back
+                    .pad_to_width_at_right(width)
+                    .pad_to_height_at_bottom(height);
+                match back.fill(Grapheme::SPACE) {
+                    Ok(back) => front.combine_or_else(back, f, fallback).map(From::from),
+                    // The fill fails only if the combined height is zero.
+                    Err(_) => Ok(EmptyBlock::new(width, 0).into()),
+                }
+            }
+        }
     }
 
-    #[must_use]
-    pub fn join_top_to_bottom_at_right(self, bottom: Self) -> Self {
-        let width = cmp::max(self.width(), bottom.width());
-        self.pad_to_width_at_left(width)
-            .join_top_to_bottom_at_left(bottom.pad_to_width_at_left(width))
+    fn validate(&self) -> Result<(), CongruenceError> {
+        match self {
+            ModalBlock::Empty(_) => Ok(()),
+            ModalBlock::Content(ref block) => block.validate(),
+        }
     }
 }
 
-/// Statically parameterized operations.
-impl<C> Block<C>
+/// Truncation.
+impl<C> ModalBlock<C>
 where
     C: Content,
 {
-    pub fn with_length_at<A>(length: usize, width: usize) -> Self
-    where
-        Self: WithLength<A>,
-        A: typed::Axis,
-    {
-        WithLength::with_length(length, width)
-    }
-
-    #[must_use]
-    pub fn pad_at<L>(self, length: usize) -> Self
-    where
-        Self: Pad<L>,
-        L: typed::Alignment,
-    {
-        Pad::pad(self, length)
+    pub fn truncate_to_width_at_right(self, width: usize, ellipsis: Option<Grapheme>) -> Self {
+        match self {
+            ModalBlock::Empty(block) => block.truncate_to_width(width).into(),
+            ModalBlock::Content(block) => block.truncate_to_width_at_right(width, ellipsis).into(),
+        }
     }
 
-    #[must_use]
-    pub fn pad_to_length_at<A, L>(self, length: usize) -> Self
-    where
-        Self: PadToLength<A, L>,
-        A: typed::Axis,
-        L: typed::Coaxial<A>,
-    {
-        PadToLength::pad_to_length(self, length)
+    pub fn truncate_to_width_at_left(self, width: usize, ellipsis: Option<Grapheme>) -> Self {
+        match self {
+            ModalBlock::Empty(block) => block.truncate_to_width(width).into(),
+            ModalBlock::Content(block) => block.truncate_to_width_at_left(width, ellipsis).into(),
+        }
     }
 
-    #[must_use]
-    pub fn join_at<A, L>(self, other: Self) -> Self
-    where
-        Self: Join<A, L>,
-        A: typed::Axis,
-        L: typed::ContraAxial<A>,
-    {
-        Join::join(self, other)
+    pub fn truncate_to_height_at_bottom(self, height: usize, ellipsis: Option<Grapheme>) -> Self {
+        match self {
+            ModalBlock::Empty(block) => block.truncate_to_height(height).into(),
+            ModalBlock::Content(block) if height == 0 => EmptyBlock::new(block.width(), 0).into(),
+            ModalBlock::Content(block) => {
+                block.truncate_to_height_at_bottom(height, ellipsis).into()
+            }
+        }
     }
-}
 
-impl<'t> Block<Cow<'t, str>> {
-    pub fn into_owned(self) -> Block<Cow<'static, str>> {
-        Block {
-            inner: self.inner.into_owned(),
+    pub fn truncate_to_height_at_top(self, height: usize, ellipsis: Option<Grapheme>) -> Self {
+        match self {
+            ModalBlock::Empty(block) => block.truncate_to_height(height).into(),
+            ModalBlock::Content(block) if height == 0 => EmptyBlock::new(block.width(), 0).into(),
+            ModalBlock::Content(block) => block.truncate_to_height_at_top(height, ellipsis).into(),
         }
     }
 }
 
-impl<C, S> Block<Styled<C, S>>
+impl<C> ModalBlock<C>
 where
-    C: AsRef<str> + Content + From<String>,
-    S: Default + Style,
+    C: Content,
 {
-    #[must_use]
-    pub fn restyle(self, style: S) -> Self {
-        Block {
-            inner: self.inner.restyle(style),
+    pub fn split_at_width(self, width: usize) -> (Self, Self) {
+        match self {
+            ModalBlock::Empty(block) => {
+                let left = block.truncate_to_width(width);
+                let right = EmptyBlock::new(block.width - left.width, block.height);
+                (left.into(), right.into())
+            }
+            ModalBlock::Content(block) => {
+                let width = cmp::min(width, block.width());
+                let (left, right): (Vec<_>, Vec<_>) = block
+                    .lines
+                    .into_iter()
+                    .map(|line| {
+                        (
+                            truncate_columns(line.clone(), width),
+                            skip_columns(line, width),
+                        )
+                    })
+                    .unzip();
+                (
+                    ContentBlock::from(left).into(),
+                    ContentBlock::from(right).into(),
+                )
+            }
+        }
+    }
+
+    pub fn split_at_height(self, height: usize) -> (Self, Self) {
+        match self {
+            ModalBlock::Empty(block) => {
+                let top = block.truncate_to_height(height);
+                let bottom = EmptyBlock::new(block.width, block.height - top.height);
+                (top.into(), bottom.into())
+            }
+            ModalBlock::Content(block) => {
+                let width = block.width();
+                let mut top = block.lines.into_vec();
+                let bottom = top.split_off(cmp::min(height, top.len()));
+                let split = |lines: Vec<C>| -> Self {
+                    if lines.is_empty() {
+                        EmptyBlock::new(width, 0).into()
+                    }
+                    else {
+                        ContentBlock {
+                            lines: lines.into(),
+                            width: CachedWidth::new(width),
+                        }
+                        .into()
+                    }
+                };
+                (split(top), split(bottom))
+            }
         }
     }
 }
 
-impl<C> DynamicallyAligned for Block<C>
+impl<C> ModalBlock<C>
 where
     C: Content,
 {
-    fn with_length(axis: valued::Axis, length: usize, width: usize) -> Self {
-        use crate::align::valued::Axis;
-
-        match axis {
-            Axis::LeftRight => Block::with_dimensions(length, width),
-            Axis::TopBottom => Block::with_dimensions(width, length),
+    fn map_lines<D, F>(self, f: F) -> ModalBlock<D>
+    where
+        D: Content,
+        F: FnMut(C) -> D,
+    {
+        match self {
+            ModalBlock::Empty(block) => ModalBlock::Empty(block),
+            ModalBlock::Content(block) => ModalBlock::Content(block.map_lines(f)),
         }
     }
+}
 
-    fn pad(self, alignment: impl Into<valued::Alignment>, length: usize) -> Self {
-        use crate::align::valued::Alignment;
+impl<'t> ModalBlock<Cow<'t, str>> {
+    pub fn into_owned(self) -> ModalBlock<Cow<'static, str>> {
+        self.map_lines(|line| line.into_owned().into())
+    }
+}
 
-        match alignment.into() {
-            Alignment::LEFT => self.pad_at_left(length),
-            Alignment::RIGHT => self.pad_at_right(length),
-            Alignment::TOP => self.pad_at_top(length),
-            Alignment::BOTTOM => self.pad_at_bottom(length),
+impl<C, S> ModalBlock<Styled<C, S>>
+where
+    C: AsRef<str> + Content + From<String>,
+    S: PartialEq + Style,
+{
+    pub fn restyle(self, style: S) -> Self {
+        match self {
+            ModalBlock::Empty(block) => ModalBlock::Empty(block),
+            ModalBlock::Content(block) => ModalBlock::Content(block.restyle(style)),
         }
     }
 
-    fn pad_to_length(self, alignment: impl Into<valued::Alignment>, length: usize) -> Self {
-        use crate::align::valued::Alignment;
-
-        match alignment.into() {
-            Alignment::LEFT => self.pad_to_width_at_left(length),
-            Alignment::RIGHT => self.pad_to_width_at_right(length),
-            Alignment::TOP => self.pad_to_height_at_top(length),
-            Alignment::BOTTOM => self.pad_to_height_at_bottom(length),
+    pub fn restyle_masked(self, mask: &Mask, style: S) -> Self {
+        match self {
+            ModalBlock::Empty(block) => ModalBlock::Empty(block),
+            ModalBlock::Content(block) => ModalBlock::Content(block.restyle_masked(mask, style)),
         }
     }
 
-    fn join(self, alignment: valued::AxialAlignment, other: Self) -> Self {
-        use crate::align::valued::AxialAlignment;
-
-        match alignment {
-            AxialAlignment::LEFT_RIGHT_AT_TOP => self.join_left_to_right_at_top(other),
-            AxialAlignment::LEFT_RIGHT_AT_BOTTOM => self.join_left_to_right_at_bottom(other),
-            AxialAlignment::TOP_BOTTOM_AT_LEFT => self.join_top_to_bottom_at_left(other),
-            AxialAlignment::TOP_BOTTOM_AT_RIGHT => self.join_top_to_bottom_at_right(other),
+    pub fn restyle_gradient(self, from: S, to: S, axis: valued::Axis) -> Self
+    where
+        S: Interpolate,
+    {
+        match self {
+            ModalBlock::Empty(block) => ModalBlock::Empty(block),
+            ModalBlock::Content(block) => {
+                ModalBlock::Content(block.restyle_gradient(from, to, axis))
+            }
         }
     }
 }
 
-impl<C> Fill<C, C> for Block<C>
+impl<C> From<ContentBlock<C>> for ModalBlock<C>
 where
     C: Content,
 {
-    type Output = Self;
-
-    fn fill(self, content: C) -> Self::Output {
-        let block = EmptyBlock {
-            width: self.width(),
-            height: self.height(),
-        };
-        Block {
-            inner: match block.fill(content) {
-                Ok(block) => block.into(),
-                Err(block) => block.into(),
-            },
-        }
+    fn from(block: ContentBlock<C>) -> Self {
+        ModalBlock::Content(block)
     }
 }
 
-impl<'t, C> Fill<C, Grapheme<'t>> for Block<C>
+impl<C> From<EmptyBlock> for ModalBlock<C>
 where
     C: Content,
 {
-    type Output = Self;
-
-    fn fill(self, glyph: Grapheme<'t>) -> Self::Output {
-        let block = EmptyBlock {
-            width: self.width(),
-            height: self.height(),
-        };
-        Block {
-            inner: match block.fill(glyph) {
-                Ok(block) => block.into(),
-                Err(block) => block.into(),
-            },
-        }
+    fn from(block: EmptyBlock) -> Self {
+        ModalBlock::Empty(block)
     }
 }
 
-impl<C> From<ModalBlock<C>> for Block<C>
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Block<C = String>
 where
     C: Content,
 {
-    fn from(block: ModalBlock<C>) -> Self {
-        Block { inner: block }
-    }
+    inner: ModalBlock<C>,
 }
 
-impl<C> Join<typed::LeftRight, typed::Bottom> for Block<C>
+/// Accumulates formatted text into the lines of a block.
+///
+/// Each line break in written text begins a new line.
+#[derive(Clone, Debug)]
+pub struct BlockBuilder<C = String>
 where
     C: Content,
 {
-    fn join(self, other: Self) -> Self {
-        self.join_left_to_right_at_bottom(other)
-    }
+    lines: Vec<C>,
+    line: String,
 }
 
-impl<C> Join<typed::LeftRight, typed::Top> for Block<C>
+impl<C> BlockBuilder<C>
 where
-    C: Content,
+    C: Content + From<String>,
 {
-    fn join(self, other: Self) -> Self {
-        self.join_left_to_right_at_top(other)
+    pub fn new() -> Self {
+        BlockBuilder {
+            lines: vec![],
+            line: String::new(),
+        }
     }
-}
 
-impl<C> Join<typed::TopBottom, typed::Left> for Block<C>
-where
-    C: Content,
-{
-    fn join(self, other: Self) -> Self {
-        self.join_top_to_bottom_at_left(other)
+    pub fn finish(mut self) -> Block<C> {
+        if !self.line.is_empty() {
+            self.push_line();
+        }
+        if self.lines.is_empty() {
+            Block::zero()
+        }
+        else {
+            Block {
+                inner: ContentBlock::from(self.lines).into(),
+            }
+        }
     }
-}
 
-impl<C> Join<typed::TopBottom, typed::Right> for Block<C>
-where
-    C: Content,
-{
-    fn join(self, other: Self) -> Self {
-        self.join_top_to_bottom_at_right(other)
+    fn push_line(&mut self) {
+        let mut line = mem::take(&mut self.line);
+        if line.ends_with('\r') {
+            line.pop();
+        }
+        self.lines.push(line.into());
     }
 }
 
-impl<C> Pad<typed::Bottom> for Block<C>
+impl<C> Default for BlockBuilder<C>
 where
-    C: Content,
+    C: Content + From<String>,
 {
-    fn pad(self, width: usize) -> Self {
-        self.pad_at_bottom(width)
+    fn default() -> Self {
+        BlockBuilder::new()
     }
 }
 
-impl<C> Pad<typed::Left> for Block<C>
+impl<C> fmt::Write for BlockBuilder<C>
 where
-    C: Content,
+    C: Content + From<String>,
 {
-    fn pad(self, width: usize) -> Self {
-        self.pad_at_left(width)
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        let mut lines = text.split('\n');
+        if let Some(line) = lines.next() {
+            self.line.push_str(line);
+        }
+        for line in lines {
+            self.push_line();
+            self.line.push_str(line);
+        }
+        Ok(())
     }
 }
 
-impl<C> Pad<typed::Right> for Block<C>
+/// Formats a block as a grid with a column and row ruler and visible spaces.
+pub struct DebugGrid<'b, C>
 where
     C: Content,
 {
-    fn pad(self, width: usize) -> Self {
-        self.pad_at_right(width)
-    }
+    block: &'b Block<C>,
 }
 
-impl<C> Pad<typed::Top> for Block<C>
+impl<'b, C> Debug for DebugGrid<'b, C>
 where
     C: Content,
 {
-    fn pad(self, width: usize) -> Self {
-        self.pad_at_top(width)
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        const SPACE: &str = "\u{B7}";
+
+        let width = self.block.width();
+        let height = self.block.height();
+        let margin = height.saturating_sub(1).to_string().len();
+        let ruler: String = (0..width)
+            .map(|x| char::from_digit((x % 10) as u32, 10).unwrap())
+            .collect();
+        let border = "-".repeat(width);
+        writeln!(formatter, "{:margin$}  {}", "", ruler, margin = margin)?;
+        writeln!(formatter, "{:margin$} +{}+", "", border, margin = margin)?;
+        for (y, row) in self.block.cells().into_iter().enumerate() {
+            let line: String = row
+                .iter()
+                .map(|glyph| {
+                    if *glyph == Grapheme::SPACE {
+                        SPACE
+                    }
+                    else {
+                        glyph.get()
+                    }
+                })
+                .collect();
+            writeln!(formatter, "{:>margin$} |{}|", y, line, margin = margin)?;
+        }
+        write!(formatter, "{:margin$} +{}+", "", border, margin = margin)
     }
 }
 
-impl<C> PadToLength<typed::LeftRight, typed::Left> for Block<C>
+impl<C> Block<C>
 where
     C: Content,
 {
-    fn pad_to_length(self, length: usize) -> Self {
-        self.pad_to_width_at_left(length)
+    pub fn zero() -> Self {
+        Self::with_dimensions(0, 0)
     }
-}
 
-impl<C> PadToLength<typed::LeftRight, typed::Right> for Block<C>
-where
-    C: Content,
-{
-    fn pad_to_length(self, length: usize) -> Self {
-        self.pad_to_width_at_right(length)
+    pub fn with_content(content: impl Into<C>) -> Self {
+        Block {
+            inner: ContentBlock::from(content.into().into_lines()).into(),
+        }
     }
-}
 
-impl<C> PadToLength<typed::TopBottom, typed::Bottom> for Block<C>
-where
-    C: Content,
-{
-    fn pad_to_length(self, length: usize) -> Self {
-        self.pad_to_height_at_bottom(length)
+    /// Constructs a block from lines of content.
+    ///
+    /// Lines are padded to the width of the widest line. Content with line breaks is split into
+    /// additional lines.
+    pub fn from_lines(lines: Vec<C>) -> Self {
+        lines.into_iter().collect()
     }
-}
 
-impl<C> PadToLength<typed::TopBottom, typed::Top> for Block<C>
-where
-    C: Content,
-{
-    fn pad_to_length(self, length: usize) -> Self {
-        self.pad_to_height_at_top(length)
+    /// Constructs a block with content laid out from top to bottom.
+    ///
+    /// Each grapheme occupies its own row and each line of the content forms a column. Columns
+    /// are joined from left to right and are as wide as their widest grapheme.
+    pub fn vertical_text(content: impl Into<C>) -> Self {
+        Self::vertical_text_with(content.into(), false)
     }
-}
 
-impl<C> Render for Block<C>
-where
-    C: Content,
-{
-    fn render_into(&self, target: &mut impl Write) -> io::Result<()> {
-        if let ModalBlock::Content(ref block) = self.inner {
-            for line in block.lines.iter() {
-                line.render_into(target)?;
+    /// Constructs a block with content laid out from top to bottom like `vertical_text` and
+    /// replaces punctuation with vertical presentation forms (e.g., `「` with `﹁`).
+    pub fn vertical_text_rotated(content: impl Into<C>) -> Self {
+        Self::vertical_text_with(content.into(), true)
+    }
+
+    fn vertical_text_with(content: C, is_rotated: bool) -> Self {
+        content
+            .into_lines()
+            .into_iter()
+            .map(|line| {
+                let forms: Vec<_> = line
+                    .to_graphemes()
+                    .iter()
+                    .map(|glyph| {
+                        if is_rotated {
+                            vertical_form(glyph.get())
+                        }
+                        else {
+                            None
+                        }
+                    })
+                    .collect();
+                let lines: Vec<_> = forms
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, form)| match form {
+                        Some(form) => C::grapheme(Grapheme::from(form)),
+                        None => line.clone().skip(index).truncate(1),
+                    })
+                    .collect();
+                if lines.is_empty() {
+                    Block::zero()
+                }
+                else {
+                    Block {
+                        inner: ContentBlock::from(lines).into(),
+                    }
+                }
+            })
+            .reduce(Block::join_left_to_right_at_top)
+            .unwrap_or_else(Block::zero)
+    }
+
+    pub fn with_content_split_by(content: impl Into<C>, policy: LineBreakPolicy) -> Self {
+        Block {
+            inner: ContentBlock::from(content.into().into_lines_with(policy)).into(),
+        }
+    }
+
+    pub fn with_sanitized_content(content: impl Into<C>, policy: &SanitizePolicy) -> Self {
+        Self::with_content(content.into().sanitize(policy))
+    }
+
+    // Rendered text is sanitized, so tabs are expanded and any remaining control characters are
+    // replaced.
+    pub fn from_rendered(text: &str) -> Self
+    where
+        C: From<String>,
+    {
+        Self::with_sanitized_content(
+            String::from(content::strip_escapes(text)),
+            &SanitizePolicy::default(),
+        )
+    }
+
+    pub fn with_dimensions(width: usize, height: usize) -> Self {
+        Block {
+            inner: EmptyBlock { width, height }.into(),
+        }
+    }
+
+    pub fn with_height(height: usize) -> Self {
+        Self::with_dimensions(0, height)
+    }
+
+    pub fn with_width(width: usize) -> Self {
+        Self::with_dimensions(width, 0)
+    }
+
+    pub fn filled<T>(width: usize, height: usize, filler: T) -> Self
+    where
+        Self: Fill<C, T, Output = Self>,
+    {
+        Self::with_dimensions(width, height).fill(filler)
+    }
+
+    pub fn with_extent_of<D>(block: &Block<D>) -> Self
+    where
+        D: Content,
+    {
+        Self::with_dimensions(block.width(), block.height())
+    }
+
+    pub fn filled_like<D, T>(block: &Block<D>, filler: T) -> Self
+    where
+        Self: Fill<C, T, Output = Self>,
+        D: Content,
+    {
+        Self::with_extent_of(block).fill(filler)
+    }
+
+    pub fn height(&self) -> usize {
+        self.inner.height()
+    }
+
+    pub fn width(&self) -> usize {
+        self.inner.width()
+    }
+
+    pub fn extent(&self) -> Extent {
+        Extent {
+            width: self.width(),
+            height: self.height(),
+        }
+    }
+
+    pub fn length_at<A>(&self) -> usize
+    where
+        A: typed::Axis,
+    {
+        *self.extent().axially_aligned_at::<A>()
+    }
+
+    /// Returns `true` if the block has no content.
+    ///
+    /// Blocks without content may still have non-zero area. For example, a block constructed
+    /// with `with_dimensions(1, 1)` is empty but occupies a cell. See `is_zero` and `is_blank`.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns `true` if the block has zero area (i.e., its width or height is zero).
+    pub fn is_zero(&self) -> bool {
+        self.width() == 0 || self.height() == 0
+    }
+
+    /// Returns `true` if the block has no content or its content consists only of spaces and
+    /// transparent cells.
+    pub fn is_blank(&self) -> bool {
+        match self.inner {
+            ModalBlock::Empty(_) => true,
+            ModalBlock::Content(ref block) => block.lines.iter().all(is_blank),
+        }
+    }
+
+    /// Fills the block with the given filler.
+    ///
+    /// Unlike `fill`, which leaves blocks that cannot be filled unchanged, this returns an error
+    /// if the block has zero height or the filler has no content.
+    pub fn try_fill<T>(self, filler: T) -> Result<Self, FillError>
+    where
+        Self: Fill<C, T, Output = Self>,
+    {
+        let (width, height) = (self.width(), self.height());
+        if height == 0 {
+            return Err(FillError::ZeroHeight);
+        }
+        let block = self.fill(filler);
+        if width > 0 && block.is_empty() {
+            Err(FillError::EmptyFiller)
+        }
+        else {
+            Ok(block)
+        }
+    }
+
+    pub fn lazy(self) -> LazyBlock<C> {
+        self.into()
+    }
+
+    pub fn with_baseline(self, row: usize) -> BaselineBlock<C> {
+        BaselineBlock::new(self, row)
+    }
+
+    pub fn debug_grid(&self) -> DebugGrid<'_, C> {
+        DebugGrid { block: self }
+    }
+
+    // Empty blocks are presented as rows of spaces.
+    pub(crate) fn cells(&self) -> Vec<Vec<Grapheme<'_>>> {
+        match self.inner {
+            ModalBlock::Empty(ref block) => vec![vec![Grapheme::SPACE; block.width]; block.height],
+            ModalBlock::Content(ref block) => {
+                block.lines.iter().map(|line| line.to_graphemes()).collect()
             }
         }
-        Ok(())
     }
 
-    fn render(&self) -> Cow<str> {
+    // Gets whether or not the block hides any block behind it when overlaid with the default
+    // policy. Empty blocks are transparent.
+    pub(crate) fn is_opaque(&self) -> bool {
         match self.inner {
-            ModalBlock::Empty(_) => "".into(),
-            ModalBlock::Content(ref block) => block
-                .lines
-                .iter()
-                .fold(String::new(), |mut output, line| {
-                    output.push_str(line.render().trim_end());
-                    output.push('\n');
-                    output
+            ModalBlock::Empty(_) => false,
+            ModalBlock::Content(ref block) => block.lines.iter().all(|line| {
+                line.to_graphemes().iter().all(|glyph| {
+                    matches!(
+                        OverlayPolicy::default().layer(glyph, &Grapheme::SPACE),
+                        Layer::Front(_)
+                    )
                 })
-                .into(),
+            }),
         }
     }
-}
 
-impl<C> WithLength<typed::LeftRight> for Block<C>
-where
-    C: Content,
-{
-    fn with_length(length: usize, width: usize) -> Self {
-        Self::with_dimensions(length, width)
+    fn into_content_or_fill(self, glyph: Grapheme) -> Result<ContentBlock<C>, EmptyBlock> {
+        match self.inner {
+            ModalBlock::Empty(block) => block.fill(glyph).map_err(|_| block),
+            ModalBlock::Content(block) => Ok(block),
+        }
+    }
+
+    /// Renders each line of the block.
+    ///
+    /// Lines are rendered as they are by [`Render::render`] but without line breaks. Lines are
+    /// rendered lazily, so output can be written and interleaved with other output line by line.
+    pub fn render_lines(&self) -> impl '_ + Iterator<Item = Cow<'_, str>> {
+        self.render_lines_with(&RenderContext::default())
+    }
+
+    pub fn render_lines_with(
+        &self,
+        context: &RenderContext,
+    ) -> impl '_ + Iterator<Item = Cow<'_, str>> {
+        let context = *context;
+        let is_isolated = is_isolated();
+        let lines: &[C] = match self.inner {
+            ModalBlock::Empty(_) => &[],
+            ModalBlock::Content(ref block) => &block.lines,
+        };
+        lines.iter().map(move |line| {
+            let text = line.render_with(&context);
+            if is_isolated {
+                content::isolate(text.trim_end()).into()
+            }
+            else {
+                match text {
+                    Cow::Borrowed(text) => text.trim_end().into(),
+                    Cow::Owned(text) => text.trim_end().to_owned().into(),
+                }
+            }
+        })
+    }
+
+    /// Converts the block into its lines of content without rendering.
+    ///
+    /// Every line has the width of the block. Empty blocks are presented as lines of spaces.
+    pub fn into_content_vec(self) -> Vec<C> {
+        match self.inner {
+            ModalBlock::Empty(block) => vec![C::space().repeat(block.width); block.height],
+            ModalBlock::Content(block) => block.lines.into_vec(),
+        }
     }
 }
 
-impl<C> WithLength<typed::TopBottom> for Block<C>
+impl<C> Block<C>
 where
     C: Content,
 {
-    fn with_length(length: usize, width: usize) -> Self {
-        Self::with_dimensions(width, length)
+    #[must_use]
+    pub fn push(self, content: impl Into<C>) -> Self {
+        Block {
+            inner: self
+                .into_content_or_fill(Grapheme::SPACE)
+                .unwrap_or_else(|block| {
+                    ContentBlock {
+                        lines: vec![].into(),
+                        width: CachedWidth::new(0),
+                    }
+                    .pad_to_width_at_right(block.width)
+                })
+                .push(content)
+                .into(),
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::borrow::Cow;
+    /// Pushes content aligned against the width of the block.
+    ///
+    /// If the content is wider than the block, then the block is widened and its existing lines
+    /// are aligned against the content instead.
+    #[must_use]
+    pub fn push_aligned(
+        self,
+        alignment: valued::HorizontalAlignment,
+        content: impl Into<C>,
+    ) -> Self {
+        self.push_block_aligned(alignment, Block::with_content(content))
+    }
 
-    use crate::block::{Block, Fill};
-    use crate::content::{Style as Transform, Styled};
-    use crate::Render;
+    /// Pushes content aligned against the width of the block, truncating the content at the
+    /// right if it is wider than the block.
+    #[must_use]
+    pub fn push_aligned_truncated(
+        self,
+        alignment: valued::HorizontalAlignment,
+        content: impl Into<C>,
+    ) -> Self {
+        let width = self.width();
+        let block = Block::with_content(content).truncate_to_width_at_right(width, None);
+        self.push_block_aligned(alignment, block)
+    }
 
-    #[test]
+    /// Pushes content centered against the width of the block.
+    ///
+    /// If the content is wider than the block, then the block is widened and its existing lines
+    /// are centered against the content instead. When the padding cannot be split evenly, the
+    /// extra column is placed at the right.
+    #[must_use]
+    pub fn push_centered(self, content: impl Into<C>) -> Self {
+        let block = Block::with_content(content);
+        let width = cmp::max(self.width(), block.width());
+        self.pad_to_length_centered_at::<typed::LeftRight>(width)
+            .join_top_to_bottom_at_left(block.pad_to_length_centered_at::<typed::LeftRight>(width))
+    }
+
+    fn push_block_aligned(self, alignment: valued::HorizontalAlignment, block: Self) -> Self {
+        use crate::align::valued::HorizontalAlignment;
+
+        match alignment {
+            HorizontalAlignment::Left => self.join_top_to_bottom_at_left(block),
+            HorizontalAlignment::Right => self.join_top_to_bottom_at_right(block),
+        }
+    }
+}
+
+impl<C> Block<C>
+where
+    C: Content,
+{
+    #[must_use]
+    pub fn pad_to_width_at_right(self, width: usize) -> Self {
+        self.inner.pad_to_width_at_right(width).into()
+    }
+
+    #[must_use]
+    pub fn pad_to_height_at_bottom(self, height: usize) -> Self {
+        self.inner.pad_to_height_at_bottom(height).into()
+    }
+
+    #[must_use]
+    pub fn join_left_to_right_at_top(self, right: Self) -> Self {
+        self.inner.join_left_to_right_at_top(right.inner).into()
+    }
+
+    #[must_use]
+    pub fn join_top_to_bottom_at_left(self, bottom: Self) -> Self {
+        self.inner.join_top_to_bottom_at_left(bottom.inner).into()
+    }
+
+    /// Overlays the block onto another block.
+    ///
+    /// Spaces and transparent cells in the front block show the back block. See
+    /// `OverlayPolicy::Spaces`.
+    #[must_use]
+    pub fn overlay(self, back: Self) -> Self {
+        self.inner.overlay(back.inner).into()
+    }
+
+    /// Overlays the block onto another block with the given policy.
+    #[must_use]
+    pub fn overlay_by(self, back: Self, policy: OverlayPolicy) -> Self {
+        self.overlay_with(back, |front, back| policy.layer(front, back))
+    }
+
+    #[must_use]
+    pub fn overlay_with(self, back: Self, f: impl FnMut(&Grapheme, &Grapheme) -> Layer) -> Self {
+        self.inner.overlay_with(back.inner, f).into()
+    }
+
+    /// Overlays the block onto another block where cells are selected by a mask.
+    ///
+    /// Unlike `overlay`, spaces in selected cells are taken from the front block and graphemes in
+    /// cells that are not selected are taken from the back block.
+    #[must_use]
+    pub fn overlay_masked(self, mask: &Mask, back: Self) -> Self {
+        self.inner
+            .overlay_at_with(back.inner, |x, y, _, _| mask_layer(mask, x, y))
+            .into()
+    }
+
+    /// Overlays layers given from front to back.
+    ///
+    /// This is equivalent to overlaying each layer onto the composition of the layers behind it,
+    /// but each cell is resolved from all layers in one pass without composing intermediate
+    /// blocks.
+    pub fn overlay_all(layers: impl IntoIterator<Item = Self>) -> Self {
+        Block::overlay_all_by(layers, OverlayPolicy::default())
+    }
+
+    /// Overlays layers given from front to back with the given policy.
+    ///
+    /// See `overlay_all`.
+    pub fn overlay_all_by(layers: impl IntoIterator<Item = Self>, policy: OverlayPolicy) -> Self {
+        let layers: Vec<_> = layers.into_iter().collect();
+        let (width, height) = layers.iter().fold((0, 0), |(width, height), layer| {
+            (
+                cmp::max(width, layer.width()),
+                cmp::max(height, layer.height()),
+            )
+        });
+        // Empty blocks are presented as spaces, so they are only filled if spaces are opaque.
+        let is_space_opaque = matches!(
+            policy.layer(&Grapheme::SPACE, &Grapheme::SPACE),
+            Layer::Front(_)
+        );
+        let layers: Vec<Vec<C>> = layers
+            .into_iter()
+            .filter_map(|layer| match layer.inner {
+                ModalBlock::Empty(block) if !is_space_opaque || block.width == 0 => None,
+                inner => Block::from(inner)
+                    .into_content_or_fill(Grapheme::SPACE)
+                    .ok(),
+            })
+            .map(|block| {
+                block
+                    .pad_to_height_at_bottom(height)
+                    .pad_to_width_at_right(width)
+                    .lines
+                    .into_iter()
+                    .collect()
+            })
+            .collect();
+        let last = match layers.len().checked_sub(1) {
+            Some(last) => last,
+            _ => return Block::with_dimensions(width, height),
+        };
+        let lines: Vec<_> = (0..height)
+            .map(|y| {
+                // Find the front-most layer shown in each cell of the row.
+                let shown: Vec<_> = {
+                    let graphemes: Vec<_> =
+                        layers.iter().map(|lines| lines[y].to_graphemes()).collect();
+                    let n = graphemes.iter().map(Vec::len).min().unwrap_or(0);
+                    (0..n)
+                        .map(|x| {
+                            (0..last)
+                                .find(|&k| {
+                                    matches!(
+                                        policy.layer(&graphemes[k][x], &graphemes[k + 1][x]),
+                                        Layer::Front(_)
+                                    )
+                                })
+                                .unwrap_or(last)
+                        })
+                        .collect()
+                };
+                // Concatenate runs of cells shown from the same layer.
+                let mut line = C::empty();
+                let mut x = 0usize;
+                while x < shown.len() {
+                    let k = shown[x];
+                    let n = shown[x..].iter().take_while(|&&j| j == k).count();
+                    line = C::concatenate(line, layers[k][y].clone().skip(x).truncate(n));
+                    x += n;
+                }
+                line
+            })
+            .collect();
+        Block {
+            inner: ContentBlock::from(lines).into(),
+        }
+    }
+}
+
+/// Fallible operations.
+impl<C> Block<C>
+where
+    C: Content,
+{
+    pub fn try_join_left_to_right_at_top(self, right: Self) -> Result<Self, CongruenceError> {
+        self.inner
+            .try_join_left_to_right_at_top(right.inner)
+            .map(From::from)
+    }
+
+    pub fn try_join_top_to_bottom_at_left(self, bottom: Self) -> Result<Self, CongruenceError> {
+        self.inner
+            .try_join_top_to_bottom_at_left(bottom.inner)
+            .map(From::from)
+    }
+
+    pub fn try_join_left_to_right_at_bottom(self, right: Self) -> Result<Self, CongruenceError> {
+        self.inner.validate()?;
+        right.inner.validate()?;
+        Ok(self.join_left_to_right_at_bottom(right))
+    }
+
+    pub fn try_join_top_to_bottom_at_right(self, bottom: Self) -> Result<Self, CongruenceError> {
+        self.inner.validate()?;
+        bottom.inner.validate()?;
+        Ok(self.join_top_to_bottom_at_right(bottom))
+    }
+
+    pub fn try_overlay(self, back: Self) -> Result<Self, CongruenceError> {
+        self.inner.try_overlay(back.inner).map(From::from)
+    }
+
+    pub fn try_overlay_by(
+        self,
+        back: Self,
+        policy: OverlayPolicy,
+    ) -> Result<Self, CongruenceError> {
+        self.try_overlay_with(back, |front, back| policy.layer(front, back))
+    }
+
+    pub fn try_overlay_with(
+        self,
+        back: Self,
+        f: impl FnMut(&Grapheme, &Grapheme) -> Layer,
+    ) -> Result<Self, CongruenceError> {
+        self.inner.try_overlay_with(back.inner, f).map(From::from)
+    }
+
+    pub fn try_overlay_masked(self, mask: &Mask, back: Self) -> Result<Self, CongruenceError> {
+        self.inner
+            .try_overlay_at_with(back.inner, |x, y, _, _| mask_layer(mask, x, y))
+            .map(From::from)
+    }
+}
+
+/// In-place operations.
+impl<C> Block<C>
+where
+    C: Content,
+{
+    pub fn reserve(&mut self, additional: usize) {
+        if let ModalBlock::Content(ref mut block) = self.inner {
+            block.lines.reserve(additional);
+        }
+    }
+
+    pub fn push_mut(&mut self, content: impl Into<C>) {
+        match self.inner {
+            ModalBlock::Content(ref mut block) => block.push_mut(content),
+            _ => self.map_mut(|block| block.push(content)),
+        }
+    }
+
+    pub fn pad_to_width_at_right_mut(&mut self, width: usize) {
+        match self.inner {
+            ModalBlock::Content(ref mut block) => block.pad_to_width_at_right_mut(width),
+            ModalBlock::Empty(ref mut block) => *block = block.pad_to_width_at_right(width),
+        }
+    }
+
+    pub fn pad_to_height_at_bottom_mut(&mut self, height: usize) {
+        match self.inner {
+            ModalBlock::Content(ref mut block) => block.pad_to_height_at_bottom_mut(height),
+            ModalBlock::Empty(ref mut block) => *block = block.pad_to_height_at_bottom(height),
+        }
+    }
+
+    pub fn join_left_to_right_at_top_mut(&mut self, right: Self) {
+        match (&mut self.inner, right.inner) {
+            (ModalBlock::Content(ref mut left), ModalBlock::Content(right)) => {
+                left.join_left_to_right_at_top_mut(right)
+            }
+            (_, right) => self.map_mut(|left| left.join_left_to_right_at_top(right.into())),
+        }
+    }
+
+    pub fn join_top_to_bottom_at_left_mut(&mut self, bottom: Self) {
+        match (&mut self.inner, bottom.inner) {
+            (ModalBlock::Content(ref mut top), ModalBlock::Content(bottom)) => {
+                top.join_top_to_bottom_at_left_mut(bottom)
+            }
+            (_, bottom) => self.map_mut(|top| top.join_top_to_bottom_at_left(bottom.into())),
+        }
+    }
+
+    fn map_mut(&mut self, f: impl FnOnce(Self) -> Self) {
+        *self = f(mem::replace(self, Block::zero()));
+    }
+}
+
+/// Terminal operations.
+#[cfg(feature = "terminal")]
+impl<C> Block<C>
+where
+    C: Content,
+{
+    /// Fits the block to the width of the terminal using the default [`FitPolicy`].
+    ///
+    /// The block is returned unchanged if the extent of the terminal cannot be determined. The
+    /// height of the block is not fit to the terminal.
+    #[must_use]
+    pub fn fit_to_terminal(self) -> Self {
+        self.fit_to_terminal_by(FitPolicy::default())
+    }
+
+    #[must_use]
+    pub fn fit_to_terminal_by(self, policy: FitPolicy) -> Self {
+        match terminal::terminal_extent() {
+            Some(extent) => self.fit_to_width_by(extent.width, policy),
+            _ => self,
+        }
+    }
+
+    /// Fits the block to the given width as if it were the width of a terminal.
+    #[must_use]
+    pub fn fit_to_width_by(self, width: usize, policy: FitPolicy) -> Self {
+        let block = match policy {
+            FitPolicy::Truncate(ellipsis) => self.truncate_to_width_at_right(width, ellipsis),
+            FitPolicy::Reflow(policy) => {
+                if self.width() > width {
+                    self.reflow_by(width, policy)
+                        .truncate_to_width_at_right(width, None)
+                }
+                else {
+                    self
+                }
+            }
+        };
+        block.pad_to_width_at_right(width)
+    }
+}
+
+/// Parallel operations.
+///
+/// These operations compose lines in parallel and are otherwise equivalent to their sequential
+/// counterparts.
+#[cfg(feature = "rayon")]
+impl<C> Block<C>
+where
+    C: Content + Send + Sync,
+{
+    /// Constructs a block from lines of content like `Block::from_lines`.
+    ///
+    /// Lines are split, measured, and normalized in parallel.
+    pub fn par_from_lines(lines: Vec<C>) -> Self {
+        let lines: Vec<_> = lines
+            .into_par_iter()
+            .flat_map_iter(Content::into_lines)
+            .collect();
+        if lines.is_empty() {
+            Block::zero()
+        }
+        else {
+            Block {
+                inner: ContentBlock::par_from(lines).into(),
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn par_pad_to_width_at_right(self, width: usize) -> Self {
+        match self.inner {
+            ModalBlock::Content(block) => {
+                ModalBlock::from(block.par_pad_to_width_at_right(width)).into()
+            }
+            inner => inner.pad_to_width_at_right(width).into(),
+        }
+    }
+
+    #[must_use]
+    pub fn par_join_left_to_right_at_top(self, right: Self) -> Self {
+        match (self.inner, right.inner) {
+            (ModalBlock::Content(left), ModalBlock::Content(right)) => {
+                ModalBlock::from(left.par_join_left_to_right_at_top(right)).into()
+            }
+            (left, right) => left.join_left_to_right_at_top(right).into(),
+        }
+    }
+
+    #[must_use]
+    pub fn par_join_top_to_bottom_at_left(self, bottom: Self) -> Self {
+        match (self.inner, bottom.inner) {
+            (ModalBlock::Content(top), ModalBlock::Content(bottom)) => {
+                ModalBlock::from(top.par_join_top_to_bottom_at_left(bottom)).into()
+            }
+            (top, bottom) => top.join_top_to_bottom_at_left(bottom).into(),
+        }
+    }
+
+    #[must_use]
+    pub fn par_overlay(self, back: Self) -> Self {
+        self.par_overlay_with(back, |front, back| {
+            OverlayPolicy::default().layer(front, back)
+        })
+    }
+
+    #[must_use]
+    pub fn par_overlay_with(
+        self,
+        back: Self,
+        f: impl Fn(&Grapheme, &Grapheme) -> Layer + Sync,
+    ) -> Self {
+        match (self.inner, back.inner) {
+            (ModalBlock::Content(front), ModalBlock::Content(back)) => {
+                ModalBlock::from(front.par_overlay_with(back, f)).into()
+            }
+            (front, back) => front.overlay_with(back, f).into(),
+        }
+    }
+}
+
+impl<C> Block<C>
+where
+    C: Content,
+{
+    #[must_use]
+    pub fn pad_at_left(self, width: usize) -> Self {
+        self.pad_along::<typed::LeftRight, typed::Left>(width)
+    }
+
+    #[must_use]
+    pub fn pad_at_right(self, width: usize) -> Self {
+        self.pad_along::<typed::LeftRight, typed::Right>(width)
+    }
+
+    #[must_use]
+    pub fn pad_at_top(self, height: usize) -> Self {
+        self.pad_along::<typed::TopBottom, typed::Top>(height)
+    }
+
+    #[must_use]
+    pub fn pad_at_bottom(self, height: usize) -> Self {
+        self.pad_along::<typed::TopBottom, typed::Bottom>(height)
+    }
+
+    #[must_use]
+    pub fn pad_to_width_at_left(self, width: usize) -> Self {
+        self.pad_to_length_along::<typed::LeftRight, typed::Left>(width)
+    }
+
+    #[must_use]
+    pub fn pad_to_height_at_top(self, height: usize) -> Self {
+        self.pad_to_length_along::<typed::TopBottom, typed::Top>(height)
+    }
+
+    #[must_use]
+    pub fn join_left_to_right_at_bottom(self, right: Self) -> Self {
+        self.join_along::<typed::LeftRight, typed::Bottom>(right)
+    }
+
+    #[must_use]
+    pub fn join_top_to_bottom_at_right(self, bottom: Self) -> Self {
+        self.join_along::<typed::TopBottom, typed::Right>(bottom)
+    }
+
+    #[must_use]
+    pub fn overlay_at_offset(self, back: Self, offset: Axial<usize>) -> Self {
+        let front = self
+            .pad_at_left(offset.horizontal)
+            .pad_at_top(offset.vertical);
+        front.overlay(back)
+    }
+
+    /// Overlays the block onto another block such that their corners `K` coincide.
+    #[must_use]
+    pub fn overlay_at_corner<K>(self, back: Self) -> Self
+    where
+        K: typed::Corner,
+    {
+        let width = cmp::max(self.width(), back.width());
+        let height = cmp::max(self.height(), back.height());
+        let pad = |block: Self| {
+            let block = match K::VALUE.horizontal() {
+                valued::HorizontalAlignment::Left => block.pad_to_width_at_right(width),
+                valued::HorizontalAlignment::Right => block.pad_to_width_at_left(width),
+            };
+            match K::VALUE.vertical() {
+                valued::VerticalAlignment::Top => block.pad_to_height_at_bottom(height),
+                valued::VerticalAlignment::Bottom => block.pad_to_height_at_top(height),
+            }
+        };
+        pad(self).overlay(pad(back))
+    }
+
+    /// Translates the block by a vector.
+    ///
+    /// Positive components pad the block at the left and top and negative components truncate
+    /// the block at the left and top, discarding the cells that are moved past the origin.
+    #[must_use]
+    pub fn translate(self, vector: AxisVector) -> Self {
+        let block = if vector.horizontal < 0 {
+            let width = self
+                .width()
+                .saturating_sub(vector.horizontal.unsigned_abs());
+            self.truncate_to_width_at_left(width, None)
+        }
+        else {
+            self.pad_at_left(vector.horizontal.unsigned_abs())
+        };
+        if vector.vertical < 0 {
+            let height = block
+                .height()
+                .saturating_sub(vector.vertical.unsigned_abs());
+            block.truncate_to_height_at_top(height, None)
+        }
+        else {
+            block.pad_at_top(vector.vertical.unsigned_abs())
+        }
+    }
+}
+
+/// Truncation.
+impl<C> Block<C>
+where
+    C: Content,
+{
+    #[must_use]
+    pub fn truncate_to_width_at_right(self, width: usize, ellipsis: Option<Grapheme>) -> Self {
+        self.inner
+            .truncate_to_width_at_right(width, ellipsis)
+            .into()
+    }
+
+    #[must_use]
+    pub fn truncate_to_width_at_left(self, width: usize, ellipsis: Option<Grapheme>) -> Self {
+        self.inner.truncate_to_width_at_left(width, ellipsis).into()
+    }
+
+    #[must_use]
+    pub fn truncate_to_height_at_bottom(self, height: usize, ellipsis: Option<Grapheme>) -> Self {
+        self.inner
+            .truncate_to_height_at_bottom(height, ellipsis)
+            .into()
+    }
+
+    #[must_use]
+    pub fn truncate_to_height_at_top(self, height: usize, ellipsis: Option<Grapheme>) -> Self {
+        self.inner
+            .truncate_to_height_at_top(height, ellipsis)
+            .into()
+    }
+}
+
+/// Searching.
+impl<C> Block<C>
+where
+    C: Content,
+{
+    /// Finds occurrences of text within lines of the block.
+    ///
+    /// Matches are aligned to graphemes and are yielded as the column and row of their first
+    /// grapheme.
+    pub fn find(&self, needle: &str) -> impl Iterator<Item = (usize, usize)> {
+        let needle: Vec<_> = content::graphemes(needle).collect();
+        let mut matches = vec![];
+        if !needle.is_empty() {
+            for (y, row) in self.cells().iter().enumerate() {
+                let mut x = 0usize;
+                for (index, glyph) in row.iter().enumerate() {
+                    let is_match = row.len() - index >= needle.len()
+                        && row[index..]
+                            .iter()
+                            .zip(needle.iter())
+                            .all(|(glyph, text)| glyph.get() == *text);
+                    if is_match {
+                        matches.push((x, y));
+                    }
+                    x += glyph.width();
+                }
+            }
+        }
+        matches.into_iter()
+    }
+
+    /// Gets the grapheme that occupies the given column and row.
+    ///
+    /// Wide graphemes occupy more than one column.
+    pub fn hit(&self, x: usize, y: usize) -> Option<Grapheme<'_>> {
+        match self.inner {
+            ModalBlock::Empty(ref block) => {
+                (x < block.width && y < block.height).then_some(Grapheme::SPACE)
+            }
+            ModalBlock::Content(ref block) => {
+                let mut column = 0usize;
+                block
+                    .lines
+                    .get(y)?
+                    .to_graphemes()
+                    .into_iter()
+                    .find(|glyph| {
+                        column += glyph.width();
+                        column > x
+                    })
+            }
+        }
+    }
+}
+
+/// Trimming.
+impl<C> Block<C>
+where
+    C: Content,
+{
+    #[must_use]
+    pub fn trim_at_left(self) -> Self {
+        self.trim_columns(
+            |line, n| line.skip(n),
+            |glyphs| {
+                glyphs
+                    .iter()
+                    .take_while(|glyph| **glyph == Grapheme::SPACE)
+                    .count()
+            },
+        )
+    }
+
+    #[must_use]
+    pub fn trim_at_right(self) -> Self {
+        self.trim_columns(
+            |line, n| {
+                let m = line.to_graphemes().len();
+                line.truncate(m - n)
+            },
+            |glyphs| {
+                glyphs
+                    .iter()
+                    .rev()
+                    .take_while(|glyph| **glyph == Grapheme::SPACE)
+                    .count()
+            },
+        )
+    }
+
+    #[must_use]
+    pub fn trim_at_top(self) -> Self {
+        let n = self.count_blank_rows(|lines| lines.iter());
+        self.split_at_height(n).1
+    }
+
+    #[must_use]
+    pub fn trim_at_bottom(self) -> Self {
+        let n = self.count_blank_rows(|lines| lines.iter().rev());
+        let height = self.height() - n;
+        self.split_at_height(height).0
+    }
+
+    #[must_use]
+    pub fn trim_horizontal(self) -> Self {
+        self.trim_at_left().trim_at_right()
+    }
+
+    #[must_use]
+    pub fn trim_vertical(self) -> Self {
+        self.trim_at_top().trim_at_bottom()
+    }
+
+    #[must_use]
+    pub fn trim(self) -> Self {
+        self.trim_vertical().trim_horizontal()
+    }
+
+    fn count_blank_rows<'i, I>(&'i self, f: impl FnOnce(&'i [C]) -> I) -> usize
+    where
+        I: Iterator<Item = &'i C>,
+    {
+        match self.inner {
+            ModalBlock::Empty(ref block) => block.height,
+            ModalBlock::Content(ref block) => {
+                f(&block.lines).take_while(|line| is_blank(*line)).count()
+            }
+        }
+    }
+
+    // Removes the given number of columns from each line, where the number is the least count of
+    // leading (or trailing) spaces among lines.
+    fn trim_columns(
+        self,
+        mut trim: impl FnMut(C, usize) -> C,
+        mut spaces: impl FnMut(&[Grapheme]) -> usize,
+    ) -> Self {
+        match self.inner {
+            ModalBlock::Empty(block) => Block::with_dimensions(0, block.height),
+            ModalBlock::Content(block) => {
+                let n = block
+                    .lines
+                    .iter()
+                    .map(|line| spaces(&line.to_graphemes()))
+                    .min()
+                    .unwrap_or(0);
+                if n == 0 {
+                    Block {
+                        inner: block.into(),
+                    }
+                }
+                else if n >= block.width() {
+                    Block::with_dimensions(0, block.height())
+                }
+                else {
+                    let lines: Vec<_> = block.lines.into_iter().map(|line| trim(line, n)).collect();
+                    Block {
+                        inner: ContentBlock::from(lines).into(),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Normalization.
+impl<C> Block<C>
+where
+    C: Content,
+{
+    /// Trims trailing space columns and rows.
+    ///
+    /// Blocks that are visually identical have the same normalized form. Blocks with no visible
+    /// content are normalized to the zero block.
+    #[must_use]
+    pub fn normalized(self) -> Self {
+        match self.inner {
+            ModalBlock::Empty(_) => Block::zero(),
+            ModalBlock::Content(block) => {
+                let mut lines: Vec<_> = block.lines.into_iter().map(wrap::trim_end).collect();
+                while lines.last().is_some_and(|line| line.width() == 0) {
+                    lines.pop();
+                }
+                if lines.is_empty() {
+                    Block::zero()
+                }
+                else {
+                    Block {
+                        inner: ContentBlock::from(lines).into(),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Compares the graphemes of blocks, ignoring trailing space columns and rows.
+    pub fn approx_eq<D>(&self, other: &Block<D>) -> bool
+    where
+        D: Content,
+    {
+        self.clone().normalized().cells() == other.clone().normalized().cells()
+    }
+}
+
+impl<C> Block<C>
+where
+    C: Content,
+{
+    pub fn split_at_width(self, width: usize) -> (Self, Self) {
+        let (left, right) = self.inner.split_at_width(width);
+        (left.into(), right.into())
+    }
+
+    pub fn split_at_height(self, height: usize) -> (Self, Self) {
+        let (top, bottom) = self.inner.split_at_height(height);
+        (top.into(), bottom.into())
+    }
+
+    /// Splits the block into pages that are `height` lines tall.
+    ///
+    /// The last page contains the remaining lines and may be shorter. A `height` of zero is
+    /// treated as one, so each page contains at least one line.
+    pub fn pages(self, height: usize) -> impl Iterator<Item = Self> {
+        let height = cmp::max(height, 1);
+        let mut block = self;
+        iter::from_fn(move || {
+            if block.height() == 0 {
+                None
+            }
+            else {
+                let (page, remainder) =
+                    mem::replace(&mut block, Block::zero()).split_at_height(height);
+                block = remainder;
+                Some(page)
+            }
+        })
+    }
+
+    /// Splits the block into pages that end with a footer.
+    ///
+    /// The last line of each page is the footer returned by the given function for the number of
+    /// the page and the number of pages. Returns an error if `height` is less than two, because
+    /// the footer leaves no lines for content.
+    pub fn pages_with_footer<F>(
+        self,
+        height: usize,
+        mut f: F,
+    ) -> Result<impl Iterator<Item = Self>, PageError>
+    where
+        F: FnMut(usize, usize) -> C,
+    {
+        // Reserve the last line of each page for the footer.
+        let height = height
+            .checked_sub(1)
+            .filter(|&n| n > 0)
+            .ok_or(PageError::ZeroContentHeight)?;
+        let pages: Vec<_> = self.pages(height).collect();
+        let n = pages.len();
+        Ok(pages.into_iter().enumerate().map(move |(index, page)| {
+            let (footer, _) = Block::with_content(f(index + 1, n)).split_at_height(1);
+            page.pad_to_height_at_bottom(height)
+                .join_top_to_bottom_at_left(footer)
+        }))
+    }
+
+    /// Repeats each grapheme `sx` times and each line `sy` times.
+    #[must_use]
+    pub fn scale(self, sx: usize, sy: usize) -> Self {
+        match self.inner {
+            ModalBlock::Empty(block) => Block::with_dimensions(block.width * sx, block.height * sy),
+            ModalBlock::Content(block) => {
+                let lines: Vec<_> = block
+                    .lines
+                    .into_iter()
+                    .flat_map(|line| iter::repeat_n(line.stretch(sx), sy))
+                    .collect();
+                Block {
+                    inner: ContentBlock::from(lines).into(),
+                }
+            }
+        }
+    }
+
+    /// Repeats the content of the block in both axes, truncating at the given dimensions.
+    #[must_use]
+    pub fn tile_to(self, width: usize, height: usize) -> Self {
+        Block::with_dimensions(width, height).fill(&self)
+    }
+
+    #[must_use]
+    pub fn reflow(self, width: usize) -> Self {
+        self.reflow_by(width, WrapPolicy::default())
+    }
+
+    #[must_use]
+    pub fn reflow_by(self, width: usize, policy: WrapPolicy) -> Self {
+        self.reflow_with(width, |paragraph, width| {
+            wrap::wrap_by(paragraph, width, policy)
+        })
+    }
+
+    /// Reflows the block like `reflow` and justifies all but the last line of each paragraph.
+    #[must_use]
+    pub fn reflow_justified(self, width: usize) -> Self {
+        self.reflow_justified_by(width, WrapPolicy::default())
+    }
+
+    #[must_use]
+    pub fn reflow_justified_by(self, width: usize, policy: WrapPolicy) -> Self {
+        self.reflow_with(width, |paragraph, width| {
+            wrap::justify_by(paragraph, width, policy)
+        })
+    }
+
+    fn reflow_with(self, width: usize, f: impl Fn(C, usize) -> Vec<C>) -> Self {
+        let lines = match self.inner {
+            ModalBlock::Empty(_) => {
+                return self;
+            }
+            ModalBlock::Content(block) => block.lines,
+        };
+        let mut paragraph: Option<C> = None;
+        let mut output = vec![];
+        for line in lines.into_iter().map(wrap::trim_end) {
+            if line.width() == 0 {
+                output.extend(paragraph.take().map(|paragraph| f(paragraph, width)));
+                output.push(vec![line]);
+            }
+            else {
+                paragraph = Some(match paragraph.take() {
+                    Some(paragraph) => C::concatenate(C::concatenate(paragraph, C::space()), line),
+                    None => line,
+                });
+            }
+        }
+        output.extend(paragraph.map(|paragraph| f(paragraph, width)));
+        let lines: Vec<_> = output.into_iter().flatten().collect();
+        Block {
+            inner: ContentBlock::from(lines)
+                .pad_to_width_at_right(width)
+                .into(),
+        }
+    }
+}
+
+/// Decorations.
+impl<C> Block<C>
+where
+    C: Content,
+{
+    #[must_use]
+    pub fn with_shadow<T>(self, offset: Axial<usize>, filler: T) -> Self
+    where
+        Self: Fill<C, T, Output = Self>,
+        T: Clone,
+    {
+        self.with_shadow_toward::<typed::BottomRight, T>(offset, filler)
+    }
+
+    /// Casts a shadow that is offset toward the corner `K`.
+    #[must_use]
+    pub fn with_shadow_toward<K, T>(self, offset: Axial<usize>, filler: T) -> Self
+    where
+        Self: Fill<C, T, Output = Self>,
+        K: typed::Corner,
+        T: Clone,
+    {
+        let Axial {
+            horizontal: x,
+            vertical: y,
+        } = offset;
+        let (width, height) = (self.width(), self.height());
+        let is_right = K::VALUE.horizontal() == valued::HorizontalAlignment::Right;
+        let is_bottom = K::VALUE.vertical() == valued::VerticalAlignment::Bottom;
+        // Positions of the shadow and the top of the block in the output.
+        let sx = if is_right { x } else { 0 };
+        let (by, sy) = if is_bottom { (0, y) } else { (y, 0) };
+        // Fills the intersection of the shadow with the given region of the output. The shadow
+        // is composited from the regions beyond the block rather than overlaid, because spaces
+        // within the block are not transparent here.
+        let region = |left: usize, right: usize, top: usize, bottom: usize| {
+            let (x0, x1) = (cmp::max(left, sx), cmp::min(right, sx + width));
+            let (y0, y1) = (cmp::max(top, sy), cmp::min(bottom, sy + height));
+            Block::filled(x1.saturating_sub(x0), y1.saturating_sub(y0), filler.clone())
+                .pad_at_left(x0.saturating_sub(left))
+                .pad_at_top(y0.saturating_sub(top))
+                .pad_to_width_at_right(right - left)
+                .pad_to_height_at_bottom(bottom - top)
+        };
+        let side = if is_right {
+            region(width, width + x, by, by + height)
+        }
+        else {
+            region(0, x, by, by + height)
+        };
+        let end = if is_bottom {
+            region(0, width + x, height, height + y)
+        }
+        else {
+            region(0, width + x, 0, y)
+        };
+        let middle = if is_right {
+            self.join_left_to_right_at_top(side)
+        }
+        else {
+            side.join_left_to_right_at_top(self)
+        };
+        if is_bottom {
+            middle.join_top_to_bottom_at_left(end)
+        }
+        else {
+            end.join_top_to_bottom_at_left(middle)
+        }
+    }
+
+    /// Prefixes each line with a gutter produced by the given function.
+    ///
+    /// The function receives the index of each line. Gutters are aligned at the right and padded
+    /// to the width of the widest gutter.
+    #[must_use]
+    pub fn with_gutter<T>(self, mut f: impl FnMut(usize) -> T) -> Self
+    where
+        T: Into<C>,
+    {
+        let gutters: Vec<C> = (0..self.height()).map(|y| f(y).into()).collect();
+        let width = gutters.iter().map(Content::width).max().unwrap_or(0);
+        let gutters: Vec<_> = gutters
+            .into_iter()
+            .map(|gutter| C::concatenate(C::space().repeat(width - gutter.width()), gutter))
+            .collect();
+        if gutters.is_empty() {
+            self
+        }
+        else {
+            Block {
+                inner: ContentBlock::from(gutters).into(),
+            }
+            .join_left_to_right_at_top(self)
+        }
+    }
+
+    /// Fills the region of identical graphemes that is connected to the given column and row.
+    ///
+    /// The region is bounded by differing graphemes, such as the outline of a box.
+    #[must_use]
+    pub fn flood_fill<T>(self, x: usize, y: usize, filler: T) -> Self
+    where
+        Self: Fill<C, T, Output = Self>,
+    {
+        let mask = Mask::flood(&self, x, y);
+        Block::filled_like(&self, filler).overlay_masked(&mask, self)
+    }
+
+    #[must_use]
+    pub fn outline<T>(self, filler: T) -> Self
+    where
+        Self: Fill<C, T, Output = Self>,
+    {
+        let (width, height) = (self.width(), self.height());
+        // Replace non-space content with the filler and spaces with spaces.
+        let mask = self
+            .clone()
+            .overlay_with(Block::filled(width, height, filler), |front, _| {
+                if *front == Grapheme::SPACE {
+                    Layer::Front(())
+                }
+                else {
+                    Layer::Back(())
+                }
+            });
+        let outline = (0..3).flat_map(|y| (0..3).map(move |x| (x, y))).fold(
+            Block::with_dimensions(width + 2, height + 2),
+            |outline, (x, y)| {
+                mask.clone().overlay_at_offset(
+                    outline,
+                    Axial {
+                        horizontal: x,
+                        vertical: y,
+                    },
+                )
+            },
+        );
+        self.overlay_at_offset(
+            outline,
+            Axial {
+                horizontal: 1,
+                vertical: 1,
+            },
+        )
+    }
+}
+
+/// Axis-generic operations.
+///
+/// Joins and pads along either axis and at any alignment are expressed with these operations,
+/// which reduce to the fundamental joins at the top and left and pads at the right and bottom.
+impl<C> Block<C>
+where
+    C: Content,
+{
+    // Joins the blocks along the axis `A` with their orthogonal extents aligned at `L`.
+    fn join_along<A, L>(self, other: Self) -> Self
+    where
+        A: typed::Axis,
+        L: typed::ContraAxial<A>,
+    {
+        let (first, second) = if L::VALUE == <A::Orthogonal as typed::Axis>::VALUE.origin() {
+            (self, other)
+        }
+        else {
+            let length = cmp::max(
+                self.length_at::<A::Orthogonal>(),
+                other.length_at::<A::Orthogonal>(),
+            );
+            // Blocks are aligned at `L` by padding at the opposite alignment.
+            (
+                self.pad_to_length_along::<A::Orthogonal, L::Opposite>(length),
+                other.pad_to_length_along::<A::Orthogonal, L::Opposite>(length),
+            )
+        };
+        match A::VALUE {
+            valued::Axis::LeftRight => first.inner.join_left_to_right_at_top(second.inner),
+            valued::Axis::TopBottom => first.inner.join_top_to_bottom_at_left(second.inner),
+        }
+        .into()
+    }
+
+    // Pads the block by `length` along the axis `A` at `L`.
+    fn pad_along<A, L>(self, length: usize) -> Self
+    where
+        A: typed::Axis,
+        L: typed::Coaxial<A>,
+    {
+        // Padding is empty, so spaces are only written into the lines of content.
+        let padding = match A::VALUE {
+            valued::Axis::LeftRight => Block::with_dimensions(length, self.height()),
+            valued::Axis::TopBottom => Block::with_dimensions(self.width(), length),
+        };
+        if L::VALUE == A::VALUE.origin() {
+            padding.join_along::<A, typed::OrthogonalOrigin<A>>(self)
+        }
+        else {
+            self.join_along::<A, typed::OrthogonalOrigin<A>>(padding)
+        }
+    }
+
+    // Pads the block along the axis `A` at `L` such that its length is at least `length`.
+    fn pad_to_length_along<A, L>(self, length: usize) -> Self
+    where
+        A: typed::Axis,
+        L: typed::Coaxial<A>,
+    {
+        if L::VALUE == A::VALUE.origin() {
+            let n = length.saturating_sub(self.length_at::<A>());
+            self.pad_along::<A, L>(n)
+        }
+        else {
+            match A::VALUE {
+                valued::Axis::LeftRight => self.inner.pad_to_width_at_right(length),
+                valued::Axis::TopBottom => self.inner.pad_to_height_at_bottom(length),
+            }
+            .into()
+        }
+    }
+
+    // Trims whitespace from the edge of the block along the axis `A` at `L`.
+    fn trim_along<A, L>(self) -> Self
+    where
+        A: typed::Axis,
+        L: typed::Coaxial<A>,
+    {
+        match (A::VALUE, L::VALUE == A::VALUE.origin()) {
+            (valued::Axis::LeftRight, true) => self.trim_at_left(),
+            (valued::Axis::LeftRight, false) => self.trim_at_right(),
+            (valued::Axis::TopBottom, true) => self.trim_at_top(),
+            (valued::Axis::TopBottom, false) => self.trim_at_bottom(),
+        }
+    }
+
+    // Truncates the block along the axis `A` at `L` such that its length is at most `length`.
+    fn truncate_along<A, L>(self, length: usize, ellipsis: Option<Grapheme>) -> Self
+    where
+        A: typed::Axis,
+        L: typed::Coaxial<A>,
+    {
+        match (A::VALUE, L::VALUE == A::VALUE.origin()) {
+            (valued::Axis::LeftRight, true) => self.truncate_to_width_at_left(length, ellipsis),
+            (valued::Axis::LeftRight, false) => self.truncate_to_width_at_right(length, ellipsis),
+            (valued::Axis::TopBottom, true) => self.truncate_to_height_at_top(length, ellipsis),
+            (valued::Axis::TopBottom, false) => self.truncate_to_height_at_bottom(length, ellipsis),
+        }
+    }
+
+    // Constructs an empty block with the given `length` along the axis `A` and `width` along the
+    // orthogonal axis.
+    fn with_length_along<A>(length: usize, width: usize) -> Self
+    where
+        A: typed::Axis,
+    {
+        match A::VALUE {
+            valued::Axis::LeftRight => Block::with_dimensions(length, width),
+            valued::Axis::TopBottom => Block::with_dimensions(width, length),
+        }
+    }
+}
+
+/// Statically parameterized operations.
+impl<C> Block<C>
+where
+    C: Content,
+{
+    pub fn with_length_at<A>(length: usize, width: usize) -> Self
+    where
+        Self: WithLength<A>,
+        A: typed::Axis,
+    {
+        WithLength::with_length(length, width)
+    }
+
+    pub fn spacer_at<A>(length: usize) -> Self
+    where
+        Self: WithLength<A>,
+        A: typed::Axis,
+    {
+        Self::with_length_at::<A>(length, 0)
+    }
+
+    /// Pads the block at the origin of the axis `A`.
+    ///
+    /// See `AxialBlock`.
+    #[must_use]
+    pub fn pad_at_origin<A>(self, length: usize) -> Self
+    where
+        Self: AxialBlock<A>,
+        A: typed::Axis,
+    {
+        Pad::<A::Origin>::pad(self, length)
+    }
+
+    /// Joins the blocks along the axis `A` at the origin of the orthogonal axis.
+    ///
+    /// See `AxialBlock`.
+    #[must_use]
+    pub fn join_at_origin<A>(self, other: Self) -> Self
+    where
+        Self: AxialBlock<A>,
+        A: typed::Axis,
+    {
+        Join::<A, typed::OrthogonalOrigin<A>>::join(self, other)
+    }
+
+    #[must_use]
+    pub fn pad_at<L>(self, length: usize) -> Self
+    where
+        Self: Pad<L>,
+        L: typed::Alignment,
+    {
+        Pad::pad(self, length)
+    }
+
+    #[must_use]
+    pub fn trim_at<L>(self) -> Self
+    where
+        Self: Trim<L>,
+        L: typed::Alignment,
+    {
+        Trim::trim(self)
+    }
+
+    #[must_use]
+    pub fn pad_to_length_at<A, L>(self, length: usize) -> Self
+    where
+        Self: PadToLength<A, L>,
+        A: typed::Axis,
+        L: typed::Coaxial<A>,
+    {
+        PadToLength::pad_to_length(self, length)
+    }
+
+    /// Pads both ends of the axis `A` such that the length of the block is at least `length`.
+    ///
+    /// When the padding cannot be split evenly, the end (the right or bottom) receives the
+    /// remainder.
+    #[must_use]
+    pub fn pad_to_length_centered_at<A>(self, length: usize) -> Self
+    where
+        A: typed::Axis,
+    {
+        let n = length.saturating_sub(self.length_at::<A>());
+        let (start, end) = (n / 2, n - (n / 2));
+        match A::VALUE {
+            valued::Axis::LeftRight => self.pad_at_left(start).pad_at_right(end),
+            valued::Axis::TopBottom => self.pad_at_top(start).pad_at_bottom(end),
+        }
+    }
+
+    #[must_use]
+    pub fn join_at<A, L>(self, other: Self) -> Self
+    where
+        Self: Join<A, L>,
+        A: typed::Axis,
+        L: typed::ContraAxial<A>,
+    {
+        Join::join(self, other)
+    }
+
+    #[must_use]
+    pub fn truncate_at<A, L>(self, length: usize) -> Self
+    where
+        Self: Truncate<A, L>,
+        A: typed::Axis,
+        L: typed::Coaxial<A>,
+    {
+        Truncate::truncate(self, length, None)
+    }
+
+    #[must_use]
+    pub fn truncate_with_ellipsis_at<A, L>(self, length: usize, ellipsis: Grapheme) -> Self
+    where
+        Self: Truncate<A, L>,
+        A: typed::Axis,
+        L: typed::Coaxial<A>,
+    {
+        Truncate::truncate(self, length, Some(ellipsis))
+    }
+}
+
+impl<'t> Block<Cow<'t, str>> {
+    pub fn into_owned(self) -> Block<Cow<'static, str>> {
+        Block {
+            inner: self.inner.into_owned(),
+        }
+    }
+}
+
+impl<C, S> Block<Styled<C, S>>
+where
+    C: AsRef<str> + Content + From<String>,
+    S: PartialEq + Style,
+{
+    /// Overlays the block onto another block and resolves the grapheme and style of each cell.
+    ///
+    /// See `Styled::overlay_resolved`.
+    #[must_use]
+    pub fn overlay_resolved(
+        self,
+        back: Self,
+        mut f: impl FnMut((&Grapheme, Option<&S>), (&Grapheme, Option<&S>)) -> Resolved<S>,
+    ) -> Self {
+        self.inner
+            .combine_with(back.inner, |_, content| {
+                Styled::overlay_resolved(content, &mut f)
+            })
+            .into()
+    }
+
+    pub fn try_overlay_resolved(
+        self,
+        back: Self,
+        mut f: impl FnMut((&Grapheme, Option<&S>), (&Grapheme, Option<&S>)) -> Resolved<S>,
+    ) -> Result<Self, CongruenceError> {
+        self.inner
+            .try_combine_with(back.inner, |_, content| {
+                Styled::overlay_resolved(content, &mut f)
+            })
+            .map(From::from)
+    }
+
+    #[must_use]
+    pub fn restyle(self, style: S) -> Self {
+        Block {
+            inner: self.inner.restyle(style),
+        }
+    }
+
+    /// Restyles the cells selected by a mask.
+    ///
+    /// Cells that are not selected retain their style. Empty blocks have no styled content and
+    /// are unchanged.
+    #[must_use]
+    pub fn restyle_masked(self, mask: &Mask, style: S) -> Self {
+        Block {
+            inner: self.inner.restyle_masked(mask, style),
+        }
+    }
+
+    #[must_use]
+    pub fn restyle_gradient(self, from: S, to: S, axis: valued::Axis) -> Self
+    where
+        S: Interpolate,
+    {
+        Block {
+            inner: self.inner.restyle_gradient(from, to, axis),
+        }
+    }
+}
+
+impl<C> sealed::Sealed for Block<C> where C: Content {}
+
+impl<C> DynamicallyAligned for Block<C>
+where
+    C: Content,
+{
+    fn with_length(axis: valued::Axis, length: usize, width: usize) -> Self {
+        use crate::align::valued::Axis;
+
+        match axis {
+            Axis::LeftRight => Block::with_dimensions(length, width),
+            Axis::TopBottom => Block::with_dimensions(width, length),
+        }
+    }
+
+    fn pad(self, alignment: impl Into<valued::Alignment>, length: usize) -> Self {
+        use crate::align::valued::Alignment;
+
+        match alignment.into() {
+            Alignment::LEFT => self.pad_at_left(length),
+            Alignment::RIGHT => self.pad_at_right(length),
+            Alignment::TOP => self.pad_at_top(length),
+            Alignment::BOTTOM => self.pad_at_bottom(length),
+        }
+    }
+
+    fn pad_to_length(self, alignment: impl Into<valued::Alignment>, length: usize) -> Self {
+        use crate::align::valued::Alignment;
+
+        match alignment.into() {
+            Alignment::LEFT => self.pad_to_width_at_left(length),
+            Alignment::RIGHT => self.pad_to_width_at_right(length),
+            Alignment::TOP => self.pad_to_height_at_top(length),
+            Alignment::BOTTOM => self.pad_to_height_at_bottom(length),
+        }
+    }
+
+    fn pad_to_length_centered(self, axis: valued::Axis, length: usize) -> Self {
+        use crate::align::valued::Axis;
+
+        match axis {
+            Axis::LeftRight => self.pad_to_length_centered_at::<typed::LeftRight>(length),
+            Axis::TopBottom => self.pad_to_length_centered_at::<typed::TopBottom>(length),
+        }
+    }
+
+    fn trim_edge(self, alignment: impl Into<valued::Alignment>) -> Self {
+        use crate::align::valued::Alignment;
+
+        match alignment.into() {
+            Alignment::LEFT => self.trim_at_left(),
+            Alignment::RIGHT => self.trim_at_right(),
+            Alignment::TOP => self.trim_at_top(),
+            Alignment::BOTTOM => self.trim_at_bottom(),
+        }
+    }
+
+    fn truncate(
+        self,
+        alignment: impl Into<valued::Alignment>,
+        length: usize,
+        ellipsis: Option<Grapheme>,
+    ) -> Self {
+        use crate::align::valued::Alignment;
+
+        match alignment.into() {
+            Alignment::LEFT => self.truncate_to_width_at_left(length, ellipsis),
+            Alignment::RIGHT => self.truncate_to_width_at_right(length, ellipsis),
+            Alignment::TOP => self.truncate_to_height_at_top(length, ellipsis),
+            Alignment::BOTTOM => self.truncate_to_height_at_bottom(length, ellipsis),
+        }
+    }
+
+    fn join(self, alignment: valued::AxialAlignment, other: Self) -> Self {
+        use crate::align::valued::AxialAlignment;
+
+        match alignment {
+            AxialAlignment::LEFT_RIGHT_AT_TOP => self.join_left_to_right_at_top(other),
+            AxialAlignment::LEFT_RIGHT_AT_BOTTOM => self.join_left_to_right_at_bottom(other),
+            AxialAlignment::TOP_BOTTOM_AT_LEFT => self.join_top_to_bottom_at_left(other),
+            AxialAlignment::TOP_BOTTOM_AT_RIGHT => self.join_top_to_bottom_at_right(other),
+        }
+    }
+
+    fn overlay_at(self, corner: valued::Corner, back: Self) -> Self {
+        use crate::align::valued::Corner;
+
+        match corner {
+            Corner::TopLeft => self.overlay_at_corner::<typed::TopLeft>(back),
+            Corner::TopRight => self.overlay_at_corner::<typed::TopRight>(back),
+            Corner::BottomLeft => self.overlay_at_corner::<typed::BottomLeft>(back),
+            Corner::BottomRight => self.overlay_at_corner::<typed::BottomRight>(back),
+        }
+    }
+}
+
+impl<C> Fill<C, C> for Block<C>
+where
+    C: Content,
+{
+    type Output = Self;
+
+    fn fill(self, content: C) -> Self::Output {
+        let block = EmptyBlock {
+            width: self.width(),
+            height: self.height(),
+        };
+        Block {
+            inner: block.fill_or_empty(content),
+        }
+    }
+}
+
+impl<'t, C> Fill<C, Grapheme<'t>> for Block<C>
+where
+    C: Content,
+{
+    type Output = Self;
+
+    fn fill(self, glyph: Grapheme<'t>) -> Self::Output {
+        let block = EmptyBlock {
+            width: self.width(),
+            height: self.height(),
+        };
+        Block {
+            inner: block.fill_or_empty(glyph),
+        }
+    }
+}
+
+impl<C, T, F> Fill<C, FromFn<F>> for Block<C>
+where
+    C: Content + FromCell<T>,
+    T: Cell,
+    F: FnMut(usize, usize) -> T,
+{
+    type Output = Self;
+
+    fn fill(self, filler: FromFn<F>) -> Self::Output {
+        let block = EmptyBlock {
+            width: self.width(),
+            height: self.height(),
+        };
+        Block {
+            inner: block.fill_or_empty(filler),
+        }
+    }
+}
+
+impl<'b, C> Fill<C, &'b Block<C>> for Block<C>
+where
+    C: Content,
+{
+    type Output = Self;
+
+    fn fill(self, block: &'b Block<C>) -> Self::Output {
+        let empty = EmptyBlock {
+            width: self.width(),
+            height: self.height(),
+        };
+        Block {
+            inner: empty.fill_or_empty(block),
+        }
+    }
+}
+
+// Each item is split into lines.
+impl<C> FromIterator<C> for Block<C>
+where
+    C: Content,
+{
+    fn from_iter<I>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+    {
+        let lines: Vec<_> = lines.into_iter().flat_map(Content::into_lines).collect();
+        if lines.is_empty() {
+            Block::zero()
+        }
+        else {
+            Block {
+                inner: ContentBlock::from(lines).into(),
+            }
+        }
+    }
+}
+
+// Each item is split into lines and pushed onto the bottom of the block.
+impl<C> Extend<C> for Block<C>
+where
+    C: Content,
+{
+    fn extend<I>(&mut self, lines: I)
+    where
+        I: IntoIterator<Item = C>,
+    {
+        let lines = lines.into_iter();
+        self.reserve(lines.size_hint().0);
+        for line in lines {
+            self.push_mut(line);
+        }
+    }
+}
+
+impl<C> IntoIterator for Block<C>
+where
+    C: Content,
+{
+    type Item = C;
+    type IntoIter = vec::IntoIter<C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_content_vec().into_iter()
+    }
+}
+
+impl<'t> From<&'t LineBuffer> for Block<Cow<'t, str>> {
+    fn from(buffer: &'t LineBuffer) -> Self {
+        // Lines are borrowed from the buffer and are only copied if they must be padded.
+        let lines: Vec<_> = buffer.lines().map(Cow::Borrowed).collect();
+        Block {
+            inner: ContentBlock::from(lines).into(),
+        }
+    }
+}
+
+impl From<Block<String>> for Block<Cow<'static, str>> {
+    fn from(block: Block<String>) -> Self {
+        Block {
+            inner: block.inner.map_lines(Cow::Owned),
+        }
+    }
+}
+
+impl<C, S> From<Block<C>> for Block<Styled<C, S>>
+where
+    C: AsRef<str> + Content + From<String>,
+    S: PartialEq + Style,
+{
+    fn from(block: Block<C>) -> Self {
+        Block {
+            inner: block.inner.map_lines(Styled::plain),
+        }
+    }
+}
+
+impl<C> From<ModalBlock<C>> for Block<C>
+where
+    C: Content,
+{
+    fn from(block: ModalBlock<C>) -> Self {
+        Block { inner: block }
+    }
+}
+
+impl<C, A, L> Join<A, L> for Block<C>
+where
+    C: Content,
+    A: typed::Axis,
+    L: typed::ContraAxial<A>,
+{
+    fn join(self, other: Self) -> Self {
+        self.join_along::<A, L>(other)
+    }
+}
+
+impl<C, L> Pad<L> for Block<C>
+where
+    C: Content,
+    L: typed::Alignment,
+{
+    fn pad(self, width: usize) -> Self {
+        self.pad_along::<L::Axis, L>(width)
+    }
+}
+
+impl<C, L> Trim<L> for Block<C>
+where
+    C: Content,
+    L: typed::Alignment,
+{
+    fn trim(self) -> Self {
+        self.trim_along::<L::Axis, L>()
+    }
+}
+
+impl<C, A, L> PadToLength<A, L> for Block<C>
+where
+    C: Content,
+    A: typed::Axis,
+    L: typed::Coaxial<A>,
+{
+    fn pad_to_length(self, length: usize) -> Self {
+        self.pad_to_length_along::<A, L>(length)
+    }
+}
+
+impl<C, A, L> Truncate<A, L> for Block<C>
+where
+    C: Content,
+    A: typed::Axis,
+    L: typed::Coaxial<A>,
+{
+    fn truncate(self, length: usize, ellipsis: Option<Grapheme>) -> Self {
+        self.truncate_along::<A, L>(length, ellipsis)
+    }
+}
+
+impl<C> Display for Block<C>
+where
+    C: Content,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.render())
+    }
+}
+
+impl<C> Render for Block<C>
+where
+    C: Content,
+{
+    fn render_into(&self, target: &mut impl Write) -> io::Result<()> {
+        self.render_into_with(target, &RenderContext::default())
+    }
+
+    fn render(&self) -> Cow<'_, str> {
+        self.render_with(&RenderContext::default())
+    }
+
+    fn render_into_with(&self, target: &mut impl Write, context: &RenderContext) -> io::Result<()> {
+        if let ModalBlock::Content(ref block) = self.inner {
+            let is_isolated = is_isolated();
+            for line in block.lines.iter() {
+                if is_isolated {
+                    let text = content::isolate(&line.render_with(context));
+                    target.write_all(text.as_bytes())?;
+                }
+                else {
+                    line.render_into_with(target, context)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn render_with(&self, context: &RenderContext) -> Cow<'_, str> {
+        self.render_lines_with(context)
+            .fold(String::new(), |mut output, line| {
+                output.push_str(&line);
+                output.push('\n');
+                output
+            })
+            .into()
+    }
+}
+
+impl<C, A> WithLength<A> for Block<C>
+where
+    C: Content,
+    A: typed::Axis,
+{
+    fn with_length(length: usize, width: usize) -> Self {
+        Block::with_length_along::<A>(length, width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::io::{self, Write};
+    use std::ptr;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::align::typed::{self, Bottom, Left, LeftRight, Right, Top, TopBottom};
+    use crate::align::valued::{self, HorizontalAlignment};
+    use crate::align::{
+        Axial, CornerEnvelope as _, Corners, Horizontal, HorizontalEnvelope as _, Invert as _,
+        Nonant, Perimeter, Quadrant, Rotate as _, Square, Vertical, VerticalEnvelope as _,
+    };
+    use crate::animation::Frames;
+    use crate::ansi::AnsiStyle;
+    use crate::block::{self, AxialBlock, Block, Fill, ModalBlock};
+    use crate::content::{
+        self, CellBuffer, Content as _, Grapheme, Interpolate, Layer, LineBreakPolicy, LineBuffer,
+        OverflowPolicy, OverlayPolicy, Resolved, SanitizePolicy, Style as Transform, Styled,
+        StyledBuilder, StyledCell, WidthPolicy,
+    };
+    use crate::error::{CongruenceError, FillError, GraphemeError, PageError, ThresholdError};
+    use crate::layout::{self, Columns};
+    use crate::markdown::{self, ToMarkdown};
+    use crate::mask::Mask;
+    use crate::primitive::{
+        self, Arrow, AxialPalette, AxisVector, CodeBlock, CodeOverflow, Font, Gauge, Grid, Heads,
+        Level, Levels, Line, LinePalette, List, Marker, Palette, Rect, Stroke, Theme,
+    };
+    use crate::table::{Cell, Column, Records, RowAlignment, Table, Tabulate};
+    use crate::wrap::{self, WrapPolicy};
+    use crate::{ColorDepth, Render, RenderContext};
+
+    // Runs a test that modifies global settings (e.g., `AmbiguousWidth`) in its own process, so
+    // that the settings cannot affect tests that run concurrently. The test is given by its path
+    // within this module.
+    fn isolated(test: &str, f: impl FnOnce()) {
+        const ISOLATED: &str = "HAKO_TEST_ISOLATED";
+
+        if std::env::var_os(ISOLATED).is_some() {
+            f();
+        }
+        else {
+            let status = std::process::Command::new(std::env::current_exe().unwrap())
+                .args([
+                    &format!("block::tests::{}", test),
+                    "--exact",
+                    "--test-threads=1",
+                ])
+                .env(ISOLATED, "1")
+                .status()
+                .unwrap();
+            assert!(status.success(), "isolated test `{}` failed", test);
+        }
+    }
+
+    // Content that implements only the required functions of `Content`. If `IS_REPEATABLE` is
+    // `false`, then `repeat` returns empty content, so lines are never padded.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Text<const IS_REPEATABLE: bool = true>(String);
+
+    impl<const IS_REPEATABLE: bool> Render for Text<IS_REPEATABLE> {
+        fn render(&self) -> Cow<'_, str> {
+            self.0.render()
+        }
+    }
+
+    impl<const IS_REPEATABLE: bool> From<&'_ str> for Text<IS_REPEATABLE> {
+        fn from(text: &str) -> Self {
+            Text(text.to_owned())
+        }
+    }
+
+    impl<const IS_REPEATABLE: bool> content::Content for Text<IS_REPEATABLE> {
+        fn empty() -> Self {
+            Text(String::empty())
+        }
+
+        fn grapheme(glyph: Grapheme) -> Self {
+            Text(String::grapheme(glyph))
+        }
+
+        fn repeat(self, n: usize) -> Self {
+            if IS_REPEATABLE {
+                Text(self.0.repeat(n))
+            }
+            else {
+                Text::empty()
+            }
+        }
+
+        fn truncate(self, width: usize) -> Self {
+            Text(content::Content::truncate(self.0, width))
+        }
+
+        fn sanitize(self, policy: &SanitizePolicy) -> Self {
+            Text(self.0.sanitize(policy))
+        }
+
+        fn into_lines_with(self, policy: LineBreakPolicy) -> Vec<Self> {
+            self.0
+                .into_lines_with(policy)
+                .into_iter()
+                .map(Text)
+                .collect()
+        }
+
+        fn concatenate(left: Self, right: Self) -> Self {
+            Text(left.0 + &right.0)
+        }
+
+        fn overlay_with(
+            content: content::Congruent<Self>,
+            f: impl FnMut(&Grapheme, &Grapheme) -> Layer,
+        ) -> Self {
+            let (front, back) = content.into();
+            Text(String::overlay_with(
+                content::Congruent::try_from((front.0, back.0)).unwrap(),
+                f,
+            ))
+        }
+
+        fn width(&self) -> usize {
+            self.0.width()
+        }
+    }
+
+    #[test]
     fn block_empty() {
         let block = <Block>::zero();
         assert!(block.is_empty());
 
-        let block = <Block>::with_width(1);
-        assert!(block.is_empty());
+        let block = <Block>::with_width(1);
+        assert!(block.is_empty());
+
+        let block = <Block>::with_height(1);
+        assert!(block.is_empty());
+
+        let block = <Block>::with_width(1).join_top_to_bottom_at_left(Block::with_height(1));
+        assert!(block.is_empty());
+
+        let mut block = <Block>::zero();
+        block = block.push("");
+        assert!(!block.is_empty());
+    }
+
+    #[test]
+    fn block_lazy_composition() {
+        let compose = |a: Block, b: Block| {
+            a.pad_at_left(1)
+                .pad_at_left(1)
+                .join_top_to_bottom_at_right(b.pad_to_width_at_right(4))
+                .overlay(Block::with_dimensions(6, 4))
+        };
+        let lazy = <Block>::with_content("ab")
+            .lazy()
+            .pad_at_left(1)
+            .pad_at_left(1)
+            .join_top_to_bottom_at_right(<Block>::with_content("c").lazy().pad_to_width_at_right(4))
+            .overlay(Block::with_dimensions(6, 4).lazy());
+        let eager = compose(Block::with_content("ab"), Block::with_content("c"));
+        assert_eq!((lazy.width(), lazy.height()), (6, 4));
+        assert_eq!(lazy.render(), eager.render());
+        assert_eq!(lazy.evaluate(), eager);
+
+        // Back blocks are skipped only if the front block is opaque and covers them.
+        let back = || <Block>::with_content("wx\nyz").lazy();
+        let overlay = |front: &str| {
+            <Block>::with_content(front)
+                .lazy()
+                .overlay(back())
+                .render()
+                .into_owned()
+        };
+        assert_eq!(overlay("ab\ncd"), "ab\ncd\n");
+        assert_eq!(overlay("a \ncd"), "ax\ncd\n");
+        assert_eq!(overlay("abc"), "abc\nyz\n");
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn block_parallel_composition() {
+        let left = <Block>::with_content("a\nbc\nd");
+        let right = <Block>::with_content("ef\ng");
+        assert_eq!(
+            left.clone().par_join_left_to_right_at_top(right.clone()),
+            left.clone().join_left_to_right_at_top(right.clone()),
+        );
+        assert_eq!(left.clone().par_overlay(right.clone()), left.overlay(right),);
+
+        let lines = vec!["a".to_owned(), "bcd\ne".to_owned(), String::new()];
+        assert_eq!(
+            <Block>::par_from_lines(lines.clone()),
+            <Block>::from_lines(lines)
+        );
+        assert_eq!(<Block>::par_from_lines(vec![]), <Block>::zero());
+    }
+
+    #[test]
+    fn block_line_break_policy() {
+        let lines = |text: &str, policy| {
+            <Block>::with_content_split_by(text, policy)
+                .into_content_vec()
+                .into_iter()
+                .map(|line| line.trim_end().to_owned())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(lines("a\r\nb\n", LineBreakPolicy::Simple), ["a", "b"]);
+        assert_eq!(lines("a\u{2028}b", LineBreakPolicy::Simple), ["a\u{2028}b"]);
+        assert_eq!(
+            lines("a\rb\u{2028}c\u{C}d\r\n", LineBreakPolicy::Unicode),
+            ["a", "b", "c", "d"],
+        );
+        // A trailing line break does not begin an empty line, but a trailing blank line is kept.
+        assert_eq!(lines("a\n\n", LineBreakPolicy::Simple), ["a", ""]);
+        assert_eq!(
+            lines("a\u{2029}\u{2029}", LineBreakPolicy::Unicode),
+            ["a", ""]
+        );
+
+        let styled = |text: &str| {
+            Styled::<String>::concatenate(
+                Styled::new((), "a\n".to_owned()),
+                Styled::plain(text.to_owned()),
+            )
+            .into_lines()
+            .len()
+        };
+        assert_eq!(styled(""), 1);
+        assert_eq!(styled("\n"), 2);
+        assert_eq!(styled("\u{200B}"), 2);
+        assert_eq!(styled("\n\u{200B}"), 3);
+        assert_eq!(String::from("a\n\u{200B}").into_lines().len(), 2);
+    }
+
+    #[test]
+    fn block_ambiguous_width() {
+        isolated("block_ambiguous_width", || {
+            use crate::content::AmbiguousWidth;
+
+            let ellipsis = || OverflowPolicy::Ellipsis(Grapheme::from('\u{2026}'));
+            let text = String::from("\u{2022}\u{2022}ab");
+            assert_eq!(AmbiguousWidth::get(), AmbiguousWidth::Narrow);
+            assert_eq!(<Block>::with_content("a\u{2022}b").width(), 3);
+            assert_eq!(
+                text.clone().truncate_to_width_with(3, ellipsis()),
+                Ok(("\u{2022}\u{2022}\u{2026}".to_owned(), 3)),
+            );
+
+            AmbiguousWidth::Wide.set();
+            assert_eq!(AmbiguousWidth::get(), AmbiguousWidth::Wide);
+            assert_eq!(<Block>::with_content("a\u{2022}b").width(), 4);
+            assert_eq!(
+                text.truncate_to_width_with(4, ellipsis()),
+                Ok(("\u{2022}\u{2026}".to_owned(), 4)),
+            );
+        });
+    }
+
+    #[test]
+    fn block_cached_width() {
+        isolated("block_cached_width", || {
+            use crate::content::AmbiguousWidth;
+
+            struct Armenian;
+
+            impl WidthPolicy for Armenian {
+                fn width(&self, glyph: &Grapheme) -> Option<usize> {
+                    (glyph.get() == "\u{561}").then_some(3)
+                }
+            }
+
+            // Widths that are cached before changing width settings are measured again.
+            let styled = Styled::<String>::concatenate(
+                Styled::plain("\u{561}".to_owned()),
+                Styled::new((), "\u{2026}".to_owned()),
+            );
+            let block = <Block>::with_content("\u{561}");
+            assert_eq!((styled.width(), block.width()), (2, 1));
+            content::set_width_policy(Armenian);
+            assert_eq!((styled.width(), block.width()), (4, 3));
+            AmbiguousWidth::Wide.set();
+            assert_eq!(styled.width(), 5);
+            content::clear_width_policy();
+            AmbiguousWidth::Narrow.set();
+            assert_eq!((styled.width(), block.width()), (2, 1));
+
+            // Cell buffers measure their cells again, so lines agree with other content.
+            let buffer = CellBuffer::from("a\u{2026}");
+            assert_eq!((buffer.width(), buffer.width_at(1)), (2, Some(1)));
+            AmbiguousWidth::Wide.set();
+            assert_eq!((buffer.width(), buffer.width_at(1)), (3, Some(2)));
+            let line = CellBuffer::concatenate(buffer.clone(), CellBuffer::from("\u{2026}"));
+            assert_eq!(line.width(), 5);
+            assert_eq!(buffer.truncate(1).width(), 1);
+        });
+    }
+
+    #[test]
+    fn block_width_policy() {
+        isolated("block_width_policy", || {
+            struct Armenian;
+
+            impl WidthPolicy for Armenian {
+                fn width(&self, glyph: &Grapheme) -> Option<usize> {
+                    (glyph.get() == "\u{561}").then_some(3)
+                }
+            }
+
+            content::set_width_policy(Armenian);
+            let lines: Vec<String> = vec!["\u{561}a".into(), "bcde".into(), "f".into()];
+            let block = <Block>::from_lines(lines.clone());
+            assert_eq!(block.width(), 4);
+            assert_eq!(block.render(), "\u{561}a\nbcde\nf\n");
+            assert_eq!(
+                <Block>::with_content("\u{561}\u{561}")
+                    .join_left_to_right_at_top(block.clone())
+                    .width(),
+                10,
+            );
+            #[cfg(feature = "rayon")]
+            {
+                // The policy also applies on the worker threads of parallel operations.
+                assert_eq!(<Block>::par_from_lines(lines), block);
+                assert_eq!(
+                    <Block>::with_content("\u{561}\u{561}")
+                        .par_join_left_to_right_at_top(block.clone())
+                        .width(),
+                    10,
+                );
+            }
+
+            content::clear_width_policy();
+            assert_eq!(<Block>::with_content("\u{561}a").width(), 2);
+        });
+    }
+
+    #[test]
+    fn block_debug_grid() {
+        let block = <Block>::with_content("ab\nc");
+        assert_eq!(block.to_string(), "ab\nc\n");
+        assert_eq!(
+            format!("{:?}", block.debug_grid()),
+            "   01\n  +--+\n0 |ab|\n1 |c\u{B7}|\n  +--+",
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn block_testing_diff() {
+        use crate::testing;
+
+        let block = <Block>::with_content("ab\ncd");
+        crate::assert_block_eq!(block, Block::<Rc<str>>::with_content("ab\ncd"));
+        assert_eq!(
+            testing::diff(&block, &Block::<Rc<str>>::with_content("ab\ncx")).unwrap(),
+            "left: 2x2, right: 2x2\n0 |ab|ab|\n1 |cd|cx|  ^\n",
+        );
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn block_join_normalizes_width(
+            left in crate::strategy::block::<String>(8, 4),
+            right in crate::strategy::block::<String>(8, 4),
+        ) {
+            let block = left.join_top_to_bottom_at_left(right);
+            for row in block.cells() {
+                proptest::prop_assert_eq!(
+                    row.iter().map(Grapheme::width).sum::<usize>(),
+                    block.width(),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn block_from_rendered() {
+        let block =
+            <Block>::with_content("ab\nc").join_left_to_right_at_top(Block::with_content("d"));
+        assert_eq!(
+            <Block>::from_rendered(&block.render()).render(),
+            block.render()
+        );
+
+        let block = <Block>::from_rendered("\x1B[1;31ma\x1B[0m\tb\n\x1B]0;title\x07c\n");
+        assert_eq!(block.render(), "a       b\nc\n");
+        assert_eq!(block.width(), 9);
+    }
+
+    #[test]
+    fn block_normalized() {
+        let block = <Block>::with_content("a")
+            .join_left_to_right_at_top(Block::with_content("b"))
+            .pad_at_right(2)
+            .pad_at_bottom(1);
+        assert_eq!(block.clone().normalized(), Block::with_content("ab"));
+        assert!(block.approx_eq(&Block::<Rc<str>>::with_content("ab ")));
+        assert!(!block.approx_eq(&Block::<Rc<str>>::with_content(" ab")));
+        assert!(<Block>::filled(2, 2, Grapheme::SPACE)
+            .normalized()
+            .approx_eq(&<Block>::with_dimensions(3, 1)));
+    }
+
+    #[test]
+    fn block_stack_macro() {
+        let a = <Block>::with_content("a\na");
+        let b = <Block>::with_content("b");
+        let footer = <Block>::with_content("ccccc");
+        let block = crate::hako!(v [h [a, b; gap = 1, align = bottom], footer; align = right]);
+        assert_eq!(block.render(), "  a\n  a b\nccccc\n");
+    }
+
+    #[test]
+    fn block_builder() {
+        use std::fmt::Write as _;
+
+        use crate::block::BlockBuilder;
+
+        let mut builder = BlockBuilder::new();
+        write!(builder, "{}-", 1).unwrap();
+        writeln!(builder, "{}", 2).unwrap();
+        write!(builder, "three").unwrap();
+        let block: Block = builder.finish();
+        assert_eq!(block.render(), "1-2\nthree\n");
+        assert_eq!(block.width(), 5);
+    }
+
+    #[test]
+    fn block_push_aligned() {
+        use crate::align::valued::HorizontalAlignment;
+
+        let block = <Block>::with_content("header")
+            .push_aligned(HorizontalAlignment::Right, "ab")
+            .push_centered("cd")
+            .push_aligned_truncated(HorizontalAlignment::Right, "overflowing");
+        assert_eq!(block.render(), "header\n    ab\n  cd\noverfl\n");
+
+        let block = <Block>::with_content("ab\nc").push_centered("defgh");
+        assert_eq!(block.render(), " ab\n c\ndefgh\n");
+        let block = <Block>::with_content("ab").push_centered("c");
+        assert_eq!(block.render(), "ab\nc\n");
+    }
+
+    #[test]
+    fn block_extent() {
+        use crate::align::valued;
+
+        let block = <Block>::with_content("abc\nd");
+        assert_eq!(block.length_at::<LeftRight>(), 3);
+        assert_eq!(block.length_at::<TopBottom>(), 2);
+        assert_eq!(block.extent().length(valued::Axis::LeftRight), 3);
+    }
+
+    #[test]
+    fn block_dimension_matching() {
+        let block = <Block>::with_content("abc\nd");
+        let mask = <Block>::filled_like(&block, Grapheme::from('#'));
+        assert_eq!(mask.render(), "###\n###\n");
+        assert_eq!(<Block>::with_extent_of(&block).extent(), block.extent());
+        let spacer = <Block>::spacer_at::<TopBottom>(2);
+        assert_eq!((spacer.width(), spacer.height()), (0, 2));
+    }
+
+    #[test]
+    fn block_scale() {
+        use crate::content::Content;
+
+        let block = <Block>::with_content("ab\nc").scale(2, 2);
+        assert_eq!(block.render(), "aabb\naabb\ncc\ncc\n");
+        assert_eq!(block.width(), 4);
+
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        struct Upper(bool);
+
+        impl Transform for Upper {
+            fn apply<'t>(&self, text: &'t str) -> Cow<'t, str> {
+                if self.0 {
+                    text.to_uppercase().into()
+                }
+                else {
+                    text.into()
+                }
+            }
+        }
+
+        let block = Block::<Styled<String, Upper>>::with_content(Content::concatenate(
+            Styled::new(Upper(true), "a"),
+            Styled::new(Upper(false), "b"),
+        ))
+        .scale(2, 1);
+        assert_eq!(block.render(), "AAbb\n");
+
+        assert_eq!(String::from("a\u{4E00}").stretch(2), "aa\u{4E00}\u{4E00}");
+        assert_eq!(Cow::from("ab").stretch(0), "");
+        let buffer = CellBuffer::from("a\u{4E00}").stretch(3);
+        assert_eq!(
+            (buffer.as_str(), buffer.width()),
+            ("aaa\u{4E00}\u{4E00}\u{4E00}", 9)
+        );
+    }
+
+    #[test]
+    fn block_trim() {
+        let block = <Block>::with_content("\n  ab \n   c\n\n");
+        assert_eq!(block.clone().trim().render(), "ab\n c\n");
+        assert_eq!(block.clone().trim_at::<Top>().height(), 3);
+        assert_eq!(block.trim_horizontal().width(), 2);
+        assert_eq!(
+            <Block>::with_dimensions(2, 2).trim().extent(),
+            Default::default()
+        );
+    }
+
+    #[test]
+    fn block_find_and_hit() {
+        let block = <Block>::with_content("ab\u{4E00}ab\nxab");
+        assert_eq!(
+            block.find("ab").collect::<Vec<_>>(),
+            vec![(0, 0), (4, 0), (1, 1)]
+        );
+        assert_eq!(block.hit(3, 0), Some(Grapheme::from('\u{4E00}')));
+        assert_eq!(block.hit(5, 1), Some(Grapheme::SPACE));
+        assert_eq!(block.hit(6, 2), None);
+    }
+
+    #[test]
+    fn block_masked() {
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        struct Upper(bool);
+
+        impl Transform for Upper {
+            fn apply<'t>(&self, text: &'t str) -> Cow<'t, str> {
+                if self.0 {
+                    text.to_uppercase().into()
+                }
+                else {
+                    text.into()
+                }
+            }
+        }
+
+        let front = <Block>::with_content("ab  \ncd");
+        let mask = Mask::from_predicate(&front, |glyph| glyph.get() != "b");
+        assert!(mask.get(2, 0) && mask.get(3, 1) && !mask.get(1, 0));
+        let block = front.overlay_masked(&mask, Block::filled(4, 2, Grapheme::from('x')));
+        assert_eq!(block.render(), "ax\ncd\n");
+        assert_eq!(block.hit(2, 1), Some(Grapheme::SPACE));
+
+        let block =
+            Block::<Styled<String, Upper>>::with_content(Styled::new(Upper(false), "ab\ncd"))
+                .restyle_masked(&mask.invert(), Upper(true));
+        assert_eq!(block.render(), "aB\ncd\n");
+    }
+
+    #[test]
+    fn block_flood_fill() {
+        let block = <Block>::with_content("+--+\n|  |\n+--+").pad_at_right(1);
+        let block = block.flood_fill(1, 1, Grapheme::from('#'));
+        assert_eq!(block.render(), "+--+\n|##|\n+--+\n");
+        let block = block.flood_fill(4, 0, Grapheme::from('.'));
+        assert_eq!(block.render(), "+--+.\n|##|.\n+--+.\n");
+        assert_eq!(Mask::flood(&block, 9, 9), Mask::with_dimensions(5, 3));
+    }
+
+    #[test]
+    fn block_polyline() {
+        let block: Block = primitive::polyline([(0, 0), (2, 2), (4, 0)], &Palette::ASCII);
+        assert_eq!(block.render(), "\\   /\n \\ /\n  \\\n");
+        let block: Block = primitive::polyline([(0, 0), (2, 2), (2, 0), (0, 2)], &Palette::ASCII);
+        assert_eq!(block.render(), "\\ |\n X|\n/ \\\n");
+        assert!(primitive::polyline::<String, _>(None, &Palette::ASCII).is_empty());
+    }
+
+    #[test]
+    fn block_rect() {
+        let rect = Rect::<String>::new(4, 3)
+            .stroke(Stroke::ASCII)
+            .fill(Grapheme::from('#'));
+        assert_eq!(rect.into_block().render(), "+--+\n|##|\n+--+\n");
+        let rect = Rect::<String>::new(3, 1).stroke(Stroke::ASCII);
+        assert_eq!(rect.into_block().render(), "---\n");
+        let rect = Rect::<String>::new(1, 2).stroke(Stroke::ASCII);
+        assert_eq!(rect.into_block().render(), "|\n|\n");
+        let rect = Rect::<String>::new(2, 2)
+            .stroke(Stroke::ASCII)
+            .fill(String::from("#"));
+        assert_eq!(rect.into_block().render(), "++\n++\n");
+        let block = Rect::<String>::new(0, 3).into_block();
+        assert_eq!((block.width(), block.height()), (0, 3));
+    }
+
+    #[test]
+    fn block_grid() {
+        let grid = Grid::new(vec![
+            vec![<Block>::with_content("a"), Block::with_content("bb\nb")],
+            vec![Block::with_content("cccc")],
+        ])
+        .column_width(0, 2)
+        .rules(Stroke::ASCII);
+        assert_eq!(
+            grid.into_block().render(),
+            "a  | bb\n   | b\n---+---\ncc |\n"
+        );
+        let grid = Grid::new(vec![vec![
+            <Block>::with_content("a"),
+            Block::with_content("b"),
+        ]])
+        .gutter(Axial {
+            horizontal: 2,
+            vertical: 0,
+        });
+        assert_eq!(grid.into_block().render(), "a  b\n");
+    }
+
+    #[test]
+    fn block_compose_grid() {
+        use crate::align::valued::{HorizontalAlignment, VerticalAlignment};
+
+        let block = layout::compose_grid(
+            vec![
+                vec![<Block>::with_content("a"), Block::with_content("bb\nb")],
+                vec![Block::with_content("ccc")],
+            ],
+            HorizontalAlignment::Right,
+            VerticalAlignment::Bottom,
+        );
+        assert_eq!(block.render(), "   bb\n  ab\nccc\n");
+        assert_eq!(block.width(), 5);
+    }
+
+    #[test]
+    fn block_join_at_baseline() {
+        let fraction = <Block>::with_content("1\n-\n2").with_baseline(1);
+        let block = <Block>::with_content("x = ")
+            .with_baseline(0)
+            .join_left_to_right_at_baseline(fraction)
+            .join_left_to_right_at_baseline(Block::with_content(" + y").with_baseline(0));
+        assert_eq!(block.baseline(), 1);
+        assert_eq!(block.into_block().render(), "    1\nx = - + y\n    2\n");
+    }
+
+    #[test]
+    fn block_pad_centered() {
+        let block = <Block>::with_content("ab")
+            .pad_to_length_centered_at::<LeftRight>(5)
+            .pad_to_length_centered_at::<TopBottom>(4);
+        assert_eq!(block.render(), "\n ab\n\n\n");
+        assert_eq!((block.width(), block.height()), (5, 4));
+    }
+
+    #[test]
+    fn block_bidi_isolation() {
+        let isolate = |text: &str| {
+            content::isolate(text)
+                .replace('\u{2066}', "<")
+                .replace('\u{2068}', "[")
+                .replace('\u{2069}', "]")
+        };
+        assert_eq!(
+            isolate("\u{2502} \u{5D0}\u{5D1} ab \u{2502}cd  e|"),
+            "<\u{2502} [\u{5D0}\u{5D1} ab] \u{2502}[cd]  [e]|]",
+        );
+        assert_eq!(isolate("+--+"), "<+[--]+]");
+        assert_eq!(isolate(""), "<]");
+
+        #[cfg(feature = "bidi")]
+        isolated("block_bidi_isolation", || {
+            use crate::content::Isolation;
+
+            let block = <Block>::with_content("\u{5D0}\u{5D1} ab")
+                .pad_at_left(1)
+                .pad_at_right(1);
+            Isolation::FirstStrong.set();
+            assert_eq!(
+                block.render(),
+                "\u{2066} \u{2068}\u{5D0}\u{5D1} ab\u{2069}\u{2069}\n"
+            );
+            let mut output = vec![];
+            block.render_into(&mut output).unwrap();
+            assert_eq!(
+                String::from_utf8(output).unwrap(),
+                "\u{2066} \u{2068}\u{5D0}\u{5D1} ab\u{2069} \u{2069}"
+            );
+            assert_eq!(block.width(), 7);
+            Isolation::None.set();
+            assert_eq!(block.render(), " \u{5D0}\u{5D1} ab\n");
+        });
+    }
+
+    #[test]
+    fn block_vertical_text() {
+        let block = <Block>::vertical_text("\u{300C}\u{6F22}a\u{300D}\nbc");
+        assert_eq!(block.width(), 3);
+        assert_eq!(block.render(), "\u{300C}b\n\u{6F22}c\na\n\u{300D}\n");
+        let block = <Block>::vertical_text_rotated("\u{300C}\u{6F22}\u{300D}");
+        assert_eq!(block.render(), "\u{FE41}\n\u{6F22}\n\u{FE42}\n");
+    }
+
+    #[test]
+    fn block_gutter() {
+        let block = <Block>::with_content("a\n  b")
+            .push("c")
+            .pad_at_bottom(7)
+            .with_gutter(|y| format!("{} ", y + 1));
+        assert_eq!(block.width(), 6);
+        assert_eq!(block.render().lines().nth(1), Some(" 2   b"));
+        assert_eq!(block.render().lines().nth(9), Some("10"));
+    }
+
+    #[test]
+    fn block_diff_side_by_side() {
+        let block = primitive::diff_side_by_side(
+            <Block>::with_content("abc\nsame"),
+            Block::with_content("abx\nsame\nnew"),
+            4,
+        );
+        assert_eq!(block.render(), "abc  | abx\nsame   same\n     > new\n");
+        // Lines are compared after they are truncated.
+        let block = primitive::diff_side_by_side(
+            <Block>::with_content("abcd\nx"),
+            Block::with_content("abce\nxy").pad_to_width_at_right(5),
+            3,
+        );
+        assert_eq!(block.render(), "abc   abc\nx   | xy\n");
+    }
+
+    #[test]
+    fn block_diff_side_by_side_styled() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Color(char);
+
+        impl Transform for Color {
+            fn write_prefix(&self, target: &mut impl Write) -> io::Result<()> {
+                write!(target, "{}", self.0)
+            }
+        }
+
+        let block = primitive::diff_side_by_side_styled(
+            Block::<Styled<String, Color>>::with_content(Styled::plain("abcd\nsame")),
+            Block::with_content(Styled::plain("axce\nsame\nn")),
+            3,
+            Color('r'),
+        );
+        assert_eq!(block.render(), "arbc | arxc\nsam   sam\nr    > rn\n");
+    }
+
+    #[test]
+    fn block_code() {
+        let source = "fn f() {\n\tg(0123456789);\n}";
+        let block = CodeBlock::new(source)
+            .width(10)
+            .overflow(CodeOverflow::Wrap('+'))
+            .tab_width(2)
+            .into_block_with(|line| Styled::<String>::new((), line.to_owned()));
+        assert_eq!(block.render(), "1 fn f() {\n2   g(012345\n+ 6789);\n3 }\n");
+        let block = CodeBlock::new(source)
+            .width(6)
+            .into_block_with(|line| Styled::<String>::new((), line.to_owned()));
+        assert_eq!(block.render(), "1 fn f(\u{2026}\n2      \u{2026}\n3 }\n");
+    }
+
+    #[cfg(feature = "syntect")]
+    #[test]
+    fn block_syntect_styled() {
+        use syntect::highlighting::{Color, FontStyle, Style};
+
+        use crate::content::Styled;
+
+        let style = |r, font_style| Style {
+            foreground: Color {
+                r,
+                g: 0,
+                b: 0,
+                a: 255,
+            },
+            background: Color {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0,
+            },
+            font_style,
+        };
+        let line = crate::syntect::styled::<String>(&[
+            (style(1, FontStyle::BOLD), "fn"),
+            (style(2, FontStyle::empty()), " f\n"),
+        ]);
+        let block = Block::<Styled<String, AnsiStyle>>::with_content(line);
+        assert_eq!(block.width(), 4);
+        assert_eq!(
+            block.render(),
+            "\x1b[1;38;2;1;0;0mfn\x1b[22;38;2;2;0;0m f\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn block_ansi_style() {
+        let red = AnsiStyle {
+            foreground: Some([255, 0, 0]),
+            ..AnsiStyle::default()
+        };
+        let line = [
+            Styled::new(red, "a".to_owned()),
+            Styled::new(red, "b".to_owned()),
+            Styled::new(
+                AnsiStyle {
+                    is_bold: true,
+                    ..red
+                },
+                "c".to_owned(),
+            ),
+            Styled::plain("d".to_owned()),
+            Styled::new(red, "e".to_owned()),
+        ]
+        .into_iter()
+        .fold(Styled::empty(), Styled::concatenate);
+        let block = Block::<Styled<String, AnsiStyle>>::with_content(line);
+        assert_eq!(
+            block.render(),
+            "\x1b[38;2;255;0;0mab\x1b[1mc\x1b[0md\x1b[38;2;255;0;0me\x1b[0m\n"
+        );
+
+        let block = Block::<Styled<String, AnsiStyle>>::with_content(Styled::new(
+            AnsiStyle {
+                foreground: Some([250, 10, 0]),
+                background: Some([128, 128, 128]),
+                ..AnsiStyle::default()
+            },
+            "x".to_owned(),
+        ));
+        let render = |color_depth| {
+            block
+                .render_with(&RenderContext {
+                    is_styled: true,
+                    color_depth,
+                })
+                .into_owned()
+        };
+        assert_eq!(
+            render(ColorDepth::Ansi256),
+            "\x1b[38;5;196;48;5;244mx\x1b[0m\n"
+        );
+        assert_eq!(render(ColorDepth::Ansi16), "\x1b[91;100mx\x1b[0m\n");
+        assert_eq!(block.render_with(&RenderContext::PLAIN), "x\n");
+    }
+
+    #[test]
+    fn block_banner() {
+        let block: Block = primitive::banner("Hi\n1", &Font::block());
+        assert_eq!((block.width(), block.height()), (7, 10));
+        assert_eq!(
+            block.render(),
+            concat!(
+                "█ █ ███\n",
+                "█ █  █\n",
+                "███  █\n",
+                "█ █  █\n",
+                "█ █ ███\n",
+                " █\n",
+                "██\n",
+                " █\n",
+                " █\n",
+                "███\n",
+            ),
+        );
+        assert!(primitive::banner::<String>("", &Font::block()).is_empty());
+
+        #[cfg(feature = "figlet")]
+        {
+            let font = ["flf2a$ 1 1 4 0 1".to_owned(), "comment".to_owned()]
+                .into_iter()
+                .chain((' '..='~').map(|point| format!("[{}]@", point)))
+                .collect::<Vec<_>>()
+                .join("\n")
+                .replace("[ ]", "[$]");
+            let font = Font::from_figlet(&font).unwrap();
+            let block: Block = primitive::banner("a b", &font);
+            assert_eq!(block.render(), "[a][ ][b]\n");
+            assert!(Font::from_figlet("flf2a$ 1").is_err());
+        }
+    }
+
+    #[test]
+    fn block_arrow() {
+        let arrow = Arrow::<Left>::new(4)
+            .palette(Palette::ASCII)
+            .head(Heads::ASCII)
+            .tail(Some(Heads {
+                left: '+',
+                ..Heads::ASCII
+            }));
+        assert_eq!(arrow.into_block::<String>().render(), "<--+\n");
+        let arrow = Arrow::<Bottom>::new(3)
+            .palette(Palette::ASCII)
+            .head(Heads::ASCII);
+        assert_eq!(arrow.into_block::<String>().render(), "|\n|\nv\n");
+        assert_eq!(
+            Arrow::<Right>::new(1)
+                .head(Heads::ASCII)
+                .into_block::<String>()
+                .render(),
+            ">\n"
+        );
+    }
+
+    #[test]
+    fn block_line() {
+        let palette = AxialPalette::from(Palette::ASCII)
+            .with_horizontal(LinePalette::uniform('-').with_terminals('<', '>'));
+        let line = Line::<LeftRight>::new(4).palette(palette);
+        assert_eq!(line.into_block::<String>().render(), "<-->\n");
+        let line = Line::<TopBottom>::new(3).palette(palette);
+        assert_eq!(line.into_block::<String>().render(), "|\n|\n|\n");
+        let line = Line::<LeftRight>::new(1).palette(palette);
+        assert_eq!(line.into_block::<String>().render(), "-\n");
+        assert!(Line::<TopBottom>::new(0).into_block::<String>().is_zero());
+    }
+
+    #[test]
+    fn block_line_pattern() {
+        let line = Line::<LeftRight>::new(7)
+            .line_palette(LinePalette::uniform('-').with_terminals('[', ']'))
+            .pattern("- ");
+        assert_eq!(line.clone().into_block::<String>().render(), "[ - - ]\n");
+        assert_eq!(line.phase(1).into_block::<String>().render(), "[- - -]\n");
+
+        // Segments joined end to end share the pattern when phased by their offsets.
+        let left = Line::<LeftRight>::new(3)
+            .line_palette(LinePalette::uniform('-'))
+            .pattern("ab");
+        let right = left.clone().phase(3);
+        let block: Block<String> = left
+            .into_block()
+            .join_left_to_right_at_top(right.into_block());
+        assert_eq!(block.render(), "-b--a-\n");
+        assert_eq!(
+            Line::<TopBottom>::new(1)
+                .pattern("x")
+                .into_block::<String>()
+                .render(),
+            "x\n",
+        );
+    }
+
+    #[test]
+    fn block_brush() {
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        struct Ink;
+
+        impl Transform for Ink {
+            fn write_prefix(&self, target: &mut impl Write) -> io::Result<()> {
+                target.write_all(b"[")
+            }
+
+            fn write_suffix(&self, target: &mut impl Write) -> io::Result<()> {
+                target.write_all(b"]")
+            }
+        }
+
+        let block: Block<Styled<String, Ink>> = Rect::new(3, 3)
+            .stroke(Stroke::ASCII)
+            .brush(Ink)
+            .fill(Styled::plain("x".to_owned()))
+            .into_block();
+        assert_eq!(block.render(), "[+-+]\n[|]x[|]\n[+-+]\n");
+
+        let block: Block<Styled<String, Ink>> = Line::<TopBottom>::new(2)
+            .palette(Palette::ASCII)
+            .brush(Ink)
+            .into_block();
+        assert_eq!(block.render(), "[|]\n[|]\n");
+        let block: Block<Styled<String, Ink>> = Arrow::<Right>::new(3)
+            .palette(Palette::ASCII)
+            .head(Heads::ASCII)
+            .brush(Ink)
+            .into_block();
+        assert_eq!(block.render(), "[-->]\n");
+        assert_eq!(Rect::<String>::new(4, 2).into_block().width(), 4);
+    }
+
+    #[test]
+    fn block_theme() {
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        struct Tag(&'static str);
+
+        impl Transform for Tag {
+            fn write_prefix(&self, target: &mut impl Write) -> io::Result<()> {
+                write!(target, "<{}>", self.0)
+            }
+
+            fn write_suffix(&self, target: &mut impl Write) -> io::Result<()> {
+                write!(target, "</{}>", self.0)
+            }
+        }
+
+        let theme = Theme {
+            stroke: Stroke::ASCII,
+            border: Tag("b"),
+            fill: Some(".".to_owned()),
+            padding: Tag("p"),
+            title: Tag("t"),
+            emphasis: Tag("e"),
+        };
+        let block: Block<Styled<String, Tag>> = Rect::new(3, 3).theme(&theme).into_block();
+        assert_eq!(
+            block.render(),
+            "<b>+-+</b>\n<b>|</b>.<b>|</b>\n<b>+-+</b>\n",
+        );
+
+        let table = Table::new(vec![vec![Block::with_content(Styled::plain(
+            "x".to_owned(),
+        ))]])
+        .header(vec![Block::with_content(Styled::plain("h".to_owned()))])
+        .theme(&theme);
+        assert_eq!(
+            table.into_block().render(),
+            concat!(
+                "<b>+---+</b>\n",
+                "<b>|</b><p> </p><t>h</t><p> </p><b>|</b>\n",
+                "<b>+---+</b>\n",
+                "<b>|</b><p> </p>x<p> </p><b>|</b>\n",
+                "<b>+---+</b>\n",
+            ),
+        );
+
+        let list = List::new(vec![Block::with_content(Styled::plain("a".to_owned()))])
+            .marker(Marker::Bullet('*'))
+            .theme(&theme);
+        assert_eq!(list.into_block().render(), "<e>*</e> a\n");
+    }
+
+    #[test]
+    fn block_translate() {
+        let vector = AxisVector::toward::<Right>(2) + AxisVector::toward::<Bottom>(1);
+        assert_eq!(vector, AxisVector::new(2, 1));
+        assert_eq!(vector.rotate_clockwise(), AxisVector::new(-1, 2));
+        assert_eq!(vector.rotate_counterclockwise(), AxisVector::new(1, -2));
+        assert_eq!(vector.rotate_clockwise().rotate_clockwise(), -vector);
+        assert_eq!(vector.get::<TopBottom>(), 1);
+
+        let block = <Block>::with_content("ab\ncd");
+        assert_eq!(block.clone().translate(vector).render(), "\n  ab\n  cd\n");
+        assert_eq!(block.translate(AxisVector::new(-1, -1)).render(), "d\n");
+
+        let header = <Block>::with_content("header");
+        let scene = layout::Scene::new()
+            .place_along(AxisVector::ZERO, header)
+            .place_along(
+                AxisVector::toward::<Bottom>(2),
+                <Block>::with_content("body"),
+            )
+            .place_along(AxisVector::new(-1, -1), <Block>::with_content("*"));
+        assert_eq!(scene.into_block().render(), " header\n\n\n*body\n");
+    }
+
+    #[test]
+    fn block_corners() {
+        let corners = Stroke::ASCII.corners();
+        assert_eq!(*corners.cornered_at::<typed::BottomRight>(), '+');
+        let corners = Corners {
+            top_left: 1,
+            top_right: 2,
+            bottom_left: 3,
+            bottom_right: 4,
+        };
+        let rotated = corners.rotate_clockwise();
+        assert_eq!(
+            (
+                rotated.top_left,
+                rotated.top_right,
+                rotated.bottom_left,
+                rotated.bottom_right
+            ),
+            (3, 1, 4, 2),
+        );
+        let rotated = rotated.rotate_counterclockwise();
+        assert_eq!(*rotated.cornered_at::<typed::TopRight>(), 2);
+        assert_eq!(
+            valued::Corner::TopLeft
+                .rotate_clockwise()
+                .rotate_clockwise(),
+            valued::Corner::TopLeft.opposite(),
+        );
+        assert_eq!(
+            <typed::TopRight as typed::Corner>::VALUE,
+            valued::Corner::new(
+                valued::VerticalAlignment::Top,
+                valued::HorizontalAlignment::Right
+            ),
+        );
+
+        let front = <Block>::with_content("x");
+        let back = <Block>::with_content("...\n...");
+        assert_eq!(
+            front
+                .clone()
+                .overlay_at_corner::<typed::BottomRight>(back.clone())
+                .render(),
+            "...\n..x\n",
+        );
+        assert_eq!(
+            front.overlay_at_corner::<typed::TopRight>(back).render(),
+            "..x\n...\n",
+        );
+
+        let block = <Block>::with_content("ab");
+        assert_eq!(
+            block
+                .clone()
+                .with_shadow_toward::<typed::TopLeft, _>(
+                    Axial {
+                        horizontal: 1,
+                        vertical: 1
+                    },
+                    Grapheme::from('#')
+                )
+                .render(),
+            "##\n ab\n",
+        );
+        assert_eq!(
+            block
+                .with_shadow(
+                    Axial {
+                        horizontal: 1,
+                        vertical: 1
+                    },
+                    Grapheme::from('#')
+                )
+                .render(),
+            "ab\n ##\n",
+        );
+    }
+
+    #[test]
+    fn block_nonant() {
+        let mut titles = Nonant::uniform("");
+        *titles.anchored_at_mut::<Top, typed::Center>() = "title";
+        *titles.anchored_at_mut::<Bottom, Right>() = "page";
+        assert_eq!(*titles.top(), "title");
+        assert_eq!(*titles.cornered_at::<typed::BottomRight>(), "page");
+        assert_eq!(
+            *titles.aligned(
+                valued::VerticalAnchor::Center,
+                valued::HorizontalAnchor::Center
+            ),
+            "",
+        );
+
+        let rotated = titles.rotate_clockwise();
+        assert_eq!(*rotated.right(), "title");
+        assert_eq!(*rotated.bottom_left(), "page");
+        let inverted = titles.invert();
+        assert_eq!(*inverted.anchored_at::<Bottom, typed::Center>(), "title");
+        assert_eq!(*inverted.anchored_at::<Top, Left>(), "page");
+        assert_eq!(
+            valued::HorizontalAnchor::from(HorizontalAlignment::Left).opposite(),
+            valued::HorizontalAnchor::Right,
+        );
+    }
+
+    #[test]
+    fn block_envelope_conversions() {
+        let margins = Perimeter::from(Axial {
+            horizontal: 2usize,
+            vertical: 1,
+        });
+        assert_eq!(
+            (margins.left, margins.right, margins.top, margins.bottom),
+            (2, 2, 1, 1),
+        );
+        let (horizontal, vertical) = margins.split();
+        assert_eq!((horizontal.left, vertical.bottom), (2, 1));
+        let margins = Square::merge(horizontal, Vertical::uniform(0));
+        assert_eq!((margins.right, margins.top), (2, 0));
+
+        let quadrant = Quadrant::from(Corners {
+            top_left: 'a',
+            top_right: 'b',
+            bottom_left: 'c',
+            bottom_right: 'd',
+        });
+        let columns = Horizontal::<Vertical<char>>::from(quadrant);
+        assert_eq!((columns.left.bottom, columns.right.top), ('c', 'b'));
+
+        let (corners, edges, center) = Nonant::merge(
+            Corners::from(Quadrant::uniform('+')),
+            Square::uniform('-'),
+            ' ',
+        )
+        .split();
+        assert_eq!((corners.top_left, edges.bottom, center), ('+', '-', ' '));
+    }
+
+    #[test]
+    fn block_envelope_combinators() {
+        let margins = Perimeter::uniform(1usize).map(|margin| margin * 2);
+        assert_eq!(margins.into_iter().sum::<usize>(), 8);
+
+        let palette = Corners::from(Quadrant::uniform('+')).map(Grapheme::from);
+        assert!(palette.as_ref().into_iter().all(|glyph| glyph.get() == "+"));
+
+        let mut insets = Axial {
+            horizontal: 1,
+            vertical: 2,
+        };
+        *insets.as_mut().vertical += 1;
+        let sums = insets.zip(Axial {
+            horizontal: 10,
+            vertical: 20,
+        });
+        assert_eq!(
+            sums.map(|(a, b)| a + b).into_iter().collect::<Vec<_>>(),
+            vec![11, 23],
+        );
+        let (left, right) = Horizontal::uniform('a').zip(Horizontal::uniform(0)).right;
+        assert_eq!((left, right), ('a', 0));
+        assert_eq!(Vertical::uniform('b').into_iter().count(), 2);
+        assert_eq!(Nonant::uniform(()).into_iter().count(), 9,);
+    }
+
+    #[test]
+    fn block_dynamically_aligned() {
+        use crate::align::valued::{Alignment, AxialAlignment, Axis, Corner};
+        use crate::block::DynamicallyAligned;
+
+        let block = <Block>::filled_with_length(Axis::TopBottom, 2, 1, Grapheme::from('|'));
+        assert_eq!(block.render(), "|\n|\n");
+        let block = <Block>::with_content("a").join_with_separator(
+            AxialAlignment::LEFT_RIGHT_AT_TOP,
+            block,
+            <Block>::with_content("b"),
+        );
+        assert_eq!(block.render(), "a|b\n |\n");
+
+        let block = block
+            .pad_to_length_centered(Axis::LeftRight, 5)
+            .truncate(Alignment::BOTTOM, 1, None)
+            .trim_edge(Alignment::LEFT);
+        assert_eq!(block.render(), "a|b\n");
+        let block = <Block>::with_content("x")
+            .overlay_at(Corner::BottomRight, block.pad(Alignment::BOTTOM, 1));
+        assert_eq!(block.render(), "a|b\n   x\n");
+    }
+
+    #[test]
+    fn block_typed_dispatch() {
+        use crate::align::typed::{AlignmentFunction, AxisFunction, CornerFunction};
+
+        struct Rule(usize);
+
+        impl AxisFunction for Rule {
+            type Output = Block;
+
+            fn call<A>(self) -> Self::Output
+            where
+                A: typed::Axis,
+            {
+                Line::<A>::new(self.0)
+                    .palette(Palette::ASCII)
+                    .into_block()
+                    .join_at_origin::<A>(<Block>::with_content("x"))
+            }
+        }
+
+        struct Margin(Block, usize);
+
+        impl AlignmentFunction for Margin {
+            type Output = Block;
+
+            fn call<L>(self) -> Self::Output
+            where
+                L: typed::Alignment,
+            {
+                self.0
+                    .pad_at::<L>(self.1)
+                    .trim_at::<L>()
+                    .pad_at::<L>(self.1)
+            }
+        }
+
+        struct Anchor(Block, Block);
+
+        impl CornerFunction for Anchor {
+            type Output = Block;
+
+            fn call<K>(self) -> Self::Output
+            where
+                K: typed::Corner,
+            {
+                self.0.overlay_at_corner::<K>(self.1)
+            }
+        }
+
+        assert_eq!(
+            valued::Axis::LeftRight.with_typed(Rule(2)).render(),
+            "--x\n"
+        );
+        assert_eq!(
+            valued::Axis::TopBottom.with_typed(Rule(2)).render(),
+            "|\n|\nx\n"
+        );
+        let block = <Block>::with_content("a");
+        assert_eq!(
+            valued::Alignment::LEFT
+                .with_typed(Margin(block.clone(), 2))
+                .render(),
+            "  a\n",
+        );
+        assert_eq!(
+            valued::Alignment::BOTTOM
+                .with_typed(Margin(block.clone(), 1))
+                .height(),
+            2,
+        );
+        assert_eq!(
+            valued::Corner::BottomLeft
+                .with_typed(Anchor(block, <Block>::with_content("..\n..")))
+                .render(),
+            "..\na.\n",
+        );
+    }
+
+    #[test]
+    fn block_content_conversions() {
+        use std::borrow::Cow;
+
+        let block = <Block>::with_content("ab\nc");
+        let owned: Block<Cow<'static, str>> = block.clone().into();
+        assert_eq!(owned.render(), "ab\nc\n");
+
+        let styled: Block<Styled<String, ()>> = block.clone().into();
+        assert_eq!(styled.render(), "ab\nc\n");
+        assert_eq!(styled.width(), 2);
+
+        assert_eq!(block.into_content_vec(), vec!["ab", "c "]);
+        assert_eq!(
+            <Block>::with_dimensions(2, 2).into_content_vec(),
+            vec!["  ", "  "],
+        );
+        assert!(<Block>::zero().into_content_vec().is_empty());
+    }
+
+    #[test]
+    fn block_lines_iteration() {
+        let mut block = <Block>::from_lines(vec!["a".into(), "bc\nd".into()]);
+        assert_eq!(block.render(), "a\nbc\nd\n");
+
+        block.extend(vec![String::from("efg"), String::from("h")]);
+        assert_eq!(block.width(), 3);
+        assert_eq!(block.render(), "a\nbc\nd\nefg\nh\n");
+
+        let lines: Vec<_> = block.into_iter().collect();
+        assert_eq!(lines, vec!["a  ", "bc ", "d  ", "efg", "h  "]);
+
+        let mut block = <Block>::with_dimensions(2, 1);
+        block.extend(Some(String::from("a")));
+        assert_eq!(block.into_iter().collect::<Vec<_>>(), vec!["  ", "a "]);
+    }
+
+    #[test]
+    fn block_render_lines() {
+        let block = <Block>::with_content("a \n\nbc");
+        let lines: Vec<_> = block.render_lines().collect();
+        assert_eq!(lines, vec!["a", "", "bc"]);
+        assert!(lines.iter().all(|line| matches!(line, Cow::Borrowed(_))));
+
+        let mut output = Vec::new();
+        for line in block.render_lines() {
+            writeln!(output, "> {}", line).unwrap();
+        }
+        assert_eq!(String::from_utf8(output).unwrap(), "> a\n> \n> bc\n");
+
+        assert_eq!(<Block>::with_dimensions(2, 2).render_lines().count(), 0);
+    }
+
+    #[cfg(feature = "terminal")]
+    #[test]
+    fn block_fit_to_width() {
+        use crate::content::Grapheme;
+        use crate::terminal::FitPolicy;
+
+        let block = <Block>::with_content("abc def\ngh");
+        assert_eq!(
+            block
+                .clone()
+                .fit_to_width_by(5, FitPolicy::default())
+                .render(),
+            "abc \u{2026}\ngh\n",
+        );
+        assert_eq!(
+            block
+                .clone()
+                .fit_to_width_by(4, FitPolicy::Reflow(WrapPolicy::WordBoundary))
+                .render(),
+            "abc\ndef\ngh\n",
+        );
+        let block = block.fit_to_width_by(9, FitPolicy::Truncate(Some(Grapheme::from('~'))));
+        assert_eq!(block.width(), 9);
+        assert_eq!(block.render(), "abc def\ngh\n");
+    }
+
+    #[test]
+    fn block_render_context() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Tag;
+
+        impl Transform for Tag {
+            fn write_prefix(&self, target: &mut impl Write) -> io::Result<()> {
+                target.write_all(b"[")
+            }
+
+            fn write_suffix(&self, target: &mut impl Write) -> io::Result<()> {
+                target.write_all(b"]")
+            }
+        }
+
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Depth;
+
+        impl Transform for Depth {
+            fn write_prefix_with(
+                &self,
+                target: &mut impl Write,
+                context: &RenderContext,
+            ) -> io::Result<()> {
+                match (context.is_styled, context.color_depth) {
+                    (false, _) => Ok(()),
+                    (true, ColorDepth::TrueColor) => target.write_all(b"<"),
+                    (true, _) => target.write_all(b"("),
+                }
+            }
+        }
+
+        let block: Block<Styled<String, Tag>> = Block::with_content(Styled::concatenate(
+            Styled::new(Tag, "ab".to_owned()),
+            Styled::plain("c".to_owned()),
+        ));
+        assert_eq!(block.render(), "[ab]c\n");
+        assert_eq!(block.render_with(&RenderContext::PLAIN), "abc\n");
+        let mut output = Vec::new();
+        block
+            .render_into_with(&mut output, &RenderContext::PLAIN)
+            .unwrap();
+        assert_eq!(output, b"abc");
+
+        let block: Block<Styled<String, Depth>> =
+            Block::with_content(Styled::new(Depth, "a".to_owned()));
+        assert_eq!(block.render(), "<a\n");
+        let context = RenderContext {
+            color_depth: ColorDepth::Ansi256,
+            ..RenderContext::default()
+        };
+        assert_eq!(
+            block.render_lines_with(&context).collect::<Vec<_>>(),
+            ["(a"]
+        );
+        assert_eq!(block.render_with(&RenderContext::PLAIN), "a\n");
+    }
+
+    #[test]
+    fn block_animation_frames() {
+        let frames = <Frames>::ellipsis();
+        assert_eq!(frames.len(), 4);
+        assert!(frames.iter().all(|frame| frame.width() == 3));
+        assert_eq!(frames.frame_at(2).unwrap().render(), "..\n");
+        assert_eq!(frames.frame_at(5).unwrap().render(), ".\n");
+        assert_eq!(
+            frames
+                .frame_at(Duration::from_millis(950))
+                .unwrap()
+                .render(),
+            "...\n"
+        );
+
+        let frames = <Frames>::new(
+            vec![Block::with_content("a"), Block::with_content("bc\nd")],
+            Duration::ZERO,
+        );
+        assert_eq!(
+            frames.frame_at(Duration::from_secs(1)).unwrap().render(),
+            "a\n\n"
+        );
+        assert!(<Frames>::new(vec![], Duration::ZERO).frame_at(0).is_none());
+    }
+
+    #[test]
+    fn block_gauge() {
+        let gauge = Gauge::new(0.5, 4).palette(Palette::ASCII);
+        assert_eq!(gauge.level(), Level::Ok);
+        assert_eq!(gauge.into_block::<String>().render(), "--    50%\n");
+        let gauge = Gauge::new(0.8125, 4);
+        assert_eq!(gauge.level(), Level::Warn);
+        assert_eq!(
+            gauge.labeled(false).into_block::<String>().render(),
+            "\u{2500}\u{2500}\u{2500}\n"
+        );
+        assert_eq!(Gauge::new(2.0, 1).level(), Level::Crit);
+        assert_eq!(
+            Gauge::new(0.5, 1)
+                .thresholds(0.25, 0.5)
+                .map(|gauge| gauge.level()),
+            Ok(Level::Crit),
+        );
+        assert_eq!(
+            Gauge::new(0.5, 1).thresholds(0.5, 1.5).err(),
+            Some(ThresholdError::OutOfRange),
+        );
+        assert_eq!(
+            Gauge::new(0.5, 1).thresholds(f64::NAN, 0.5).err(),
+            Some(ThresholdError::OutOfRange),
+        );
+        assert_eq!(
+            Gauge::new(0.5, 1).thresholds(0.9, 0.5).err(),
+            Some(ThresholdError::Unordered),
+        );
+
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        struct Tag(&'static str);
+
+        impl Transform for Tag {
+            fn apply<'t>(&self, text: &'t str) -> Cow<'t, str> {
+                if self.0.is_empty() {
+                    text.into()
+                }
+                else {
+                    format!("{}[{}]", self.0, text).into()
+                }
+            }
+        }
+
+        let brushes = Levels {
+            ok: Tag("ok"),
+            warn: Tag("warn"),
+            crit: Tag("crit"),
+        };
+        let block: Block<Styled<String, Tag>> = Gauge::new(0.5, 2)
+            .palette(Palette::ASCII)
+            .brush(brushes)
+            .into_block();
+        assert_eq!(block.render(), "ok[-] ok[  50%]\n");
+    }
+
+    #[test]
+    fn block_table() {
+        let table = <Table>::new(vec![
+            vec![Block::with_content("a"), Block::with_content("10")],
+            vec![Block::with_content("bc")],
+        ])
+        .header(vec![Block::with_content("x"), Block::with_content("y")])
+        .align(1, HorizontalAlignment::Right)
+        .stroke(Stroke::ASCII);
+        assert_eq!(
+            table.into_block().render(),
+            concat!(
+                "+----+----+\n",
+                "| x  |  y |\n",
+                "+----+----+\n",
+                "| a  | 10 |\n",
+                "| bc |    |\n",
+                "+----+----+\n",
+            ),
+        );
+
+        #[cfg(feature = "delimited")]
+        {
+            let text = "name,n\r\n\"a, \"\"b\"\"\",1.5\n\nc,20\n";
+            let table = <Table>::from_delimited(text.as_bytes(), ',')
+                .unwrap()
+                .stroke(Stroke::ASCII);
+            assert_eq!(
+                table.into_block().render(),
+                concat!(
+                    "+--------+-----+\n",
+                    "| name   |   n |\n",
+                    "+--------+-----+\n",
+                    "| a, \"b\" | 1.5 |\n",
+                    "| c      |  20 |\n",
+                    "+--------+-----+\n",
+                ),
+            );
+        }
+    }
+
+    #[test]
+    fn block_table_spans() {
+        let table = <Table>::new(vec![
+            vec![
+                Cell::from(Block::with_content("a\nb\nc")).span_rows(2),
+                Block::with_content("1").into(),
+                Block::with_content("2").into(),
+            ],
+            vec![
+                Cell::from(Block::with_content("3")),
+                Block::with_content("4").into(),
+            ],
+            vec![Cell::from(Block::with_content("total")).span_columns(3)],
+        ])
+        .header(vec![
+            Cell::from(Block::with_content("k")),
+            Cell::from(Block::with_content("values")).span_columns(2),
+        ])
+        .stroke(Stroke::ASCII)
+        .padding(0);
+        assert_eq!(
+            table.clone().into_block().render(),
+            concat!(
+                "+-+------+\n",
+                "|k|values|\n",
+                "+-+---+--+\n",
+                "|a|1  |2 |\n",
+                "|b|   |  |\n",
+                "|c|3  |4 |\n",
+                "|total   |\n",
+                "+--------+\n",
+            ),
+        );
+        assert_eq!(
+            table.to_markdown(),
+            concat!(
+                "| k | values |  |\n",
+                "| --- | --- | --- |\n",
+                "| a<br>b<br>c | 1 | 2 |\n",
+                "|  | 3 | 4 |\n",
+                "| total |  |  |\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn block_table_wrap() {
+        let table = <Table>::new(vec![vec![
+            Cell::from(Block::with_content("the quick brown fox")),
+            Cell::from(Block::with_content("a")).align_vertical(RowAlignment::Bottom),
+            Cell::from(Block::with_content("b\nc\nd")),
+            Cell::from(Block::with_content("m")),
+        ]])
+        .align_rows(RowAlignment::Middle)
+        .max_width(18)
+        .wrap(WrapPolicy::WordBoundary)
+        .stroke(Stroke::ASCII)
+        .padding(0);
+        assert_eq!(
+            table.into_block().render(),
+            concat!(
+                "+----------+-+-+-+\n",
+                "|the quick | |b| |\n",
+                "|brown fox | |c|m|\n",
+                "|          |a|d| |\n",
+                "+----------+-+-+-+\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn block_table_wide_graphemes() {
+        let table = <Table>::new(vec![vec![Block::with_content(
+            "\u{65E5}\u{672C}\u{8A9E}\u{30C6}\u{30AD}\u{30B9}\u{30C8}",
+        )]])
+        .column_width(0, 5)
+        .stroke(Stroke::ASCII)
+        .padding(0);
+        assert_eq!(
+            table.into_block().render(),
+            "+-----+\n|\u{65E5}\u{672C} |\n+-----+\n",
+        );
+    }
+
+    #[test]
+    fn block_table_pages() {
+        let table = <Table>::new((0..3).map(|n| vec![Block::with_content(n.to_string())]))
+            .header(vec![Block::with_content("n")])
+            .stroke(Stroke::ASCII)
+            .padding(0);
+        let pages: Vec<_> = table
+            .into_pages(6)
+            .map(|page| page.render().into_owned())
+            .collect();
+        assert_eq!(
+            pages,
+            [
+                "+-+\n|n|\n+-+\n|0|\n|1|\n+-+\n",
+                "+-+\n|n|\n+-+\n|2|\n+-+\n"
+            ]
+        );
+    }
+
+    #[test]
+    fn block_table_from_rows() {
+        struct Point {
+            name: &'static str,
+            x: i32,
+        }
+
+        crate::tabulate!(Point {
+            name: "Name" => (Some(3), HorizontalAlignment::Left),
+            x: "X" => (None, HorizontalAlignment::Right),
+        });
+
+        assert_eq!(Point::columns()[0], Column::new("Name").width(3));
+        let mut records = Records::new(vec![
+            Point { name: "b", x: 10 },
+            Point { name: "a", x: 9 },
+            Point { name: "c", x: -1 },
+        ]);
+        records.sort_by_column(1);
+        records.filter_rows(|point| point.name != "c");
+        records.select_columns(vec![1, 0]);
+        assert_eq!(
+            records
+                .to_table::<String>()
+                .padding(0)
+                .stroke(Stroke::ASCII)
+                .into_block()
+                .render(),
+            "+--+---+\n| X|Nam|\n+--+---+\n| 9|a  |\n|10|b  |\n+--+---+\n",
+        );
+        records.reverse();
+        assert_eq!(
+            records.rows().map(|point| point.name).collect::<Vec<_>>(),
+            ["b", "a"]
+        );
+
+        let table = <Table>::from_rows(&[
+            Point {
+                name: "origin",
+                x: 0,
+            },
+            Point { name: "a", x: -10 },
+        ])
+        .stroke(Stroke::ASCII)
+        .padding(0);
+        assert_eq!(
+            table.into_block().render(),
+            concat!(
+                "+---+---+\n",
+                "|Nam|  X|\n",
+                "+---+---+\n",
+                "|ori|  0|\n",
+                "|a  |-10|\n",
+                "+---+---+\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn block_records_sort_by_column() {
+        struct Entry {
+            value: &'static str,
+        }
+
+        crate::tabulate!(Entry { value: "Value" });
+
+        let mut records = Records::new(
+            ["b", "NaN", "10", "a", "-inf", "9", " 9", "1e1"]
+                .into_iter()
+                .map(|value| Entry { value }),
+        );
+        records.sort_by_column(0);
+        assert_eq!(
+            records.rows().map(|entry| entry.value).collect::<Vec<_>>(),
+            ["-inf", " 9", "9", "10", "1e1", "NaN", "a", "b"],
+        );
+    }
+
+    #[test]
+    fn block_markdown() {
+        let table = <Table>::new(vec![
+            vec![Block::with_content("a|b"), Block::with_content("1")],
+            vec![Block::with_content("c\nd")],
+        ])
+        .header(vec![Block::with_content("x"), Block::with_content("*y*")])
+        .align(1, HorizontalAlignment::Right);
+        assert_eq!(
+            table.to_markdown(),
+            concat!(
+                "| x | \\*y\\* |\n",
+                "| --- | ---: |\n",
+                "| a\\|b | 1 |\n",
+                "| c<br>d |  |\n",
+            ),
+        );
+
+        let list = <List>::new((8..11).map(|n| Block::with_content(n.to_string())))
+            .marker(Marker::Numbered(8));
+        assert_eq!(list.to_markdown(), "8. 8\n9. 9\n10. 10\n");
+        assert_eq!(list.into_block().render(), " 8. 8\n 9. 9\n10. 10\n");
+        let list = <List>::new(vec![Block::with_content("a\nb")]).marker(Marker::Bullet('*'));
+        assert_eq!(list.to_markdown(), "- a\n  b\n");
+        assert_eq!(list.into_block().render(), "* a\n  b\n");
+        let list = <List>::new(vec![Block::with_content("a\nb"), Block::with_content("c")]);
+        assert_eq!(list.to_markdown(), "- a\n  b\n- c\n");
+        assert_eq!(list.into_block().render(), "\u{2022} a\n  b\n\u{2022} c\n");
+
+        assert_eq!(markdown::escape("# a"), "\\# a");
+        assert_eq!(markdown::escape("  - a"), "  \\- a");
+        assert_eq!(markdown::escape("+"), "\\+");
+        assert_eq!(markdown::escape("10. a"), "10\\. a");
+        assert_eq!(markdown::escape("1) a"), "1\\) a");
+        assert_eq!(markdown::escape("a - 1. b"), "a - 1. b");
+        assert_eq!(markdown::escape("---"), "\\---");
+        assert_eq!(markdown::escape("-1"), "-1");
+        assert_eq!(markdown::escape("1.5"), "1.5");
+    }
 
-        let block = <Block>::with_height(1);
-        assert!(block.is_empty());
+    #[test]
+    fn block_elastic_tabstops() {
+        let block: Block = layout::elastic_tabstops("a\tbb\tc\naaa\tb\n\nx\ty", '\t', 1);
+        assert_eq!(block.render(), "a   bb c\naaa b\n\nx y\n");
+    }
 
-        let block = <Block>::with_width(1).join_top_to_bottom_at_left(Block::with_height(1));
+    #[test]
+    fn block_split() {
+        let (left, right) = <Block>::with_content("abc\nde").split_at_width(2);
+        assert_eq!(
+            (left.render(), right.render()),
+            ("ab\nde\n".into(), "c\n\n".into())
+        );
+        let (top, bottom) = <Block>::with_content("a\nb\nc").split_at_height(1);
+        assert_eq!(
+            (top.render(), bottom.render()),
+            ("a\n".into(), "b\nc\n".into())
+        );
+
+        // A wide grapheme that is split is replaced by spaces on both sides.
+        let (left, right) = <Block>::with_content("a\u{65E5}b\nabcd").split_at_width(2);
+        assert_eq!((left.width(), right.width()), (2, 2));
+        assert_eq!(
+            (left.render(), right.render()),
+            ("a\nab\n".into(), " b\ncd\n".into()),
+        );
+    }
+
+    #[test]
+    fn block_flow_columns() {
+        let block = <Block>::with_content("a\nb\nc\nd\ne");
+        let block = layout::columns(block, Columns::Count(2), 1);
+        assert_eq!(block.render(), "a d\nb e\nc\n");
+    }
+
+    #[test]
+    fn block_sanitized_content() {
+        let policy = SanitizePolicy {
+            tab_width: 4,
+            replacement: Some(Grapheme::from('?')),
+        };
+        let block = <Block>::with_sanitized_content("a\tb\nab\x07\tc", &policy);
+        assert_eq!(block.render(), "a   b\nab? c\n");
+    }
+
+    #[test]
+    fn block_shadow_and_outline() {
+        let block = <Block>::with_content("ab\ncd").with_shadow(
+            Axial {
+                horizontal: 1,
+                vertical: 1,
+            },
+            Grapheme::from('#'),
+        );
+        assert_eq!(block.render(), "ab\ncd#\n ##\n");
+
+        let block = <Block>::with_content("a").outline(Grapheme::from('*'));
+        assert_eq!(block.render(), "***\n*a*\n***\n");
+    }
+
+    #[test]
+    fn block_fill_from_fn() {
+        let block = <Block>::with_dimensions(4, 2).fill(block::from_fn(|x, y| {
+            if (x + y) % 2 == 0 {
+                '#'
+            }
+            else {
+                '.'
+            }
+        }));
+        assert_eq!(block.render(), "#.#.\n.#.#\n");
+
+        let block =
+            Block::<Styled>::with_dimensions(3, 1).fill(block::from_fn(|x, _| StyledCell {
+                style: (),
+                grapheme: Grapheme::from(char::from(b'a' + x as u8)),
+            }));
+        assert_eq!(block.render(), "abc\n");
+    }
+
+    #[test]
+    fn block_fill_tiled() {
+        let tile = <Block>::with_content("ab\ncd");
+        let block = Block::with_dimensions(5, 3).fill(&tile);
+        assert_eq!(block.render(), "ababa\ncdcdc\nababa\n");
+        assert_eq!(tile.tile_to(3, 1).render(), "aba\n");
+
+        // A wide grapheme that is split at the edge of the block is replaced by a space.
+        let block = <Block>::with_content("\u{65E5}a").tile_to(5, 2);
+        assert_eq!(block.render(), "\u{65E5}a\u{65E5}\n\u{65E5}a\u{65E5}\n");
+        assert_eq!(block.width(), 5);
+        let block = <Block>::with_content("\u{65E5}a").tile_to(4, 1);
+        assert_eq!(block.render(), "\u{65E5}a\n");
+        assert_eq!(block.width(), 4);
+    }
+
+    #[test]
+    fn block_truncate_with_default_content() {
+        let text = <Text>::from("a\u{4E00}bc");
+        assert_eq!(
+            text.to_graphemes(),
+            ["a", "\u{4E00}", "b", "c"].map(|glyph| Grapheme::try_from(glyph).unwrap()),
+        );
+        assert_eq!(text.clone().skip(1), <Text>::from("\u{4E00}bc"));
+        assert_eq!(text.clone().skip(4), <Text>::from(""));
+        let block = Block::<Text>::with_content("abcdef\nab")
+            .truncate_with_ellipsis_at::<LeftRight, Left>(4, Grapheme::from('~'));
+        assert_eq!(block.render(), "~def\n~\n");
+    }
+
+    #[test]
+    fn block_ascii_content() {
+        let text = |text| Cow::<str>::Borrowed(text);
+        // Printable ASCII is measured and sliced by bytes.
+        assert_eq!(text("abcd").width(), 4);
+        assert_eq!(text("abcd").truncate(2), "ab");
+        assert_eq!(text("abcd").skip(2), "cd");
+        assert_eq!(text("abcd").skip(8), "");
+        assert_eq!(text("abcd").to_graphemes().len(), 4);
+        // Other text is segmented into graphemes.
+        assert_eq!(text("ae\u{301}b").width(), 3);
+        assert_eq!(text("ae\u{301}b").truncate(2), "ae\u{301}");
+        assert_eq!(text("ae\u{301}b").skip(1), "e\u{301}b");
+        assert_eq!(text("a\u{4E00}b").width(), 4);
+        assert_eq!(text("a\u{4E00}b").to_graphemes().len(), 3);
+
+        let block = <Block>::with_content("abcd\nae\u{301}bc")
+            .truncate_with_ellipsis_at::<LeftRight, Right>(3, Grapheme::from('~'));
+        assert_eq!(block.render(), "ab~\nae\u{301}~\n");
+        assert_eq!(block.width(), 3);
+    }
+
+    #[test]
+    fn block_truncate_with_ellipsis() {
+        let block = <Block>::with_content("abcdef\nab\nabcd")
+            .truncate_with_ellipsis_at::<LeftRight, Right>(4, Grapheme::from('~'));
+        assert_eq!(block.render(), "abc~\nab\nabcd\n");
+
+        let block = <Block>::with_content("a\nb\nc")
+            .truncate_with_ellipsis_at::<TopBottom, Bottom>(2, Grapheme::from('~'));
+        assert_eq!(block.render(), "a\n~\n");
+
+        let block = <Block>::with_content("a\nb\nc").truncate_at::<TopBottom, Top>(0);
         assert!(block.is_empty());
+        assert_eq!(block.width(), 1);
+    }
+
+    #[test]
+    fn block_truncate_wide_graphemes() {
+        let block = <Block>::with_content("\u{65E5}\u{672C}\u{8A9E}\nabcdef");
+        let truncated = block.clone().truncate_at::<LeftRight, Right>(3);
+        assert_eq!(truncated.render(), "\u{65E5}\nabc\n");
+        assert_eq!(truncated.width(), 3);
+        let truncated = block.clone().truncate_at::<LeftRight, Left>(3);
+        assert_eq!(truncated.render(), " \u{8A9E}\ndef\n");
+        assert_eq!(truncated.width(), 3);
+
+        let truncated = block
+            .clone()
+            .truncate_with_ellipsis_at::<LeftRight, Right>(4, Grapheme::from('~'));
+        assert_eq!(truncated.render(), "\u{65E5} ~\nabc~\n");
+        assert_eq!(truncated.width(), 4);
+        let truncated = block
+            .clone()
+            .truncate_with_ellipsis_at::<LeftRight, Left>(4, Grapheme::from('~'));
+        assert_eq!(truncated.render(), "~ \u{8A9E}\n~def\n");
+        assert_eq!(truncated.width(), 4);
+
+        // An ellipsis that is wider than the truncated block is not used.
+        let truncated =
+            block.truncate_with_ellipsis_at::<LeftRight, Right>(1, Grapheme::from('\u{2026}'));
+        assert_eq!(truncated.width(), 1);
+    }
+
+    #[test]
+    fn block_truncate_to_width_with() {
+        let ellipsis = || OverflowPolicy::Ellipsis(Grapheme::from('\u{4E00}'));
+        let text = String::from("abcd");
+        assert_eq!(
+            text.clone().truncate_to_width_with(3, ellipsis()),
+            Ok(("a\u{4E00}".to_owned(), 3)),
+        );
+        // The ellipsis is dropped rather than exceeding the width.
+        assert_eq!(
+            text.clone().truncate_to_width_with(1, ellipsis()),
+            Ok(("a".to_owned(), 1)),
+        );
+        assert_eq!(
+            text.clone().truncate_to_width_with(0, ellipsis()),
+            Ok((String::new(), 0)),
+        );
+        assert_eq!(
+            text.clone().truncate_to_width_with(4, ellipsis()),
+            Ok((text.clone(), 4)),
+        );
+        assert_eq!(
+            String::from("a\u{4E00}").truncate_to_width_with(2, OverflowPolicy::Fail),
+            Err("a\u{4E00}".to_owned()),
+        );
+
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Color(char);
+
+        impl Transform for Color {
+            fn write_prefix(&self, target: &mut impl Write) -> io::Result<()> {
+                write!(target, "{}", self.0)
+            }
+        }
+
+        // The width of styled content is that of the fragments that are taken.
+        let styled = || {
+            Styled::<String, Color>::concatenate(
+                Styled::new(Color('r'), "\u{65E5}"),
+                Styled::new(Color('g'), "ab"),
+            )
+        };
+        let truncated = |width, overflow| {
+            styled()
+                .truncate_to_width_with(width, overflow)
+                .map(|(content, width)| (content.render().into_owned(), width, content.width()))
+        };
+        assert_eq!(
+            truncated(3, OverflowPolicy::Clip),
+            Ok(("r\u{65E5}ga".to_owned(), 3, 3)),
+        );
+        assert_eq!(
+            truncated(2, OverflowPolicy::Clip),
+            Ok(("r\u{65E5}".to_owned(), 2, 2)),
+        );
+        assert_eq!(
+            truncated(3, OverflowPolicy::Ellipsis(Grapheme::from('~'))),
+            Ok(("r\u{65E5}~".to_owned(), 3, 3)),
+        );
+        assert_eq!(
+            truncated(1, OverflowPolicy::Fail).map_err(|content| content.render().into_owned()),
+            Err("r\u{65E5}gab".to_owned()),
+        );
+    }
+
+    #[test]
+    fn block_fallible_operations() {
+        let valid = || <Block>::with_content("ab\nc");
+        assert!(valid().try_join_left_to_right_at_top(valid()).is_ok());
+        assert!(valid().try_join_left_to_right_at_bottom(valid()).is_ok());
+        assert!(valid().try_join_top_to_bottom_at_right(valid()).is_ok());
+        assert_eq!(
+            valid()
+                .try_overlay(<Block>::with_content("xy\nzw"))
+                .map(|block| block.render().into_owned()),
+            Ok("ab\ncw\n".to_owned()),
+        );
+
+        // Lines of content that cannot be repeated are not padded to the width of a block.
+        let front = || Block::<Text<false>>::with_content("ab\nc");
+        let back = || Block::<Text<false>>::with_content("xy\nzw");
+        let mismatch = CongruenceError::LineWidthMismatch {
+            expected: 2,
+            actual: 1,
+        };
+        assert_eq!(
+            front().try_join_left_to_right_at_top(back()).err(),
+            Some(mismatch)
+        );
+        assert_eq!(
+            front().try_join_top_to_bottom_at_left(back()).err(),
+            Some(mismatch)
+        );
+        assert_eq!(
+            front().try_join_left_to_right_at_bottom(back()).err(),
+            Some(mismatch)
+        );
+        assert_eq!(
+            front().try_join_top_to_bottom_at_right(back()).err(),
+            Some(mismatch)
+        );
+        assert!(back().try_join_left_to_right_at_top(back()).is_ok());
+
+        let mismatch = CongruenceError::WidthMismatch { left: 1, right: 2 };
+        assert_eq!(front().try_overlay(back()).err(), Some(mismatch));
+        assert_eq!(
+            front().try_overlay_by(back(), OverlayPolicy::Opaque).err(),
+            Some(mismatch),
+        );
+        let mask = Mask::with_dimensions(2, 2);
+        assert_eq!(
+            front().try_overlay_masked(&mask, back()).err(),
+            Some(mismatch)
+        );
+
+        // Infallible operations take lines that are not congruent from the front block.
+        assert_eq!(front().overlay(back()).render(), "ab\nc\n");
+        assert_eq!(front().overlay_masked(&mask, back()).render(), "xy\nc\n");
+
+        // Overlaying empty blocks with no height onto content with no lines does not fail.
+        let empty = || <Block>::with_dimensions(1, 0);
+        let block = empty().overlay(Block::with_content(""));
+        assert_eq!((block.width(), block.height()), (1, 0));
+        let block = <Block>::with_content("").overlay(empty());
+        assert_eq!((block.width(), block.height()), (1, 0));
+        assert!(empty().try_overlay(Block::with_content("")).is_ok());
+        assert!(<Block>::with_content("").try_overlay(empty()).is_ok());
+    }
 
+    #[test]
+    fn block_in_place_operations() {
         let mut block = <Block>::zero();
-        block = block.push("");
-        assert!(!block.is_empty());
+        block.push_mut("ab");
+        block.push_mut("abc");
+        block.join_left_to_right_at_top_mut(Block::with_content("x\ny\nz"));
+        block.join_top_to_bottom_at_left_mut(Block::with_width(6));
+        assert_eq!(
+            block,
+            <Block>::zero()
+                .push("ab")
+                .push("abc")
+                .join_left_to_right_at_top(Block::with_content("x\ny\nz"))
+                .join_top_to_bottom_at_left(Block::with_width(6)),
+        );
+    }
+
+    #[test]
+    fn block_borrowed_content() {
+        let text = String::from("ab\ncd\n");
+        let block = Block::<Cow<str>>::with_content(text.as_str());
+        match block.inner {
+            ModalBlock::Content(ref block) => {
+                assert!(block
+                    .lines
+                    .iter()
+                    .all(|line| matches!(line, Cow::Borrowed(_))));
+            }
+            _ => panic!(),
+        }
+        let line = Cow::Borrowed("a\u{301}bc");
+        assert!(matches!(
+            line.clone().truncate(2),
+            Cow::Borrowed("a\u{301}b")
+        ));
+        assert!(matches!(line.skip(1), Cow::Borrowed("bc")));
+    }
+
+    #[test]
+    fn block_shared_content() {
+        let block = Block::<Rc<str>>::with_content("ab\nc").pad_at_left(1);
+        assert_eq!(block.render(), " ab\n c\n");
+    }
+
+    #[test]
+    fn block_line_buffer() {
+        let mut buffer = LineBuffer::with_capacity(16, 4);
+        buffer.push("ab\nc");
+        buffer.extend(["def"]);
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.line(1), Some("c"));
+        assert_eq!(buffer.line(3), None);
+        assert_eq!(buffer.lines().collect::<Vec<_>>(), ["ab", "c", "def"]);
+
+        // Lines that are as wide as the block are borrowed from the buffer.
+        let block = Block::from(&buffer);
+        assert_eq!(block.render(), "ab\nc\ndef\n");
+        let lines = block.into_content_vec();
+        assert!(matches!(lines[2], Cow::Borrowed(line) if ptr::eq(line, buffer.line(2).unwrap())));
+        assert!(matches!(lines[0], Cow::Owned(_)));
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+        assert_eq!(Block::from(&buffer).height(), 0);
+    }
+
+    #[test]
+    fn block_shared_lines() {
+        let lines = |block: &Block<Arc<str>>| match block.inner {
+            ModalBlock::Content(ref block) => block.lines.clone(),
+            _ => panic!("block has no content"),
+        };
+
+        let block = Block::<Arc<str>>::with_content("ab\nc");
+        let mut clone = block.clone();
+        assert!(Arc::ptr_eq(&lines(&block).0, &lines(&clone).0));
+
+        // Modifying a clone copies the lines, but not the content of unaffected lines.
+        clone.push_mut("d");
+        assert!(!Arc::ptr_eq(&lines(&block).0, &lines(&clone).0));
+        assert!(Arc::ptr_eq(&lines(&block)[0], &lines(&clone)[0]));
+        assert_eq!(block.render(), "ab\nc\n");
+        assert_eq!(clone.render(), "ab\nc\nd\n");
+
+        let joined = block.clone().join_top_to_bottom_at_left(clone);
+        assert!(Arc::ptr_eq(&lines(&block)[1], &lines(&joined)[1]));
+    }
+
+    #[test]
+    fn block_styled_prefix_suffix() {
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        struct Bracket(bool);
+
+        impl Transform for Bracket {
+            fn write_prefix(&self, target: &mut impl Write) -> io::Result<()> {
+                if self.0 {
+                    target.write_all(b"<")?;
+                }
+                Ok(())
+            }
+
+            fn write_suffix(&self, target: &mut impl Write) -> io::Result<()> {
+                if self.0 {
+                    target.write_all(b">")?;
+                }
+                Ok(())
+            }
+        }
+
+        let block = Block::<Styled<String, Bracket>>::with_content(Styled::concatenate(
+            Styled::new(Bracket(true), "ab"),
+            Styled::new(Bracket(false), "c"),
+        ));
+        assert_eq!(block.render(), "<ab>c\n");
+        let mut output = vec![];
+        block.render_into(&mut output).unwrap();
+        assert_eq!(output, b"<ab>c");
+    }
+
+    #[test]
+    fn block_styled_escape_error() {
+        // This style fails to write its prefix after writing part of it.
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Broken(bool);
+
+        impl Transform for Broken {
+            fn write_prefix(&self, target: &mut impl Write) -> io::Result<()> {
+                target.write_all(b"<")?;
+                if self.0 {
+                    Err(io::Error::other("broken"))
+                }
+                else {
+                    Ok(())
+                }
+            }
+
+            fn write_suffix(&self, target: &mut impl Write) -> io::Result<()> {
+                target.write_all(b">")
+            }
+        }
+
+        let block = Block::<Styled<String, Broken>>::with_content(Styled::concatenate(
+            Styled::new(Broken(true), "ab"),
+            Styled::new(Broken(false), "c"),
+        ));
+        // Rendering drops the escapes that fail to write and keeps the text.
+        assert_eq!(block.render(), "ab><c>\n");
+        let mut output = vec![];
+        assert!(block.render_into(&mut output).is_err());
+    }
+
+    #[test]
+    fn block_styled_compose() {
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        struct Attributes {
+            color: Option<char>,
+            is_bold: bool,
+        }
+
+        impl Transform for Attributes {
+            fn write_prefix(&self, target: &mut impl Write) -> io::Result<()> {
+                if let Some(color) = self.color {
+                    write!(target, "{}", color)?;
+                }
+                if self.is_bold {
+                    target.write_all(b"*")?;
+                }
+                Ok(())
+            }
+
+            fn compose(&self, over: &Self) -> Self {
+                Attributes {
+                    color: self.color.or(over.color),
+                    is_bold: self.is_bold || over.is_bold,
+                }
+            }
+        }
+
+        let bold = Attributes {
+            color: None,
+            is_bold: true,
+        };
+        let red = Attributes {
+            color: Some('r'),
+            is_bold: false,
+        };
+        let block = Block::<Styled<String, Attributes>>::with_content(Styled::concatenate(
+            Styled::new(red, "ab"),
+            Styled::new(Attributes::default(), "c"),
+        ));
+        assert_eq!(block.clone().restyle(bold).render(), "r*ab*c\n");
+        let block = Block::with_content(Styled::new(bold, "x")).overlay(block);
+        assert_eq!(block.render(), "r*xrbc\n");
+    }
+
+    #[test]
+    fn block_restyle_gradient() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Shade(u8);
+
+        impl Transform for Shade {
+            fn write_prefix(&self, target: &mut impl Write) -> io::Result<()> {
+                write!(target, "{}", self.0)
+            }
+        }
+
+        impl Interpolate for Shade {
+            fn interpolate(&self, to: &Self, t: f64) -> Self {
+                Shade((f64::from(self.0) + (f64::from(to.0) - f64::from(self.0)) * t).round() as u8)
+            }
+        }
+
+        let block = Block::<Styled<String, Shade>>::with_content(Styled::plain("abc"))
+            .join_top_to_bottom_at_left(Block::with_content(Styled::plain("def")));
+        assert_eq!(
+            block
+                .clone()
+                .restyle_gradient(Shade(0), Shade(4), valued::Axis::LeftRight)
+                .render(),
+            "0a2b4c\n0d2e4f\n",
+        );
+        assert_eq!(
+            block
+                .restyle_gradient(Shade(0), Shade(4), valued::Axis::TopBottom)
+                .render(),
+            "0abc\n4def\n",
+        );
+
+        // A single column or row receives the initial style.
+        let block = Block::<Styled<String, Shade>>::with_content(Styled::plain("a"));
+        assert_eq!(
+            block
+                .clone()
+                .restyle_gradient(Shade(1), Shade(9), valued::Axis::LeftRight)
+                .render(),
+            "1a\n",
+        );
+        assert_eq!(
+            block
+                .restyle_gradient(Shade(1), Shade(9), valued::Axis::TopBottom)
+                .render(),
+            "1a\n",
+        );
+    }
+
+    #[test]
+    fn block_styled_plain() {
+        // This style has no meaningful default.
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Color(char);
+
+        impl Transform for Color {
+            fn write_prefix(&self, target: &mut impl Write) -> io::Result<()> {
+                write!(target, "{}", self.0)
+            }
+        }
+
+        let block = Block::<Styled<String, Color>>::with_content(Styled::concatenate(
+            Styled::new(Color('r'), "ab"),
+            Styled::plain("c"),
+        ))
+        .pad_at_left(1);
+        assert_eq!(block.render(), " rabc\n");
+        assert_eq!(block.restyle(Color('g')).render(), "g abc\n");
+    }
+
+    #[test]
+    fn block_styled_spans() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Color(char);
+
+        impl Transform for Color {}
+
+        let line = Styled::<String, Color>::concatenate(
+            Styled::new(Color('r'), "a\u{4E00}"),
+            Styled::concatenate(Styled::plain(""), Styled::plain("b")),
+        );
+        let spans: Vec<_> = line
+            .spans()
+            .map(|span| (span.style.copied(), span.text, span.offset, span.column))
+            .collect();
+        assert_eq!(
+            spans,
+            [(Some(Color('r')), "a\u{4E00}", 0, 0), (None, "b", 4, 3)]
+        );
+        let graphemes: Vec<_> = line
+            .styled_graphemes()
+            .map(|(glyph, style)| (glyph.get().to_owned(), style.copied()))
+            .collect();
+        assert_eq!(
+            graphemes,
+            [
+                ("a".to_owned(), Some(Color('r'))),
+                ("\u{4E00}".to_owned(), Some(Color('r'))),
+                ("b".to_owned(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn block_styled_builder() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Color(char);
+
+        impl Transform for Color {
+            fn write_prefix(&self, target: &mut impl Write) -> io::Result<()> {
+                write!(target, "{}", self.0)
+            }
+        }
+
+        let mut builder = StyledBuilder::<String, Color>::new();
+        builder
+            .push_str(Color('r'), "ab")
+            .push_grapheme(Color('r'), Grapheme::from('c'))
+            .push_plain("")
+            .push_plain(" ")
+            .push_str(Color('g'), "d");
+        let line = builder.finish();
+        assert_eq!(line.spans().count(), 3);
+        assert_eq!(line.width(), 5);
+        assert_eq!(line.render(), "rabc gd");
+
+        let line: Styled<String, Color> = [(Color('r'), "a"), (Color('r'), "b"), (Color('g'), "c")]
+            .into_iter()
+            .collect();
+        assert_eq!(line.spans().count(), 2);
+        assert_eq!(line.render(), "rabgc");
+    }
+
+    #[test]
+    fn block_styled_coalesce() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Color(char);
+
+        impl Transform for Color {
+            fn write_prefix(&self, target: &mut impl Write) -> io::Result<()> {
+                write!(target, "{}", self.0)
+            }
+        }
+
+        // Styles need not be comparable unless fragments are coalesced.
+        #[derive(Clone, Debug)]
+        struct Opaque;
+
+        impl Transform for Opaque {}
+
+        assert_eq!(Styled::<String, Opaque>::new(Opaque, "a").render(), "a");
+
+        let line = Styled::<String, Color>::concatenate(
+            Styled::from_iter([(Color('r'), "a"), (Color('g'), "b")]),
+            Styled::concatenate(Styled::plain(""), Styled::new(Color('g'), "c")),
+        );
+        assert_eq!(line.spans().count(), 2);
+        assert_eq!(line.width(), 3);
+        assert_eq!(line.render(), "ragbc");
+
+        let line = (0..100).fold(Styled::<String, Color>::empty(), |line, _| {
+            Styled::concatenate(line, Styled::new(Color('r'), "a"))
+        });
+        assert_eq!(line.spans().count(), 1);
+        assert_eq!(line.width(), 100);
+        let line = Styled::concatenate(line, Styled::plain("b"))
+            .restyle_selected(Color('r'), |column| column == 100)
+            .coalesce();
+        assert_eq!(line.spans().count(), 1);
+    }
+
+    #[test]
+    fn block_styled_truncate() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Color(char);
+
+        impl Transform for Color {
+            fn write_prefix(&self, target: &mut impl Write) -> io::Result<()> {
+                write!(target, "{}", self.0)
+            }
+        }
+
+        let line = Styled::<String, Color>::concatenate(
+            Styled::new(Color('r'), "ab"),
+            Styled::new(Color('g'), "cdef"),
+        );
+        assert_eq!(line.clone().truncate(2).render(), "rab");
+        assert_eq!(line.clone().truncate(3).render(), "rabgc");
+        assert_eq!(line.truncate(1).width(), 1);
+
+        // Graphemes are counted across fragments, like `skip`.
+        let line = Styled::<String, Color>::concatenate(
+            Styled::new(Color('r'), "\u{65E5}"),
+            Styled::new(Color('g'), "ab"),
+        );
+        assert_eq!(line.clone().truncate(2).render(), "r\u{65E5}ga");
+        assert_eq!(line.clone().skip(2).render(), "gb");
+        assert_eq!(
+            wrap::wrap_by(line, 3, WrapPolicy::BreakAnywhere)
+                .iter()
+                .map(|line| line.render().into_owned())
+                .collect::<Vec<_>>(),
+            ["r\u{65E5}ga", "gb"],
+        );
+    }
+
+    #[test]
+    fn block_overlay_resolved() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Color(char);
+
+        impl Transform for Color {
+            fn write_prefix(&self, target: &mut impl Write) -> io::Result<()> {
+                write!(target, "{}", self.0)
+            }
+        }
+
+        let back = Block::<Styled<String, Color>>::with_content(Styled::concatenate(
+            Styled::new(Color('r'), "ab"),
+            Styled::new(Color('g'), "c"),
+        ));
+        // Take the grapheme from the front but the style from the back.
+        let block = Block::with_content(Styled::new(Color('b'), " x")).overlay_resolved(
+            back,
+            |(front, _), (_, style)| {
+                if *front == Grapheme::SPACE {
+                    Resolved::new(Layer::Back(()), style.copied())
+                }
+                else {
+                    Resolved::new(Layer::Front(()), style.copied())
+                }
+            },
+        );
+        assert_eq!(block.render(), "raxgc\n");
+    }
+
+    #[test]
+    fn block_overlay_transparent() {
+        let back = Block::<CellBuffer>::with_content("abc\ndef");
+        let front = Block::<CellBuffer>::with_content("x y").join_top_to_bottom_at_left(
+            Block::with_content(CellBuffer::concatenate(
+                CellBuffer::from(" "),
+                CellBuffer::transparent(2),
+            )),
+        );
+        assert_eq!(front.render(), "x y\n\n");
+        assert_eq!(front.clone().overlay(back.clone()).render(), "xby\ndef\n");
+        assert_eq!(
+            front
+                .clone()
+                .overlay_by(back.clone(), OverlayPolicy::Transparent)
+                .render(),
+            "x y\n ef\n"
+        );
+        assert_eq!(
+            front.overlay_by(back, OverlayPolicy::Opaque).render(),
+            "x y\n\n"
+        );
+
+        assert!(Grapheme::TRANSPARENT.is_transparent());
+        assert_ne!(Grapheme::TRANSPARENT, Grapheme::SPACE);
+        assert_eq!(Grapheme::TRANSPARENT.get(), " ");
+        assert_eq!(Grapheme::TRANSPARENT.width(), 1);
+        assert_eq!(String::grapheme(Grapheme::TRANSPARENT), " ");
+        assert!(Grapheme::TRANSPARENT.into_owned().is_transparent());
+        assert!(!Grapheme::SPACE.is_transparent());
+        let buffer = CellBuffer::concatenate(
+            CellBuffer::from(" "),
+            CellBuffer::grapheme(Grapheme::TRANSPARENT),
+        );
+        assert_eq!(
+            buffer.to_graphemes(),
+            [Grapheme::SPACE, Grapheme::TRANSPARENT]
+        );
+        assert_eq!(buffer.as_str(), "  ");
+    }
+
+    #[test]
+    fn block_overlay_all() {
+        let layers = || {
+            vec![
+                <Block>::with_content("a  \n  b"),
+                <Block>::with_dimensions(4, 1),
+                <Block>::with_content(" c\n d"),
+                <Block>::with_content("xxx\nyyy"),
+            ]
+        };
+        let pairwise = layers()
+            .into_iter()
+            .rev()
+            .reduce(|back, front| front.overlay(back))
+            .unwrap();
+        let block = Block::overlay_all(layers());
+        assert_eq!(block.render(), "acx\nydb\n");
+        assert_eq!(block, pairwise);
+        assert_eq!(
+            Block::overlay_all_by(layers(), OverlayPolicy::Opaque),
+            layers()
+                .into_iter()
+                .rev()
+                .reduce(|back, front| front.overlay_by(back, OverlayPolicy::Opaque))
+                .unwrap()
+        );
+        assert!(Block::<String>::overlay_all(vec![]).is_empty());
+    }
+
+    #[test]
+    fn block_join_along_axes() {
+        let wide = <Block>::with_content("\u{4E00}\n\u{4E00}\u{4E00}");
+        let narrow = <Block>::with_content("a");
+        assert_eq!(
+            narrow
+                .clone()
+                .join_left_to_right_at_bottom(wide.clone())
+                .render(),
+            " \u{4E00}\na\u{4E00}\u{4E00}\n"
+        );
+        assert_eq!(
+            narrow
+                .clone()
+                .join_top_to_bottom_at_right(wide.clone())
+                .render(),
+            "   a\n\u{4E00}\n\u{4E00}\u{4E00}\n"
+        );
+        assert_eq!(
+            wide.pad_at_left(1)
+                .pad_at_top(1)
+                .pad_to_height_at_top(4)
+                .render(),
+            "\n\n \u{4E00}\n \u{4E00}\u{4E00}\n"
+        );
+    }
+
+    #[test]
+    fn block_join_empty_and_content() {
+        let block =
+            <Block>::with_dimensions(2, 3).join_left_to_right_at_top(Block::with_content("a"));
+        assert_eq!((block.width(), block.height()), (3, 3));
+        assert_eq!(block.render(), "  a\n\n\n");
+        let block =
+            <Block>::with_content("ab").join_top_to_bottom_at_left(Block::with_dimensions(3, 1));
+        assert_eq!((block.width(), block.height()), (3, 2));
+        let block = <Block>::with_dimensions(1, 1)
+            .pad_at_left(2)
+            .pad_at_bottom(1);
+        assert!(matches!(block.inner, ModalBlock::Empty(_)));
+        assert_eq!((block.width(), block.height()), (3, 2));
+    }
+
+    #[test]
+    fn block_errors() {
+        assert_eq!(Grapheme::try_from(""), Err(GraphemeError::Empty));
+        assert_eq!(
+            Grapheme::try_from("ab"),
+            Err(GraphemeError::MultipleClusters)
+        );
+        assert_eq!(
+            Grapheme::try_from("e\u{301}").map(|glyph| glyph.get().to_owned()),
+            Ok("e\u{301}".to_owned()),
+        );
+        assert_eq!(
+            content::Congruent::try_from((String::from("a"), String::from("bc"))).err(),
+            Some(CongruenceError::WidthMismatch { left: 1, right: 2 }),
+        );
+        assert!(content::Congruent::try_from((String::from("ab"), String::from("cd"))).is_ok());
+
+        // Errors are reported through `std::error::Error`.
+        let error: Box<dyn std::error::Error> = Box::new(GraphemeError::MultipleClusters);
+        assert_eq!(
+            error.to_string(),
+            "text contains more than one grapheme cluster"
+        );
+        assert_eq!(
+            CongruenceError::WidthMismatch { left: 1, right: 2 }.to_string(),
+            "content widths differ: left is 1 and right is 2",
+        );
+        assert_eq!(
+            CongruenceError::LineWidthMismatch {
+                expected: 2,
+                actual: 1,
+            }
+            .to_string(),
+            "line width is 1 but the block width is 2",
+        );
+        assert_eq!(
+            FillError::ZeroHeight.to_string(),
+            "cannot fill a block with zero height"
+        );
+    }
+
+    #[test]
+    fn block_emptiness() {
+        let block = <Block>::with_dimensions(1, 1);
+        assert!(block.is_empty() && block.is_blank() && !block.is_zero());
+        let block = <Block>::with_dimensions(3, 0);
+        assert!(block.is_empty() && block.is_zero());
+        assert_eq!(
+            block.try_fill(Grapheme::from('x')),
+            Err(FillError::ZeroHeight)
+        );
+        let block = <Block>::with_content("  ");
+        assert!(!block.is_empty() && block.is_blank() && !block.is_zero());
+
+        let block = <Block>::with_dimensions(3, 2);
+        assert_eq!(
+            block.clone().try_fill(String::new()),
+            Err(FillError::EmptyFiller)
+        );
+        assert!(block.clone().fill(String::new()).is_empty());
+        let block = block.try_fill(String::from("ab\nc")).unwrap();
+        assert_eq!(block.render(), "aba\nc c\n");
+        assert!(!block.is_blank());
+    }
+
+    #[test]
+    fn block_axial_generic() {
+        // Joins blocks along an axis separated by spacers.
+        fn spaced<A>(blocks: Vec<Block>, gap: usize) -> Block
+        where
+            Block: AxialBlock<A>,
+            A: typed::Axis,
+        {
+            blocks
+                .into_iter()
+                .reduce(|output, block| output.join_at_origin::<A>(block.pad_at_origin::<A>(gap)))
+                .unwrap_or_else(|| Block::with_length_at::<A>(0, 0))
+        }
+
+        let blocks = || vec![<Block>::with_content("ab"), Block::with_content("c")];
+        let block = spaced::<LeftRight>(blocks(), 1);
+        assert_eq!(block.render(), "ab c\n");
+        assert_eq!(block.length_at::<LeftRight>(), 4);
+        let block = spaced::<TopBottom>(blocks(), 1);
+        assert_eq!(block.render(), "ab\n\nc\n");
+        assert_eq!(block.length_at::<TopBottom>(), 3);
+    }
+
+    #[test]
+    fn block_cell_buffer() {
+        let block = Block::<CellBuffer>::with_content("x")
+            .overlay(Block::with_content("a\u{301}b\n\u{4E00}c").pad_at_left(1));
+        assert_eq!(block.render(), "xa\u{301}b\n \u{4E00}c\n");
+
+        let line = CellBuffer::from("a\u{301}\u{4E00}c");
+        assert_eq!(line.len(), 3);
+        assert_eq!(line.width(), 4);
+        assert_eq!(line.get(1).unwrap().get(), "\u{4E00}");
+        assert_eq!(line.width_at(1), Some(2));
+        let line = line.skip(1);
+        assert_eq!((line.as_str(), line.width()), ("\u{4E00}c", 3));
+        let line = CellBuffer::concatenate(line.clone(), line).truncate(3);
+        assert_eq!((line.as_str(), line.width()), ("\u{4E00}c\u{4E00}", 5));
+        assert_eq!(String::from(line), "\u{4E00}c\u{4E00}");
+
+        // Cells are overlaid by column, so wide cells may cover several narrow cells.
+        let front = CellBuffer::concatenate(CellBuffer::from("a"), CellBuffer::transparent(2));
+        let block =
+            Block::<CellBuffer>::with_content(front).overlay(Block::with_content("\u{4E00}c"));
+        assert_eq!(block.render(), "a c\n");
+        assert_eq!(block.width(), 3);
+        let front =
+            CellBuffer::concatenate(CellBuffer::from("\u{4E00}"), CellBuffer::transparent(1));
+        let block = Block::<CellBuffer>::with_content(front).overlay(Block::with_content("abc"));
+        assert_eq!(block.render(), "\u{4E00}c\n");
+        assert_eq!(block.width(), 3);
+    }
+
+    #[test]
+    fn block_pages() {
+        let block = <Block>::with_content("a\nb\nc");
+        let pages: Vec<_> = block
+            .clone()
+            .pages(2)
+            .map(|page| page.render().into_owned())
+            .collect();
+        assert_eq!(pages, ["a\nb\n", "c\n"]);
+        for height in [0, 1] {
+            let pages: Vec<_> = block
+                .clone()
+                .pages(height)
+                .map(|page| page.render().into_owned())
+                .collect();
+            assert_eq!(pages, ["a\n", "b\n", "c\n"]);
+        }
+    }
+
+    #[test]
+    fn block_pages_with_footer() {
+        let block = <Block>::with_content("a\nb\nc");
+        let pages: Vec<_> = block
+            .clone()
+            .pages_with_footer(3, |n, m| format!("{}/{}", n, m))
+            .unwrap()
+            .map(|page| page.render().into_owned())
+            .collect();
+        assert_eq!(pages, ["a\nb\n1/2\n", "c\n\n2/2\n"]);
+        let pages: Vec<_> = block
+            .clone()
+            .pages_with_footer(2, |n, _| format!("{}\n-", n))
+            .unwrap()
+            .map(|page| page.render().into_owned())
+            .collect();
+        assert_eq!(pages, ["a\n1\n", "b\n2\n", "c\n3\n"]);
+        for height in [0, 1] {
+            assert!(matches!(
+                block
+                    .clone()
+                    .pages_with_footer(height, |_, _| String::new()),
+                Err(PageError::ZeroContentHeight),
+            ));
+        }
+    }
+
+    #[test]
+    fn block_reflow() {
+        let block = <Block>::with_content("the quick\nbrown fox\n\njumps").reflow(11);
+        assert_eq!(block.render(), "the quick\nbrown fox\n\njumps\n");
+        assert_eq!(block.width(), 11);
+
+        let block = block.reflow(20);
+        assert_eq!(block.render(), "the quick brown fox\n\njumps\n");
+
+        let block =
+            <Block>::with_content("ab https://x.y/z").reflow_by(4, WrapPolicy::WordBoundary);
+        assert_eq!(block.render(), "ab\nhttp\ns://\nx.y/\nz\n");
+        let block = <Block>::with_content("ab cdef").reflow_by(3, WrapPolicy::BreakAnywhere);
+        assert_eq!(block.render(), "ab\ncde\nf\n");
+        let block = <Block>::with_content("ab cdef").reflow_by(3, WrapPolicy::NoBreakOverflow);
+        assert_eq!(block.render(), "ab\ncdef\n");
+
+        let block = <Block>::with_content("ab c\u{A0}d ef\u{200B}gh ij\u{AD}kl").reflow(3);
+        assert_eq!(block.render(), "ab\nc\u{A0}d\nef\ngh\nij-\nkl\n");
+        let block = <Block>::with_content("a b\u{200B}c d").reflow_justified(5);
+        assert_eq!(block.render(), "a  b\u{200B}c\nd\n");
+
+        let block = <Block>::with_content("a bb c dd\neee\n\nffff g").reflow_justified(7);
+        assert_eq!(block.render(), "a  bb c\ndd eee\n\nffff g\n");
     }
 
     #[test]
     fn block_styled_overlay() {
-        #[derive(Clone, Copy, Debug, Default)]
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
         struct Style(colored::Style);
 
         impl Transform for Style {
@@ -1,11 +1,290 @@
-use itertools::{Itertools as _, Position};
+use itertools::{Either, Itertools as _, Position};
 use std::borrow::Cow;
+use std::cmp;
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
 use std::io::{self, Write};
+use std::iter;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr as UnicodeWidth;
 
-use crate::Render;
+use crate::error::{CongruenceError, GraphemeError};
+use crate::{Render, RenderContext};
+
+static IS_AMBIGUOUS_WIDE: AtomicBool = AtomicBool::new(false);
+
+// Incremented whenever a setting that affects the widths of text changes.
+static WIDTH_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+fn width_generation() -> usize {
+    WIDTH_GENERATION.load(Ordering::Acquire)
+}
+
+fn invalidate_widths() {
+    WIDTH_GENERATION.fetch_add(1, Ordering::AcqRel);
+}
+
+// A measured width that is measured again if any width settings have changed since.
+//
+// Cached widths are derived from content, so they are ignored when comparing and hashing.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct CachedWidth {
+    width: usize,
+    generation: usize,
+}
+
+impl CachedWidth {
+    pub fn new(width: usize) -> Self {
+        CachedWidth {
+            width,
+            generation: width_generation(),
+        }
+    }
+
+    pub fn is_current(&self) -> bool {
+        self.generation == width_generation()
+    }
+
+    pub fn get_or_measure(&self, f: impl FnOnce() -> usize) -> usize {
+        if self.is_current() {
+            self.width
+        }
+        else {
+            f()
+        }
+    }
+}
+
+impl Default for CachedWidth {
+    fn default() -> Self {
+        CachedWidth::new(0)
+    }
+}
+
+impl Eq for CachedWidth {}
+
+impl Hash for CachedWidth {
+    fn hash<H>(&self, _: &mut H)
+    where
+        H: Hasher,
+    {
+    }
+}
+
+impl PartialEq for CachedWidth {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum AmbiguousWidth {
+    #[default]
+    Narrow,
+    Wide,
+}
+
+impl AmbiguousWidth {
+    pub fn get() -> Self {
+        if IS_AMBIGUOUS_WIDE.load(Ordering::Relaxed) {
+            AmbiguousWidth::Wide
+        }
+        else {
+            AmbiguousWidth::Narrow
+        }
+    }
+
+    // NOTE: This setting is global and is typically configured once before any layout. Cached
+    //       widths are measured again after changing this setting, but blocks composed before
+    //       and after changing it may disagree.
+    pub fn set(self) {
+        IS_AMBIGUOUS_WIDE.store(self == AmbiguousWidth::Wide, Ordering::Relaxed);
+        invalidate_widths();
+    }
+}
+
+#[cfg(feature = "bidi")]
+static IS_ISOLATED: AtomicBool = AtomicBool::new(false);
+
+/// Directional isolation of rendered lines.
+///
+/// When lines are isolated, each rendered line of a block is wrapped in left-to-right isolates
+/// (U+2066 and U+2069) and each run of content within a line is wrapped in first strong isolates
+/// (U+2068 and U+2069). Layout, such as box drawing, is then always ordered from left to right
+/// and right-to-left text cannot reorder the content around it. These characters have no width
+/// and so do not affect layout.
+///
+/// Runs of content are separated by box drawing and block elements, the ASCII strokes `|` and
+/// `+`, and two or more spaces.
+#[cfg(feature = "bidi")]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Isolation {
+    #[default]
+    None,
+    FirstStrong,
+}
+
+#[cfg(feature = "bidi")]
+impl Isolation {
+    pub fn get() -> Self {
+        if IS_ISOLATED.load(Ordering::Relaxed) {
+            Isolation::FirstStrong
+        }
+        else {
+            Isolation::None
+        }
+    }
+
+    // NOTE: Like `AmbiguousWidth`, this setting is global and is typically configured once before
+    //       any rendering.
+    pub fn set(self) {
+        IS_ISOLATED.store(self == Isolation::FirstStrong, Ordering::Relaxed);
+    }
+}
+
+// Determines whether or not a code point belongs to the layout around content rather than the
+// content itself.
+fn is_layout(point: char) -> bool {
+    matches!(point, '\u{2500}'..='\u{259F}' | '|' | '+')
+}
+
+// Isolates a rendered line as described by `Isolation`.
+pub(crate) fn isolate(line: &str) -> String {
+    let points: Vec<_> = line.char_indices().collect();
+    let is_content = |index: usize| {
+        points
+            .get(index)
+            .is_some_and(|&(_, point)| !(point == ' ' || is_layout(point)))
+    };
+    let is_space = |index: usize| points.get(index).is_some_and(|&(_, point)| point == ' ');
+    let offset = |index: usize| points.get(index).map_or(line.len(), |&(offset, _)| offset);
+    let mut output = String::with_capacity(line.len() + 8);
+    output.push('\u{2066}');
+    let mut index = 0;
+    while index < points.len() {
+        if !is_content(index) {
+            output.push(points[index].1);
+            index += 1;
+            continue;
+        }
+        // Content extends through single spaces, but not through layout or wider gaps.
+        let start = index;
+        while is_content(index) || (is_space(index) && is_content(index + 1)) {
+            index += 1;
+        }
+        output.push('\u{2068}');
+        output.push_str(&line[offset(start)..offset(index)]);
+        output.push('\u{2069}');
+    }
+    output.push('\u{2069}');
+    output
+}
+
+/// Overrides the measured width of graphemes.
+///
+/// Width policies are shared among threads, so that the same policy applies to parallel
+/// operations.
+pub trait WidthPolicy: Send + Sync {
+    fn width(&self, glyph: &Grapheme) -> Option<usize>;
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct EmojiWidthPolicy;
+
+impl WidthPolicy for EmojiWidthPolicy {
+    fn width(&self, glyph: &Grapheme) -> Option<usize> {
+        // Terminals typically render emoji presentation sequences and ZWJ sequences in two
+        // columns, but these are not predicted by `unicode-width`.
+        glyph
+            .code_points()
+            .any(|point| point == '\u{FE0F}' || point == '\u{200D}')
+            .then_some(2)
+    }
+}
+
+static HAS_WIDTH_POLICY: AtomicBool = AtomicBool::new(false);
+static WIDTH_POLICY: RwLock<Option<Arc<dyn WidthPolicy>>> = RwLock::new(None);
+
+// NOTE: Like `AmbiguousWidth`, this setting is global and is typically configured once before any
+//       layout.
+pub fn set_width_policy(policy: impl 'static + WidthPolicy) {
+    *WIDTH_POLICY.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(policy));
+    HAS_WIDTH_POLICY.store(true, Ordering::Release);
+    invalidate_widths();
+}
+
+pub fn clear_width_policy() {
+    HAS_WIDTH_POLICY.store(false, Ordering::Release);
+    *WIDTH_POLICY.write().unwrap_or_else(PoisonError::into_inner) = None;
+    invalidate_widths();
+}
+
+// Printable ASCII text has exactly one grapheme and one column per byte, so segmentation and
+// width lookups are skipped.
+fn is_printable_ascii(text: &str) -> bool {
+    text.bytes().all(|byte| (b' '..=b'~').contains(&byte))
+}
+
+// Gets the byte offset of the grapheme at the given index or the length of the text if there are
+// fewer graphemes.
+fn grapheme_offset(text: &str, index: usize) -> usize {
+    if is_printable_ascii(text) {
+        cmp::min(index, text.len())
+    }
+    else {
+        text.grapheme_indices(true)
+            .nth(index)
+            .map_or(text.len(), |(offset, _)| offset)
+    }
+}
+
+// Repeats each grapheme in text `n` times.
+fn stretch(text: &str, n: usize) -> String {
+    graphemes(text)
+        .flat_map(|glyph| iter::repeat_n(glyph, n))
+        .collect()
+}
+
+pub(crate) fn graphemes(text: &str) -> impl Iterator<Item = &str> {
+    if is_printable_ascii(text) {
+        Either::Left((0..text.len()).map(move |index| &text[index..=index]))
+    }
+    else {
+        Either::Right(text.graphemes(true))
+    }
+}
+
+fn unicode_width(text: &str) -> usize {
+    if is_printable_ascii(text) {
+        return text.len();
+    }
+    match AmbiguousWidth::get() {
+        AmbiguousWidth::Narrow => UnicodeWidth::width(text),
+        AmbiguousWidth::Wide => UnicodeWidth::width_cjk(text),
+    }
+}
+
+pub(crate) fn str_width(text: &str) -> usize {
+    // The lock is only acquired if a policy has been set.
+    if !HAS_WIDTH_POLICY.load(Ordering::Acquire) {
+        return unicode_width(text);
+    }
+    let policy = WIDTH_POLICY.read().unwrap_or_else(PoisonError::into_inner);
+    match policy.as_deref() {
+        Some(policy) => graphemes(text)
+            .map(|glyph| {
+                policy
+                    .width(&Grapheme::unchecked(glyph))
+                    .unwrap_or_else(|| unicode_width(glyph))
+            })
+            .sum(),
+        None => unicode_width(text),
+    }
+}
 
 pub(crate) trait ContentSlice<C>
 where
@@ -56,21 +335,55 @@ where
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
-pub struct Grapheme<'t>(Cow<'t, str>);
+pub struct Grapheme<'t> {
+    text: Cow<'t, str>,
+    is_transparent: bool,
+}
 
 impl<'t> Grapheme<'t> {
-    pub const SPACE: Grapheme<'static> = Grapheme(Cow::Borrowed(" "));
+    pub const SPACE: Grapheme<'static> = Grapheme::opaque(Cow::Borrowed(" "));
+    /// A cell through which the back layer is always shown when overlayed.
+    ///
+    /// Transparent cells are distinct from spaces, but are rendered as spaces and have the same
+    /// width. Only content that stores cells, such as `CellBuffer`, retains transparency; other
+    /// content stores transparent cells as spaces.
+    pub const TRANSPARENT: Grapheme<'static> = Grapheme {
+        text: Cow::Borrowed(" "),
+        is_transparent: true,
+    };
+
+    const fn opaque(text: Cow<'t, str>) -> Self {
+        Grapheme {
+            text,
+            is_transparent: false,
+        }
+    }
 
     fn unchecked(text: &'t str) -> Self {
-        Grapheme(text.into())
+        Grapheme::opaque(text.into())
     }
 
     pub fn get(&self) -> &str {
-        self.0.as_ref()
+        self.text.as_ref()
+    }
+
+    pub fn is_transparent(&self) -> bool {
+        self.is_transparent
+    }
+
+    pub fn width(&self) -> usize {
+        str_width(self.get())
     }
 
     pub fn code_points(&self) -> impl '_ + Iterator<Item = char> {
-        self.0.chars()
+        self.get().chars()
+    }
+
+    pub fn into_owned(self) -> Grapheme<'static> {
+        Grapheme {
+            text: self.text.into_owned().into(),
+            is_transparent: self.is_transparent,
+        }
     }
 }
 
@@ -84,7 +397,7 @@ impl<'t> Cell for Grapheme<'t> {}
 
 impl From<char> for Grapheme<'static> {
     fn from(point: char) -> Self {
-        Grapheme(String::from(point).into())
+        Grapheme::opaque(String::from(point).into())
     }
 }
 
@@ -96,14 +409,255 @@ impl<'t, S> From<StyledCell<'t, S>> for Grapheme<'t> {
 }
 
 impl<'t> TryFrom<&'t str> for Grapheme<'t> {
-    type Error = ();
+    type Error = GraphemeError;
 
     fn try_from(text: &'t str) -> Result<Self, Self::Error> {
-        if text.graphemes(true).take(2).count() == 1 {
-            Ok(Grapheme(text.into()))
+        match graphemes(text).take(2).count() {
+            0 => Err(GraphemeError::Empty),
+            1 => Ok(Grapheme::opaque(text.into())),
+            _ => Err(GraphemeError::MultipleClusters),
+        }
+    }
+}
+
+/// Determines the layer that is shown in each cell when overlaying blocks.
+///
+/// Use `Block::overlay_with` to determine layers with a function instead.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum OverlayPolicy {
+    /// Spaces and transparent cells in the front layer show the back layer.
+    #[default]
+    Spaces,
+    /// Only transparent cells in the front layer show the back layer.
+    ///
+    /// See `Grapheme::TRANSPARENT`.
+    Transparent,
+    /// The front layer is always shown.
+    Opaque,
+}
+
+impl OverlayPolicy {
+    pub fn layer(&self, front: &Grapheme, _: &Grapheme) -> Layer {
+        let is_back = match self {
+            OverlayPolicy::Spaces => front.is_transparent() || *front == Grapheme::SPACE,
+            OverlayPolicy::Transparent => front.is_transparent(),
+            OverlayPolicy::Opaque => false,
+        };
+        if is_back {
+            Layer::Back(())
         }
         else {
-            Err(())
+            Layer::Front(())
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum OverflowPolicy {
+    Clip,
+    Ellipsis(Grapheme<'static>),
+    Fail,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum LineBreakPolicy {
+    #[default]
+    Simple,
+    Unicode,
+}
+
+impl LineBreakPolicy {
+    fn split(self, text: &str) -> Vec<&str> {
+        match self {
+            LineBreakPolicy::Simple => text
+                .split('\n')
+                .map(|line| line.strip_suffix('\r').unwrap_or(line))
+                .collect(),
+            LineBreakPolicy::Unicode => {
+                let mut lines = vec![];
+                let mut start = 0usize;
+                let mut points = text.char_indices().peekable();
+                while let Some((index, point)) = points.next() {
+                    match point {
+                        '\r' => {
+                            lines.push(&text[start..index]);
+                            start = index + 1;
+                            if let Some((_, '\n')) = points.peek() {
+                                points.next();
+                                start += 1;
+                            }
+                        }
+                        '\n' | '\u{0B}' | '\u{0C}' | '\u{85}' | '\u{2028}' | '\u{2029}' => {
+                            lines.push(&text[start..index]);
+                            start = index + point.len_utf8();
+                        }
+                        _ => {}
+                    }
+                }
+                lines.push(&text[start..]);
+                lines
+            }
+        }
+    }
+
+    fn lines(self, text: &str) -> Vec<&str> {
+        let mut lines = self.split(text);
+        // Like `str::lines`, a trailing line break does not begin an empty line.
+        if lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        lines
+    }
+}
+
+// Removes ANSI escape sequences (e.g., SGR styles) from rendered text. Control sequences are
+// terminated by a final byte and operating system commands are terminated by BEL or ST.
+pub(crate) fn strip_escapes(text: &str) -> Cow<'_, str> {
+    if !text.contains('\x1B') {
+        return text.into();
+    }
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(point) = chars.next() {
+        if point != '\x1B' {
+            output.push(point);
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                for point in chars.by_ref() {
+                    if ('\x40'..='\x7E').contains(&point) {
+                        break;
+                    }
+                }
+            }
+            Some(']') => {
+                while let Some(point) = chars.next() {
+                    if point == '\x07' {
+                        break;
+                    }
+                    if point == '\x1B' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    output.into()
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SanitizePolicy {
+    pub tab_width: usize,
+    pub replacement: Option<Grapheme<'static>>,
+}
+
+impl SanitizePolicy {
+    fn sanitize(&self, text: &str, column: &mut usize) -> String {
+        let mut output = String::with_capacity(text.len());
+        for glyph in graphemes(text) {
+            if glyph == "\t" {
+                let n = if self.tab_width == 0 {
+                    0
+                }
+                else {
+                    self.tab_width - (*column % self.tab_width)
+                };
+                output.extend(iter::repeat_n(' ', n));
+                *column += n;
+            }
+            else if glyph == "\n" || glyph == "\r\n" {
+                output.push_str(glyph);
+                *column = 0;
+            }
+            else if glyph.chars().any(char::is_control) {
+                if let Some(ref replacement) = self.replacement {
+                    output.push_str(replacement.get());
+                    *column += replacement.width();
+                }
+            }
+            else {
+                output.push_str(glyph);
+                *column += str_width(glyph);
+            }
+        }
+        output
+    }
+}
+
+impl Default for SanitizePolicy {
+    fn default() -> Self {
+        SanitizePolicy {
+            tab_width: 8,
+            replacement: Some(Grapheme::opaque(Cow::Borrowed("\u{FFFD}"))),
+        }
+    }
+}
+
+/// Lines of text stored in one contiguous buffer.
+///
+/// The text of all lines is stored in a single `String` and lines are indexed by their ends.
+/// Blocks constructed from a `LineBuffer` borrow their lines from the buffer, so the text of lines
+/// is not copied unless a line must be padded. The storage of other blocks is unchanged; such
+/// blocks still hold a vector of content, which here is a vector of borrowed slices.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct LineBuffer {
+    text: String,
+    ends: Vec<usize>,
+}
+
+impl LineBuffer {
+    pub fn new() -> Self {
+        LineBuffer::default()
+    }
+
+    pub fn with_capacity(bytes: usize, lines: usize) -> Self {
+        LineBuffer {
+            text: String::with_capacity(bytes),
+            ends: Vec::with_capacity(lines),
+        }
+    }
+
+    pub fn push(&mut self, text: &str) {
+        for line in LineBreakPolicy::default().lines(text) {
+            self.text.push_str(line);
+            self.ends.push(self.text.len());
+        }
+    }
+
+    pub fn line(&self, index: usize) -> Option<&str> {
+        let end = *self.ends.get(index)?;
+        let start = if index == 0 { 0 } else { self.ends[index - 1] };
+        Some(&self.text[start..end])
+    }
+
+    pub fn lines(&self) -> impl '_ + ExactSizeIterator<Item = &str> {
+        (0..self.ends.len()).map(move |index| self.line(index).unwrap())
+    }
+
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.ends.clear();
+    }
+}
+
+impl<'t> Extend<&'t str> for LineBuffer {
+    fn extend<I>(&mut self, lines: I)
+    where
+        I: IntoIterator<Item = &'t str>,
+    {
+        for line in lines {
+            self.push(line);
         }
     }
 }
@@ -120,6 +674,21 @@ impl<C> Congruent<C>
 where
     C: Content,
 {
+    // Gets congruent content or the content and error if the content is not congruent.
+    pub(crate) fn try_new(left: C, right: C) -> Result<Self, (CongruenceError, C, C)> {
+        let (m, n) = (left.width(), right.width());
+        if m == n {
+            Ok(Congruent { left, right })
+        }
+        else {
+            Err((
+                CongruenceError::WidthMismatch { left: m, right: n },
+                left,
+                right,
+            ))
+        }
+    }
+
     pub fn into_left_right(self) -> (C, C) {
         self.into()
     }
@@ -147,12 +716,10 @@ impl<C> TryFrom<(C, C)> for Congruent<C>
 where
     C: Content,
 {
-    type Error = ();
+    type Error = CongruenceError;
 
     fn try_from((left, right): (C, C)) -> Result<Self, Self::Error> {
-        (left.width() == right.width())
-            .then(|| Congruent { left, right })
-            .ok_or(())
+        Congruent::try_new(left, right).map_err(|(error, _, _)| error)
     }
 }
 
@@ -178,7 +745,109 @@ pub trait Content: Clone + Debug + Sized + Render {
     #[must_use]
     fn truncate(self, width: usize) -> Self;
 
-    fn into_lines(self) -> Vec<Self>;
+    /// Removes the first `width` graphemes.
+    ///
+    /// By default, the remaining graphemes are constructed with `grapheme`, so content with
+    /// attributes other than its graphemes, such as styles, implements this.
+    #[must_use]
+    fn skip(self, width: usize) -> Self {
+        self.to_graphemes()
+            .into_iter()
+            .skip(width)
+            .fold(Self::empty(), |output, glyph| {
+                Self::concatenate(output, Self::grapheme(glyph))
+            })
+    }
+
+    /// Repeats each grapheme `n` times.
+    ///
+    /// By default, each grapheme is constructed with `grapheme`, so content with attributes
+    /// other than its graphemes, such as styles, implements this.
+    #[must_use]
+    fn stretch(self, n: usize) -> Self {
+        self.to_graphemes()
+            .into_iter()
+            .fold(Self::empty(), |output, glyph| {
+                Self::concatenate(output, Self::grapheme(glyph).repeat(n))
+            })
+    }
+
+    /// Truncates the content to at most `width` columns.
+    ///
+    /// Returns the content and its width or, if the policy is `Fail` and a grapheme would be
+    /// split, the content unchanged. If an ellipsis is wider than `width`, then the content is
+    /// clipped without an ellipsis.
+    fn truncate_to_width_with(
+        self,
+        width: usize,
+        overflow: OverflowPolicy,
+    ) -> Result<(Self, usize), Self> {
+        // Gets the number of graphemes and their total width that fit within the given width.
+        fn fit(graphemes: &[Grapheme], width: usize) -> (usize, usize) {
+            let mut sum = 0usize;
+            let n = graphemes
+                .iter()
+                .take_while(|glyph| {
+                    let has_capacity = sum + glyph.width() <= width;
+                    if has_capacity {
+                        sum += glyph.width();
+                    }
+                    has_capacity
+                })
+                .count();
+            (n, sum)
+        }
+
+        let total = self.width();
+        if total <= width {
+            return Ok((self, total));
+        }
+        match overflow {
+            OverflowPolicy::Clip => {
+                let (n, sum) = fit(&self.to_graphemes(), width);
+                Ok((self.truncate(n), sum))
+            }
+            OverflowPolicy::Ellipsis(glyph) if glyph.width() > width => {
+                let (n, sum) = fit(&self.to_graphemes(), width);
+                Ok((self.truncate(n), sum))
+            }
+            OverflowPolicy::Ellipsis(glyph) => {
+                let ellipsis = glyph.width();
+                let (n, sum) = fit(&self.to_graphemes(), width.saturating_sub(ellipsis));
+                Ok((
+                    Self::concatenate(self.truncate(n), Self::grapheme(glyph)),
+                    sum + ellipsis,
+                ))
+            }
+            OverflowPolicy::Fail => {
+                let (n, sum) = fit(&self.to_graphemes(), width);
+                if sum < width {
+                    Err(self)
+                }
+                else {
+                    Ok((self.truncate(n), sum))
+                }
+            }
+        }
+    }
+
+    /// Gets the graphemes of the content.
+    ///
+    /// By default, the graphemes are copied from the content rendered without styles.
+    fn to_graphemes(&self) -> Vec<Grapheme<'_>> {
+        graphemes(&self.render_with(&RenderContext::PLAIN))
+            .map(|glyph| Grapheme::opaque(glyph.to_owned().into()))
+            .collect()
+    }
+
+    #[must_use]
+    fn sanitize(self, policy: &SanitizePolicy) -> Self;
+
+    fn into_lines(self) -> Vec<Self> {
+        self.into_lines_with(LineBreakPolicy::default())
+    }
+
+    fn into_lines_with(self, policy: LineBreakPolicy) -> Vec<Self>;
 
     #[must_use]
     fn concatenate(left: Self, right: Self) -> Self;
@@ -202,29 +871,58 @@ impl<'t> Content for Cow<'t, str> {
     }
 
     fn space() -> Self {
-        Grapheme::SPACE.0.clone()
+        Grapheme::SPACE.text.clone()
     }
 
     fn repeat(self, n: usize) -> Self {
         self.as_ref().repeat(n).into()
     }
 
+    fn stretch(self, n: usize) -> Self {
+        stretch(&self, n).into()
+    }
+
+    // Borrowed text is sliced rather than copied.
     fn truncate(self, width: usize) -> Self {
-        self.graphemes(true)
-            .take(width)
-            .fold(String::new(), |mut output, glyph| {
-                output.push_str(glyph);
-                output
-            })
-            .into()
+        match self {
+            Cow::Borrowed(text) => Cow::Borrowed(&text[..grapheme_offset(text, width)]),
+            Cow::Owned(mut text) => {
+                let offset = grapheme_offset(&text, width);
+                String::truncate(&mut text, offset);
+                Cow::Owned(text)
+            }
+        }
     }
 
-    fn into_lines(self) -> Vec<Self> {
-        self.lines()
-            .map(From::from)
-            .map(Cow::into_owned)
-            .map(From::from)
-            .collect()
+    fn skip(self, width: usize) -> Self {
+        match self {
+            Cow::Borrowed(text) => Cow::Borrowed(&text[grapheme_offset(text, width)..]),
+            Cow::Owned(mut text) => {
+                let offset = grapheme_offset(&text, width);
+                text.drain(..offset);
+                Cow::Owned(text)
+            }
+        }
+    }
+
+    fn to_graphemes(&self) -> Vec<Grapheme<'_>> {
+        graphemes(self.as_ref()).map(Grapheme::unchecked).collect()
+    }
+
+    fn sanitize(self, policy: &SanitizePolicy) -> Self {
+        policy.sanitize(self.as_ref(), &mut 0).into()
+    }
+
+    // Lines of borrowed text are borrowed from the same text.
+    fn into_lines_with(self, policy: LineBreakPolicy) -> Vec<Self> {
+        match self {
+            Cow::Borrowed(text) => policy.lines(text).into_iter().map(Cow::Borrowed).collect(),
+            Cow::Owned(text) => policy
+                .lines(&text)
+                .into_iter()
+                .map(|line| Cow::Owned(line.to_owned()))
+                .collect(),
+        }
     }
 
     fn concatenate(left: Self, right: Self) -> Self {
@@ -236,9 +934,8 @@ impl<'t> Content for Cow<'t, str> {
         mut f: impl FnMut(&Grapheme, &Grapheme) -> Layer,
     ) -> Self {
         let (front, back) = content.into();
-        front
-            .graphemes(true)
-            .zip(back.graphemes(true))
+        graphemes(&front)
+            .zip(graphemes(&back))
             .map(
                 |(front, back)| match f(&Grapheme::unchecked(front), &Grapheme::unchecked(back)) {
                     Layer::Front(_) => front,
@@ -249,7 +946,7 @@ impl<'t> Content for Cow<'t, str> {
     }
 
     fn width(&self) -> usize {
-        <str as UnicodeWidth>::width(self)
+        str_width(self)
     }
 }
 
@@ -266,8 +963,12 @@ impl Content for String {
         str::repeat(&self, n)
     }
 
+    fn stretch(self, n: usize) -> Self {
+        stretch(&self, n)
+    }
+
     fn truncate(self, width: usize) -> Self {
-        self.graphemes(true)
+        graphemes(&self)
             .take(width)
             .fold(String::new(), |mut output, glyph| {
                 output.push_str(glyph);
@@ -275,8 +976,25 @@ impl Content for String {
             })
     }
 
-    fn into_lines(self) -> Vec<Self> {
-        self.lines().map(From::from).collect()
+    fn skip(self, width: usize) -> Self {
+        graphemes(&self)
+            .skip(width)
+            .fold(String::new(), |mut output, glyph| {
+                output.push_str(glyph);
+                output
+            })
+    }
+
+    fn to_graphemes(&self) -> Vec<Grapheme<'_>> {
+        graphemes(self.as_str()).map(Grapheme::unchecked).collect()
+    }
+
+    fn sanitize(self, policy: &SanitizePolicy) -> Self {
+        policy.sanitize(&self, &mut 0)
+    }
+
+    fn into_lines_with(self, policy: LineBreakPolicy) -> Vec<Self> {
+        policy.lines(&self).into_iter().map(From::from).collect()
     }
 
     fn concatenate(left: Self, right: Self) -> Self {
@@ -288,9 +1006,8 @@ impl Content for String {
         mut f: impl FnMut(&Grapheme, &Grapheme) -> Layer,
     ) -> Self {
         let (front, back) = content.into();
-        front
-            .graphemes(true)
-            .zip(back.graphemes(true))
+        graphemes(&front)
+            .zip(graphemes(&back))
             .map(
                 |(front, back)| match f(&Grapheme::unchecked(front), &Grapheme::unchecked(back)) {
                     Layer::Front(_) => front,
@@ -301,28 +1018,550 @@ impl Content for String {
     }
 
     fn width(&self) -> usize {
-        <str as UnicodeWidth>::width(self)
+        str_width(self)
     }
 }
 
-pub trait Style: Clone + Debug {
-    fn apply<'t>(&self, text: &'t str) -> Cow<'t, str>;
-}
+macro_rules! impl_content_for_shared_str {
+    ($t:ty) => {
+        impl Content for $t {
+            fn empty() -> Self {
+                "".into()
+            }
 
-impl Style for () {
-    fn apply<'t>(&self, text: &'t str) -> Cow<'t, str> {
-        text.into()
-    }
+            fn grapheme(glyph: Grapheme) -> Self {
+                glyph.get().into()
+            }
+
+            fn repeat(self, n: usize) -> Self {
+                self.as_ref().repeat(n).into()
+            }
+
+            fn stretch(self, n: usize) -> Self {
+                stretch(&self, n).into()
+            }
+
+            fn truncate(self, width: usize) -> Self {
+                String::from(self.as_ref()).truncate(width).into()
+            }
+
+            fn skip(self, width: usize) -> Self {
+                String::from(self.as_ref()).skip(width).into()
+            }
+
+            fn to_graphemes(&self) -> Vec<Grapheme<'_>> {
+                graphemes(self.as_ref()).map(Grapheme::unchecked).collect()
+            }
+
+            fn sanitize(self, policy: &SanitizePolicy) -> Self {
+                policy.sanitize(self.as_ref(), &mut 0).into()
+            }
+
+            fn into_lines_with(self, policy: LineBreakPolicy) -> Vec<Self> {
+                policy
+                    .lines(self.as_ref())
+                    .into_iter()
+                    .map(From::from)
+                    .collect()
+            }
+
+            fn concatenate(left: Self, right: Self) -> Self {
+                format!("{}{}", left, right).into()
+            }
+
+            fn overlay_with(
+                content: Congruent<Self>,
+                mut f: impl FnMut(&Grapheme, &Grapheme) -> Layer,
+            ) -> Self {
+                let (front, back) = content.into();
+                graphemes(&front)
+                    .zip(graphemes(&back))
+                    .map(|(front, back)| {
+                        match f(&Grapheme::unchecked(front), &Grapheme::unchecked(back)) {
+                            Layer::Front(_) => front,
+                            Layer::Back(_) => back,
+                        }
+                    })
+                    .collect::<String>()
+                    .into()
+            }
+
+            fn width(&self) -> usize {
+                str_width(self)
+            }
+        }
+    };
+}
+impl_content_for_shared_str!(Rc<str>);
+impl_content_for_shared_str!(Arc<str>);
+
+/// Content stored as pre-segmented graphemes with cached widths.
+///
+/// Text is segmented once on construction. Truncating, skipping, indexing, and overlaying then
+/// operate on the stored grapheme boundaries rather than segmenting the text again, which is
+/// useful when the same content is sliced and composed many times.
+///
+/// Cached widths are measured again if width settings, such as `AmbiguousWidth`, change.
+///
+/// Unlike string content, cell buffers retain `Grapheme::TRANSPARENT` cells, which are rendered
+/// as spaces.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CellBuffer {
+    text: String,
+    cells: Vec<CellExtent>,
+    width: CachedWidth,
+}
+
+// The byte offset and width of a grapheme in the text of a `CellBuffer`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct CellExtent {
+    offset: usize,
+    width: usize,
+    is_transparent: bool,
+}
+
+impl CellBuffer {
+    /// Constructs content with `n` transparent cells.
+    pub fn transparent(n: usize) -> Self {
+        CellBuffer::grapheme(Grapheme::TRANSPARENT).repeat(n)
+    }
+
+    /// Gets the number of graphemes.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Gets the grapheme at the given index.
+    pub fn get(&self, index: usize) -> Option<Grapheme<'_>> {
+        (index < self.cells.len()).then(|| self.grapheme_at(index))
+    }
+
+    /// Gets the width of the grapheme at the given index.
+    pub fn width_at(&self, index: usize) -> Option<usize> {
+        (index < self.cells.len()).then(|| {
+            if self.width.is_current() {
+                self.cells[index].width
+            }
+            else {
+                self.grapheme_at(index).width()
+            }
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.text.as_str()
+    }
+
+    pub fn into_string(self) -> String {
+        self.text
+    }
+
+    fn grapheme_at(&self, index: usize) -> Grapheme<'_> {
+        if self.cells[index].is_transparent {
+            Grapheme::TRANSPARENT
+        }
+        else {
+            Grapheme::unchecked(self.slice(index))
+        }
+    }
+
+    fn slice(&self, index: usize) -> &str {
+        let start = self.cells[index].offset;
+        let end = self.offset(index + 1);
+        &self.text[start..end]
+    }
+
+    fn offset(&self, index: usize) -> usize {
+        self.cells
+            .get(index)
+            .map_or(self.text.len(), |cell| cell.offset)
+    }
+
+    // Measures the cells again if width settings have changed since they were measured, so that
+    // their widths can be combined with widths measured now.
+    fn refresh(mut self) -> Self {
+        if !self.width.is_current() {
+            for index in 0..self.cells.len() {
+                self.cells[index].width = self.grapheme_at(index).width();
+            }
+            self.width = CachedWidth::new(self.sum());
+        }
+        self
+    }
+
+    fn push(&mut self, glyph: &Grapheme, width: usize) {
+        self.cells.push(CellExtent {
+            offset: self.text.len(),
+            width,
+            is_transparent: glyph.is_transparent(),
+        });
+        self.text.push_str(glyph.get());
+        self.width = CachedWidth::new(self.width() + width);
+    }
+
+    fn sum(&self) -> usize {
+        self.cells.iter().map(|cell| cell.width).sum()
+    }
+
+    fn extend(&mut self, cells: impl IntoIterator<Item = CellExtent>, text: &str) {
+        let length = self.text.len();
+        self.text.push_str(text);
+        self.cells.extend(cells.into_iter().map(|cell| CellExtent {
+            offset: cell.offset + length,
+            ..cell
+        }));
+    }
+}
+
+impl AsRef<str> for CellBuffer {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
 }
 
-// TODO: Consider using `Option<S>` instead of requiring `Default`.
+impl Content for CellBuffer {
+    fn empty() -> Self {
+        CellBuffer::default()
+    }
+
+    fn grapheme(glyph: Grapheme) -> Self {
+        let mut buffer = CellBuffer::default();
+        buffer.push(&glyph, glyph.width());
+        buffer
+    }
+
+    fn repeat(self, n: usize) -> Self {
+        let source = self.refresh();
+        let mut buffer = CellBuffer {
+            text: String::with_capacity(source.text.len() * n),
+            cells: Vec::with_capacity(source.cells.len() * n),
+            width: CachedWidth::new(source.width() * n),
+        };
+        for _ in 0..n {
+            buffer.extend(source.cells.iter().copied(), &source.text);
+        }
+        buffer
+    }
+
+    fn truncate(self, width: usize) -> Self {
+        let mut buffer = self.refresh();
+        if width < buffer.cells.len() {
+            let offset = buffer.offset(width);
+            String::truncate(&mut buffer.text, offset);
+            Vec::truncate(&mut buffer.cells, width);
+            buffer.width = CachedWidth::new(buffer.sum());
+        }
+        buffer
+    }
+
+    fn skip(self, width: usize) -> Self {
+        let mut buffer = self.refresh();
+        let n = cmp::min(width, buffer.cells.len());
+        let offset = buffer.offset(n);
+        buffer.text.drain(..offset);
+        buffer.cells.drain(..n);
+        for cell in buffer.cells.iter_mut() {
+            cell.offset -= offset;
+        }
+        buffer.width = CachedWidth::new(buffer.sum());
+        buffer
+    }
+
+    fn to_graphemes(&self) -> Vec<Grapheme<'_>> {
+        (0..self.cells.len())
+            .map(|index| self.grapheme_at(index))
+            .collect()
+    }
+
+    // Sanitizing operates on text, so transparent cells become spaces.
+    fn sanitize(self, policy: &SanitizePolicy) -> Self {
+        policy.sanitize(&self.text, &mut 0).into()
+    }
+
+    fn into_lines_with(self, policy: LineBreakPolicy) -> Vec<Self> {
+        let source = self.refresh();
+        // Lines are slices of the text, so their cells are found by byte offset.
+        policy
+            .lines(&source.text)
+            .into_iter()
+            .map(|line| {
+                let start = line.as_ptr() as usize - source.text.as_ptr() as usize;
+                let end = start + line.len();
+                let cells = &source.cells[source.cells.partition_point(|cell| cell.offset < start)
+                    ..source.cells.partition_point(|cell| cell.offset < end)];
+                let mut buffer = CellBuffer {
+                    width: CachedWidth::new(cells.iter().map(|cell| cell.width).sum()),
+                    ..CellBuffer::default()
+                };
+                buffer.extend(
+                    cells.iter().map(|cell| CellExtent {
+                        offset: cell.offset - start,
+                        ..*cell
+                    }),
+                    line,
+                );
+                buffer
+            })
+            .collect()
+    }
+
+    fn concatenate(left: Self, right: Self) -> Self {
+        let (mut left, right) = (left.refresh(), right.refresh());
+        let width = left.width() + right.width();
+        left.extend(right.cells, &right.text);
+        left.width = CachedWidth::new(width);
+        left
+    }
+
+    // Cells are overlaid column by column rather than index by index, so a wide cell may cover
+    // several narrow cells. The columns of a wide cell that is only partially covered are replaced
+    // by spaces.
+    fn overlay_with(
+        content: Congruent<Self>,
+        mut f: impl FnMut(&Grapheme, &Grapheme) -> Layer,
+    ) -> Self {
+        let (front, back) = content.into();
+        let (front, back) = (front.refresh(), back.refresh());
+        let (mut front, mut back) = (Columns::new(&front), Columns::new(&back));
+        let mut output = CellBuffer::default();
+        let mut column = 0;
+        loop {
+            front.seek(column);
+            back.seek(column);
+            let (glyph, width) = match (front.get(column), back.get(column)) {
+                (Some(glyph), Some(other)) => match f(&glyph.0, &other.0) {
+                    Layer::Front(_) => front.take(glyph),
+                    Layer::Back(_) => back.take(other),
+                },
+                // Only zero-width cells remain once either side is exhausted.
+                (Some(glyph), None) => front.take(glyph),
+                (None, Some(glyph)) => back.take(glyph),
+                (None, None) => {
+                    break;
+                }
+            };
+            output.push(&glyph, width);
+            column += width;
+        }
+        output
+    }
+
+    fn width(&self) -> usize {
+        self.width.get_or_measure(|| {
+            (0..self.len())
+                .map(|index| self.grapheme_at(index).width())
+                .sum()
+        })
+    }
+}
+
+// A cursor over the cells of a `CellBuffer` by column.
+struct Columns<'b> {
+    buffer: &'b CellBuffer,
+    index: usize,
+    // The column at which the cell at `index` begins.
+    column: usize,
+}
+
+impl<'b> Columns<'b> {
+    fn new(buffer: &'b CellBuffer) -> Self {
+        Columns {
+            buffer,
+            index: 0,
+            column: 0,
+        }
+    }
+
+    // Skips cells that begin before the given column, such as those covered by a wide cell.
+    fn seek(&mut self, column: usize) {
+        while self.index < self.buffer.len() && self.column < column {
+            self.column += self.buffer.cells[self.index].width;
+            self.index += 1;
+        }
+    }
+
+    // Gets the cell that begins at the given column and whether it is stored in the buffer. The
+    // remaining columns of a cell that has been skipped are spaces.
+    fn get(&self, column: usize) -> Option<(Grapheme<'b>, usize, bool)> {
+        if self.column > column {
+            Some((Grapheme::SPACE, 1, false))
+        }
+        else if self.index < self.buffer.len() {
+            let width = self.buffer.cells[self.index].width;
+            Some((self.buffer.grapheme_at(self.index), width, true))
+        }
+        else {
+            None
+        }
+    }
+
+    fn take(
+        &mut self,
+        (glyph, width, is_stored): (Grapheme<'b>, usize, bool),
+    ) -> (Grapheme<'b>, usize) {
+        if is_stored {
+            self.column += width;
+            self.index += 1;
+        }
+        (glyph, width)
+    }
+}
+
+impl<'t> From<&'t str> for CellBuffer {
+    fn from(text: &'t str) -> Self {
+        let mut buffer = CellBuffer {
+            text: String::with_capacity(text.len()),
+            ..CellBuffer::default()
+        };
+        for glyph in graphemes(text) {
+            buffer.push(&Grapheme::unchecked(glyph), str_width(glyph));
+        }
+        buffer
+    }
+}
+
+impl From<String> for CellBuffer {
+    fn from(text: String) -> Self {
+        CellBuffer::from(text.as_str())
+    }
+}
+
+impl<'t> From<Cow<'t, str>> for CellBuffer {
+    fn from(text: Cow<'t, str>) -> Self {
+        CellBuffer::from(text.as_ref())
+    }
+}
+
+impl From<CellBuffer> for String {
+    fn from(buffer: CellBuffer) -> Self {
+        buffer.text
+    }
+}
+
+impl From<CellBuffer> for Rc<str> {
+    fn from(buffer: CellBuffer) -> Self {
+        buffer.text.into()
+    }
+}
+
+impl From<CellBuffer> for Arc<str> {
+    fn from(buffer: CellBuffer) -> Self {
+        buffer.text.into()
+    }
+}
+
+/// A style applied to fragments of text.
+///
+/// Styles are emitted as a prefix and suffix written around text, such as escape sequences that
+/// set and reset attributes of a terminal. Styles that instead transform text implement `apply`.
+pub trait Style: Clone + Debug {
+    fn write_prefix(&self, _: &mut impl Write) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_suffix(&self, _: &mut impl Write) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Writes the prefix in the given context.
+    ///
+    /// By default, the prefix is written only if the context is styled. Styles that depend on the
+    /// color depth of the target implement this.
+    fn write_prefix_with(
+        &self,
+        target: &mut impl Write,
+        context: &RenderContext,
+    ) -> io::Result<()> {
+        if context.is_styled {
+            self.write_prefix(target)
+        }
+        else {
+            Ok(())
+        }
+    }
+
+    /// Writes the suffix in the given context.
+    ///
+    /// By default, the suffix is written only if the context is styled.
+    fn write_suffix_with(
+        &self,
+        target: &mut impl Write,
+        context: &RenderContext,
+    ) -> io::Result<()> {
+        if context.is_styled {
+            self.write_suffix(target)
+        }
+        else {
+            Ok(())
+        }
+    }
+
+    /// Writes the transition from this style to the style of the next fragment in the given
+    /// context.
+    ///
+    /// The next style is `None` if the next fragment is plain or if there are no more fragments.
+    /// By default, the suffix of this style and then the prefix of the next style are written.
+    /// Styles that write escape sequences can instead write only the attributes that change.
+    fn write_transition_with(
+        &self,
+        next: Option<&Self>,
+        target: &mut impl Write,
+        context: &RenderContext,
+    ) -> io::Result<()> {
+        self.write_suffix_with(target, context)?;
+        match next {
+            Some(next) => next.write_prefix_with(target, context),
+            None => Ok(()),
+        }
+    }
+
+    /// Transforms text written between the prefix and suffix.
+    ///
+    /// By default, text is borrowed and unchanged.
+    fn apply<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        text.into()
+    }
+
+    /// Composes the style over another style.
+    ///
+    /// Attributes that are set in `self` take precedence and attributes that are not set are
+    /// retained from `over`. This is used when restyling and overlaying styled content, so, for
+    /// example, applying a bold style over a colored style may retain the color. By default, the
+    /// style replaces `over` entirely.
+    #[must_use]
+    fn compose(&self, over: &Self) -> Self {
+        let _ = over;
+        self.clone()
+    }
+}
+
+impl Style for () {}
+
+pub trait Interpolate: Sized {
+    #[must_use]
+    fn interpolate(&self, to: &Self, t: f64) -> Self;
+}
+
+impl Interpolate for () {
+    fn interpolate(&self, _: &Self, _: f64) -> Self {}
+}
+
+/// Content composed of fragments with styles.
+///
+/// Fragments may be plain and have no style. Plain fragments are written without a prefix or
+/// suffix and content that is inserted by layout operations, such as padding, is plain.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Styled<C = String, S = ()>
 where
     C: AsRef<str> + Content + From<String>,
     S: Style,
 {
-    fragments: Vec<(S, C)>,
+    fragments: Vec<(Option<S>, C)>,
+    width: CachedWidth,
 }
 
 impl<C, S> Styled<C, S>
@@ -330,75 +1569,363 @@ where
     C: AsRef<str> + Content + From<String>,
     S: Style,
 {
+    fn from_fragments(fragments: Vec<(Option<S>, C)>) -> Self {
+        let width = CachedWidth::new(measure(&fragments));
+        Styled { fragments, width }
+    }
+
     pub fn new(style: S, content: impl Into<C>) -> Self {
+        Styled::from_fragments(vec![(Some(style), content.into())])
+    }
+
+    /// Constructs content without a style.
+    pub fn plain(content: impl Into<C>) -> Self {
+        Styled::from_fragments(vec![(None, content.into())])
+    }
+
+    /// Composes the given style over the style of each fragment.
+    ///
+    /// See `Style::compose`.
+    #[must_use]
+    pub fn restyle(self, style: S) -> Self
+    where
+        S: PartialEq,
+    {
+        Styled::from_fragments(
+            self.fragments
+                .into_iter()
+                .map(|(fragment, content)| (Some(compose(&style, fragment.as_ref())), content))
+                .collect(),
+        )
+        .coalesce()
+    }
+
+    /// Merges adjacent fragments with equal styles and drops empty fragments.
+    #[must_use]
+    pub fn coalesce(self) -> Self
+    where
+        S: PartialEq,
+    {
         Styled {
-            fragments: vec![(style, content.into())],
+            width: self.width,
+            fragments: self
+                .fragments
+                .into_iter()
+                .filter(|(_, content)| !content.as_ref().is_empty())
+                .coalesce(|(left, previous), (right, next)| {
+                    if left == right {
+                        Ok((left, C::concatenate(previous, next)))
+                    }
+                    else {
+                        Err(((left, previous), (right, next)))
+                    }
+                })
+                .collect(),
         }
     }
 
     #[must_use]
-    pub fn restyle(self, style: S) -> Self {
-        Styled {
-            fragments: vec![(
-                style,
-                self.fragments
-                    .into_iter()
-                    .map(|(_, content)| content)
-                    .fold(C::empty(), |output, content| {
-                        C::concatenate(output, content)
-                    }),
-            )],
+    pub fn restyle_with(self, mut f: impl FnMut(usize) -> S) -> Self {
+        let mut column = 0usize;
+        Styled::from_fragments(
+            self.fragments
+                .iter()
+                .flat_map(|(fragment, content)| {
+                    graphemes(content.as_ref()).map(move |glyph| (fragment, glyph))
+                })
+                .map(|(fragment, glyph)| {
+                    let style = Some(compose(&f(column), fragment.as_ref()));
+                    column += str_width(glyph);
+                    (style, C::from(glyph.to_owned()))
+                })
+                .collect(),
+        )
+    }
+
+    /// Restyles the graphemes at the columns for which the given predicate is `true`.
+    ///
+    /// Other graphemes retain their style.
+    #[must_use]
+    pub fn restyle_selected(self, style: S, mut f: impl FnMut(usize) -> bool) -> Self
+    where
+        S: PartialEq,
+    {
+        let mut column = 0usize;
+        Styled::from_fragments(
+            self.fragments
+                .iter()
+                .flat_map(|(fragment, content)| {
+                    graphemes(content.as_ref()).map(move |glyph| (fragment, glyph))
+                })
+                .map(|(fragment, glyph)| {
+                    let style = if f(column) {
+                        Some(compose(&style, fragment.as_ref()))
+                    }
+                    else {
+                        fragment.clone()
+                    };
+                    column += str_width(glyph);
+                    (style, C::from(glyph.to_owned()))
+                })
+                .collect(),
+        )
+        .coalesce()
+    }
+
+    /// Gets an iterator over the graphemes of the content and their styles.
+    pub fn styled_graphemes(&self) -> impl '_ + Iterator<Item = (Grapheme<'_>, Option<&'_ S>)> {
+        self.fragments.iter().flat_map(|(style, content)| {
+            graphemes(content.as_ref())
+                .map(move |glyph| (Grapheme::unchecked(glyph), style.as_ref()))
+        })
+    }
+
+    /// Gets an iterator over the styled fragments of the content.
+    ///
+    /// Spans are yielded in order with the byte offset and column at which they begin in the
+    /// unstyled text of the content. Empty fragments are not yielded.
+    pub fn spans(&self) -> impl '_ + Iterator<Item = Span<'_, S>> {
+        let (mut offset, mut column) = (0usize, 0usize);
+        self.fragments
+            .iter()
+            .filter(|(_, content)| !content.as_ref().is_empty())
+            .map(move |(style, content)| {
+                let text = content.as_ref();
+                let span = Span {
+                    style: style.as_ref(),
+                    text,
+                    offset,
+                    column,
+                };
+                offset += text.len();
+                column += str_width(text);
+                span
+            })
+    }
+
+    /// Overlays content and resolves both the grapheme and style of each cell.
+    ///
+    /// The function receives the graphemes and styles of the front and back content and selects
+    /// the layer from which the grapheme is taken along with its style. Unlike `overlay_with`,
+    /// the style need not be the style of the selected grapheme, so styles can be merged.
+    pub fn overlay_resolved(
+        content: Congruent<Self>,
+        mut f: impl FnMut((&Grapheme, Option<&S>), (&Grapheme, Option<&S>)) -> Resolved<S>,
+    ) -> Self
+    where
+        S: PartialEq,
+    {
+        let (front, back) = content.into();
+        let mut builder = StyledBuilder::new();
+        for ((front, front_style), (back, back_style)) in
+            front.styled_graphemes().zip(back.styled_graphemes())
+        {
+            let Resolved { layer, style } = f((&front, front_style), (&back, back_style));
+            let glyph = match layer {
+                Layer::Front(_) => front,
+                Layer::Back(_) => back,
+            };
+            builder.push(style, glyph.get());
         }
+        builder.finish()
     }
 
-    fn fragment_indexed_graphemes<'i>(&'i self) -> impl 'i + Iterator<Item = (usize, Grapheme)> {
+    fn fragment_indexed_graphemes<'i>(
+        &'i self,
+    ) -> impl 'i + Iterator<Item = (usize, Grapheme<'i>)> {
         self.fragments
             .iter()
             .enumerate()
             .flat_map(|(index, (_, content))| {
-                content
-                    .as_ref()
-                    .graphemes(true)
-                    .map(move |point| (index, Grapheme::unchecked(point)))
+                graphemes(content.as_ref()).map(move |point| (index, Grapheme::unchecked(point)))
             })
     }
 }
 
+impl<C, S, T> FromIterator<(S, T)> for Styled<C, S>
+where
+    C: AsRef<str> + Content + From<String>,
+    S: PartialEq + Style,
+    T: Into<C>,
+{
+    fn from_iter<I>(fragments: I) -> Self
+    where
+        I: IntoIterator<Item = (S, T)>,
+    {
+        Styled::from_fragments(
+            fragments
+                .into_iter()
+                .map(|(style, content)| (Some(style), content.into()))
+                .collect(),
+        )
+        .coalesce()
+    }
+}
+
+/// Incrementally constructs `Styled` content.
+///
+/// Text pushed with the same style as the previous fragment is appended to that fragment, so
+/// lines can be assembled piecewise without concatenating intermediate `Styled` values.
+#[derive(Clone, Debug)]
+pub struct StyledBuilder<C = String, S = ()>
+where
+    C: AsRef<str> + Content + From<String>,
+    S: Style,
+{
+    fragments: Vec<(Option<S>, String)>,
+    phantom: PhantomData<fn() -> C>,
+}
+
+impl<C, S> StyledBuilder<C, S>
+where
+    C: AsRef<str> + Content + From<String>,
+    S: PartialEq + Style,
+{
+    pub fn new() -> Self {
+        StyledBuilder {
+            fragments: vec![],
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn push_str(&mut self, style: S, text: &str) -> &mut Self {
+        self.push(Some(style), text)
+    }
+
+    pub fn push_plain(&mut self, text: &str) -> &mut Self {
+        self.push(None, text)
+    }
+
+    pub fn push_grapheme(&mut self, style: S, glyph: Grapheme) -> &mut Self {
+        self.push(Some(style), glyph.get())
+    }
+
+    pub fn finish(self) -> Styled<C, S> {
+        Styled::from_fragments(
+            self.fragments
+                .into_iter()
+                .map(|(style, text)| (style, C::from(text)))
+                .collect(),
+        )
+    }
+
+    fn push(&mut self, style: Option<S>, text: &str) -> &mut Self {
+        if !text.is_empty() {
+            match self.fragments.last_mut() {
+                Some((last, fragment)) if *last == style => fragment.push_str(text),
+                _ => self.fragments.push((style, text.to_owned())),
+            }
+        }
+        self
+    }
+}
+
+impl<C, S> Default for StyledBuilder<C, S>
+where
+    C: AsRef<str> + Content + From<String>,
+    S: PartialEq + Style,
+{
+    fn default() -> Self {
+        StyledBuilder::new()
+    }
+}
+
+/// The grapheme and style of a cell resolved from overlayed `Styled` content.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Resolved<S> {
+    /// The layer from which the grapheme is taken.
+    pub layer: Layer,
+    pub style: Option<S>,
+}
+
+impl<S> Resolved<S> {
+    pub fn new(layer: Layer, style: Option<S>) -> Self {
+        Resolved { layer, style }
+    }
+}
+
+/// A fragment of text in `Styled` content.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Span<'t, S> {
+    /// The style of the span or `None` if the span is plain.
+    pub style: Option<&'t S>,
+    pub text: &'t str,
+    /// The byte offset of the span in the unstyled text.
+    pub offset: usize,
+    /// The column of the span in the unstyled text.
+    pub column: usize,
+}
+
+impl<'t, S> Span<'t, S> {
+    pub fn width(&self) -> usize {
+        str_width(self.text)
+    }
+}
+
+// Measures the width of the fragments of `Styled` content.
+fn measure<C, S>(fragments: &[(Option<S>, C)]) -> usize
+where
+    C: AsRef<str>,
+{
+    fragments
+        .iter()
+        .map(|(_, content)| str_width(content.as_ref()))
+        .sum()
+}
+
+// Composes a style over the style of a fragment, if any.
+fn compose<S>(style: &S, over: Option<&S>) -> S
+where
+    S: Style,
+{
+    match over {
+        Some(over) => style.compose(over),
+        None => style.clone(),
+    }
+}
+
 impl<'t, S> Styled<Cow<'t, str>, S>
 where
     S: Style,
 {
     pub fn into_owned(self) -> Styled<Cow<'static, str>, S> {
-        let Styled { fragments } = self;
-        Styled {
-            fragments: fragments
+        let Styled { fragments, .. } = self;
+        Styled::from_fragments(
+            fragments
                 .into_iter()
                 .map(|(style, content)| (style, content.into_owned().into()))
                 .collect(),
-        }
+        )
+    }
+}
+
+impl<C, S> From<String> for Styled<C, S>
+where
+    C: AsRef<str> + Content + From<String>,
+    S: Style,
+{
+    fn from(text: String) -> Self {
+        Styled::plain(text)
     }
 }
 
 impl<C, S> Content for Styled<C, S>
 where
     C: AsRef<str> + Content + From<String>,
-    S: Default + Style,
+    S: PartialEq + Style,
 {
     fn empty() -> Self {
-        Styled { fragments: vec![] }
+        Styled::from_fragments(vec![])
     }
 
     fn grapheme(glyph: Grapheme) -> Self {
-        Styled {
-            fragments: vec![(S::default(), C::grapheme(glyph))],
-        }
+        Styled::plain(C::grapheme(glyph))
     }
 
     fn repeat(self, n: usize) -> Self {
         let m = self.fragments.len();
-        Styled {
-            fragments: self
-                .fragments
+        Styled::from_fragments(
+            self.fragments
                 .into_iter()
                 .enumerate()
                 .cycle()
@@ -413,59 +1940,148 @@ where
                 })
                 .map(|(_, content)| content)
                 .collect(),
-        }
+        )
+    }
+
+    fn stretch(self, n: usize) -> Self {
+        Styled::from_fragments(
+            self.fragments
+                .into_iter()
+                .map(|(style, content)| (style, content.stretch(n)))
+                .collect(),
+        )
     }
 
     fn truncate(self, width: usize) -> Self {
-        let mut sum = 0usize;
-        let mut fragments: Vec<_> = self
-            .fragments
-            .into_iter()
-            .take_while(|(_, content)| {
-                let has_capacity = sum < width;
-                sum += content.width();
-                has_capacity
-            })
-            .collect();
-        let n = sum.saturating_sub(width);
-        if n > 0 {
-            if let Some(fragment) = fragments.pop().map(|(style, content)| {
-                let width = content.width() - n;
-                (style, content.truncate(width))
-            }) {
-                fragments.push(fragment);
-            }
-        }
-        Styled { fragments }
+        let mut n = width;
+        Styled::from_fragments(
+            self.fragments
+                .into_iter()
+                .map_while(|(style, content)| {
+                    if n == 0 {
+                        return None;
+                    }
+                    let m = graphemes(content.as_ref()).count();
+                    if n >= m {
+                        n -= m;
+                        Some((style, content))
+                    }
+                    else {
+                        let content = content.truncate(n);
+                        n = 0;
+                        Some((style, content))
+                    }
+                })
+                .collect(),
+        )
     }
 
-    fn into_lines(self) -> Vec<Self> {
+    fn skip(self, width: usize) -> Self {
+        let mut n = width;
+        Styled::from_fragments(
+            self.fragments
+                .into_iter()
+                .filter_map(|(style, content)| {
+                    let m = graphemes(content.as_ref()).count();
+                    if n >= m {
+                        n -= m;
+                        None
+                    }
+                    else {
+                        let content = content.skip(n);
+                        n = 0;
+                        Some((style, content))
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    fn to_graphemes(&self) -> Vec<Grapheme<'_>> {
+        self.fragment_indexed_graphemes()
+            .map(|(_, glyph)| glyph)
+            .collect()
+    }
+
+    fn sanitize(self, policy: &SanitizePolicy) -> Self {
+        let mut column = 0usize;
+        Styled::from_fragments(
+            self.fragments
+                .into_iter()
+                .map(|(style, content)| {
+                    let content = policy.sanitize(content.as_ref(), &mut column);
+                    (style, C::from(content))
+                })
+                .collect(),
+        )
+    }
+
+    fn into_lines_with(self, policy: LineBreakPolicy) -> Vec<Self> {
         let mut lines = vec![];
-        let mut line = Styled::empty();
+        let mut line = Styled::<C, S>::empty();
+        let mut is_broken = false;
         for (style, content) in self.fragments {
-            for split in content.as_ref().lines().with_position() {
+            for split in policy.split(content.as_ref()).into_iter().with_position() {
                 match split {
                     Position::Only(split) | Position::First(split) => {
                         line = Content::concatenate(
                             line,
-                            Styled::new(style.clone(), split.to_owned()),
+                            Styled::from_fragments(vec![(style.clone(), split.to_owned().into())]),
                         );
                     }
                     Position::Middle(split) | Position::Last(split) => {
                         lines.push(line);
-                        line = Styled::new(style.clone(), split.to_owned());
+                        line =
+                            Styled::from_fragments(vec![(style.clone(), split.to_owned().into())]);
+                        is_broken = true;
                     }
                 }
             }
         }
-        lines.push(line);
+        // Like `str::lines`, a trailing line break does not begin an empty line. Lines with text
+        // that has no width (e.g., U+200B) are not empty.
+        let is_empty = line
+            .fragments
+            .iter()
+            .all(|(_, content)| content.as_ref().is_empty());
+        if !(is_broken && is_empty) {
+            lines.push(line);
+        }
         lines
     }
 
-    fn concatenate(mut left: Self, mut right: Self) -> Self {
-        left.fragments.append(&mut right.fragments);
+    // Only the fragments at the boundary are merged rather than coalescing all fragments, so
+    // repeated concatenation does not revisit the fragments of `left`.
+    fn concatenate(mut left: Self, right: Self) -> Self {
+        while left
+            .fragments
+            .last()
+            .is_some_and(|(_, content)| content.as_ref().is_empty())
+        {
+            left.fragments.pop();
+        }
+        let width = CachedWidth::new(left.width() + right.width());
+        let mut fragments = right
+            .fragments
+            .into_iter()
+            .filter(|(_, content)| !content.as_ref().is_empty());
+        if let Some((style, content)) = fragments.next() {
+            match left.fragments.pop() {
+                Some((last, previous)) if last == style => {
+                    left.fragments
+                        .push((last, C::concatenate(previous, content)));
+                }
+                Some(last) => {
+                    left.fragments.push(last);
+                    left.fragments.push((style, content));
+                }
+                None => left.fragments.push((style, content)),
+            }
+        }
+        left.fragments.extend(fragments);
         Styled {
             fragments: left.fragments,
+            width,
         }
     }
 
@@ -478,30 +2094,32 @@ where
             .fragment_indexed_graphemes()
             .zip(back.fragment_indexed_graphemes())
             .map(|((i, front), (j, back))| match f(&front, &back) {
-                Layer::Front(_) => (Layer::Front(i), front),
-                Layer::Back(_) => (Layer::Back(j), back),
+                Layer::Front(_) => ((Layer::Front(i), j), front),
+                Layer::Back(_) => ((Layer::Back(j), j), back),
             })
             .group_by(|(index, _)| *index)
             .into_iter()
-            .fold(Styled::empty(), |output, (index, group)| {
+            .fold(Styled::empty(), |output, ((index, j), group)| {
                 let text: String = group
                     .into_iter()
                     .map(|(_, glyph)| glyph.get().to_owned())
                     .collect();
+                // Front styles are composed over the style of the back fragment beneath them.
                 let style = match index {
-                    Layer::Front(index) => front.fragments.get(index).unwrap().0.clone(),
-                    Layer::Back(index) => back.fragments.get(index).unwrap().0.clone(),
+                    Layer::Front(i) => front.fragments[i]
+                        .0
+                        .as_ref()
+                        .map(|style| compose(style, back.fragments[j].0.as_ref()))
+                        .or_else(|| back.fragments[j].0.clone()),
+                    Layer::Back(j) => back.fragments[j].0.clone(),
                 };
-                Content::concatenate(output, Styled::new(style, text))
+                Content::concatenate(output, Styled::from_fragments(vec![(style, text.into())]))
             });
         overlay
     }
 
     fn width(&self) -> usize {
-        self.fragments
-            .iter()
-            .map(|(_, content)| content.as_ref().width())
-            .sum()
+        self.width.get_or_measure(|| measure(&self.fragments))
     }
 }
 
@@ -522,20 +2140,82 @@ where
     S: Style,
 {
     fn render_into(&self, target: &mut impl Write) -> io::Result<()> {
-        for (style, content) in self.fragments.iter() {
-            target.write_all(style.apply(content.as_ref()).as_bytes())?;
-        }
-        Ok(())
+        self.render_into_with(target, &RenderContext::default())
     }
 
-    fn render(&self) -> Cow<str> {
-        self.fragments
-            .iter()
-            .fold(String::new(), |mut output, (style, content)| {
-                output.push_str(style.apply(content.as_ref()).as_ref());
-                output
-            })
-            .into()
+    fn render(&self) -> Cow<'_, str> {
+        self.render_with(&RenderContext::default())
+    }
+
+    fn render_into_with(&self, target: &mut impl Write, context: &RenderContext) -> io::Result<()> {
+        self.write_fragments(target, context, Err)
+    }
+
+    fn render_with(&self, context: &RenderContext) -> Cow<'_, str> {
+        // Writing into a `Vec` cannot fail, so only escapes that styles fail to write are lost.
+        let mut output = vec![];
+        let _ = self.write_fragments(&mut output, context, |_| Ok(()));
+        String::from_utf8_lossy(&output).into_owned().into()
+    }
+}
+
+impl<C, S> Styled<C, S>
+where
+    C: AsRef<str> + Content + From<String>,
+    S: Style,
+{
+    // Writes the fragments into the target.
+    //
+    // The escapes of each transition between styles are written into a buffer before the target,
+    // so if a style fails to write them, then `recover` is given the error and the escapes are
+    // dropped when it returns `Ok`.
+    fn write_fragments(
+        &self,
+        target: &mut impl Write,
+        context: &RenderContext,
+        mut recover: impl FnMut(io::Error) -> io::Result<()>,
+    ) -> io::Result<()> {
+        fn transition<S>(
+            previous: Option<&S>,
+            next: Option<&S>,
+            target: &mut impl Write,
+            context: &RenderContext,
+        ) -> io::Result<()>
+        where
+            S: Style,
+        {
+            match (previous, next) {
+                (Some(previous), next) => previous.write_transition_with(next, target, context),
+                (None, Some(next)) => next.write_prefix_with(target, context),
+                (None, None) => Ok(()),
+            }
+        }
+
+        let mut escapes = vec![];
+        let mut write_transition =
+            |previous: Option<&S>, next: Option<&S>, target: &mut dyn Write| -> io::Result<()> {
+                escapes.clear();
+                match transition(previous, next, &mut escapes, context) {
+                    Ok(()) => target.write_all(&escapes),
+                    Err(error) => recover(error),
+                }
+            };
+        // The style of the previously written fragment is tracked, so styles can write only the
+        // attributes that change between adjacent fragments.
+        let mut previous = None;
+        for (style, content) in self.fragments.iter() {
+            let text = match style {
+                Some(style) => style.apply(content.as_ref()),
+                None => content.as_ref().into(),
+            };
+            // Styles are not written around empty text.
+            if !text.is_empty() {
+                write_transition(previous, style.as_ref(), target)?;
+                target.write_all(text.as_bytes())?;
+                previous = style.as_ref();
+            }
+        }
+        write_transition(previous, None, target)
     }
 }
 
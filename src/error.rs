@@ -0,0 +1,123 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum GraphemeError {
+    Empty,
+    MultipleClusters,
+}
+
+impl Display for GraphemeError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphemeError::Empty => write!(formatter, "text contains no grapheme clusters"),
+            GraphemeError::MultipleClusters => {
+                write!(formatter, "text contains more than one grapheme cluster")
+            }
+        }
+    }
+}
+
+impl Error for GraphemeError {}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CongruenceError {
+    WidthMismatch { left: usize, right: usize },
+    LineWidthMismatch { expected: usize, actual: usize },
+}
+
+impl Display for CongruenceError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CongruenceError::WidthMismatch { left, right } => write!(
+                formatter,
+                "content widths differ: left is {} and right is {}",
+                left, right,
+            ),
+            CongruenceError::LineWidthMismatch { expected, actual } => write!(
+                formatter,
+                "line width is {} but the block width is {}",
+                actual, expected,
+            ),
+        }
+    }
+}
+
+impl Error for CongruenceError {}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FillError {
+    ZeroHeight,
+    EmptyFiller,
+}
+
+impl Display for FillError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FillError::ZeroHeight => write!(formatter, "cannot fill a block with zero height"),
+            FillError::EmptyFiller => write!(formatter, "cannot fill a block with empty content"),
+        }
+    }
+}
+
+impl Error for FillError {}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PageError {
+    ZeroContentHeight,
+}
+
+impl Display for PageError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PageError::ZeroContentHeight => {
+                write!(formatter, "page height leaves no lines for content")
+            }
+        }
+    }
+}
+
+impl Error for PageError {}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ThresholdError {
+    OutOfRange,
+    Unordered,
+}
+
+impl Display for ThresholdError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ThresholdError::OutOfRange => {
+                write!(formatter, "threshold is outside of the unit interval")
+            }
+            ThresholdError::Unordered => {
+                write!(formatter, "warn threshold exceeds crit threshold")
+            }
+        }
+    }
+}
+
+impl Error for ThresholdError {}
+
+#[cfg(feature = "figlet")]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FontError {
+    InvalidHeader,
+    MissingGlyph(char),
+}
+
+#[cfg(feature = "figlet")]
+impl Display for FontError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FontError::InvalidHeader => write!(formatter, "font has an invalid header"),
+            FontError::MissingGlyph(point) => {
+                write!(formatter, "font is missing a glyph for `{}`", point)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "figlet")]
+impl Error for FontError {}
@@ -0,0 +1,366 @@
+use std::{cmp, iter};
+
+use crate::align::valued::{self, Alignment, AxialAlignment};
+use crate::block::{Block, DynamicallyAligned as _};
+use crate::content::{str_width, Content};
+use crate::primitive::AxisVector;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Columns {
+    Count(usize),
+    FitWidth(usize),
+}
+
+pub fn columns<C>(block: Block<C>, columns: Columns, gutter: usize) -> Block<C>
+where
+    C: Content,
+{
+    let n = match columns {
+        Columns::Count(n) => n,
+        Columns::FitWidth(width) => (width + gutter) / (block.width() + gutter).max(1),
+    };
+    let n = cmp::min(cmp::max(n, 1), cmp::max(block.height(), 1));
+    // Balance the columns such that only the last column may be shorter than the others.
+    let height = (0..block.height()).step_by(n).len();
+    let (mut output, mut block) = block.split_at_height(height);
+    while block.height() > 0 {
+        let (column, remainder) = block.split_at_height(height);
+        output = output
+            .pad_at_right(gutter)
+            .join_left_to_right_at_top(column);
+        block = remainder;
+    }
+    output
+}
+
+/// Joins rows of blocks such that blocks in the same row have the same height and blocks in the
+/// same column have the same width.
+///
+/// Blocks are aligned within their cells by the given alignments. Missing cells in ragged rows are
+/// blank.
+pub fn compose_grid<C>(
+    cells: Vec<Vec<Block<C>>>,
+    horizontal: valued::HorizontalAlignment,
+    vertical: valued::VerticalAlignment,
+) -> Block<C>
+where
+    C: Content,
+{
+    let n = cells.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<_> = (0..n)
+        .map(|x| {
+            cells
+                .iter()
+                .filter_map(|row| row.get(x))
+                .map(Block::width)
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    cells
+        .into_iter()
+        .map(|row| {
+            let height = row.iter().map(Block::height).max().unwrap_or(0);
+            let mut row = row.into_iter();
+            widths
+                .iter()
+                .map(|&width| {
+                    row.next()
+                        .unwrap_or_else(Block::zero)
+                        .pad_to_length(horizontal.opposite(), width)
+                        .pad_to_length(vertical.opposite(), height)
+                })
+                .reduce(Block::join_left_to_right_at_top)
+                .unwrap_or_else(|| Block::with_height(height))
+        })
+        .reduce(Block::join_top_to_bottom_at_left)
+        .unwrap_or_else(Block::zero)
+}
+
+/// Aligns delimited columns of text using elastic tabstops.
+///
+/// Each delimiter ends a cell and cells in the same column are aligned across contiguous runs of
+/// lines that contain that column. Cells are padded to the width of the widest cell in their run
+/// plus `gap` and the last cell of each line, which is not followed by a delimiter, is not
+/// padded.
+pub fn elastic_tabstops<C>(text: &str, delimiter: char, gap: usize) -> Block<C>
+where
+    C: Content + From<String>,
+{
+    let lines: Vec<Vec<&str>> = text
+        .lines()
+        .map(|line| line.split(delimiter).collect())
+        .collect();
+    let mut widths: Vec<Vec<usize>> = lines
+        .iter()
+        .map(|cells| vec![0; cells.len().saturating_sub(1)])
+        .collect();
+    let n = widths.iter().map(Vec::len).max().unwrap_or(0);
+    for column in 0..n {
+        let mut start = 0;
+        while start < lines.len() {
+            let run = lines[start..]
+                .iter()
+                .take_while(|cells| cells.len() > column + 1)
+                .count();
+            let width = lines[start..(start + run)]
+                .iter()
+                .map(|cells| str_width(cells[column]))
+                .max()
+                .unwrap_or(0)
+                + gap;
+            for widths in &mut widths[start..(start + run)] {
+                widths[column] = width;
+            }
+            start += cmp::max(run, 1);
+        }
+    }
+    if lines.is_empty() {
+        return Block::zero();
+    }
+    let lines: Vec<_> = lines
+        .into_iter()
+        .zip(widths)
+        .map(|(cells, widths)| {
+            let mut line = String::new();
+            for (index, cell) in cells.into_iter().enumerate() {
+                line.push_str(cell);
+                if let Some(&width) = widths.get(index) {
+                    line.extend(iter::repeat_n(' ', width - str_width(cell)));
+                }
+            }
+            line
+        })
+        .collect();
+    Block::with_content(lines.join("\n"))
+}
+
+/// Joins blocks along an axis with a gap between each block.
+///
+/// Blocks are joined at the top or left by default. The `hako!` macro builds nested stacks.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Stack {
+    axis: valued::Axis,
+    alignment: Option<Alignment>,
+    gap: usize,
+    padding: usize,
+}
+
+impl Stack {
+    pub fn new(axis: valued::Axis) -> Self {
+        Stack {
+            axis,
+            alignment: None,
+            gap: 0,
+            padding: 0,
+        }
+    }
+
+    pub fn horizontal() -> Self {
+        Stack::new(valued::Axis::LeftRight)
+    }
+
+    pub fn vertical() -> Self {
+        Stack::new(valued::Axis::TopBottom)
+    }
+
+    // Alignments along the axis of the stack are ignored.
+    #[must_use]
+    pub fn align(mut self, alignment: impl Into<Alignment>) -> Self {
+        self.alignment = Some(alignment.into());
+        self
+    }
+
+    #[must_use]
+    pub fn gap(mut self, gap: usize) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    #[must_use]
+    pub fn pad(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn join<C, I>(self, blocks: I) -> Block<C>
+    where
+        C: Content,
+        I: IntoIterator<Item = Block<C>>,
+    {
+        let is_end = self.alignment.is_some_and(|alignment| match self.axis {
+            valued::Axis::LeftRight => alignment.is_bottom(),
+            valued::Axis::TopBottom => alignment.is_right(),
+        });
+        let (alignment, gap) = match (self.axis, is_end) {
+            (valued::Axis::LeftRight, false) => {
+                (AxialAlignment::LEFT_RIGHT_AT_TOP, Alignment::RIGHT)
+            }
+            (valued::Axis::LeftRight, true) => {
+                (AxialAlignment::LEFT_RIGHT_AT_BOTTOM, Alignment::RIGHT)
+            }
+            (valued::Axis::TopBottom, false) => {
+                (AxialAlignment::TOP_BOTTOM_AT_LEFT, Alignment::BOTTOM)
+            }
+            (valued::Axis::TopBottom, true) => {
+                (AxialAlignment::TOP_BOTTOM_AT_RIGHT, Alignment::BOTTOM)
+            }
+        };
+        let mut blocks = blocks.into_iter();
+        let first = blocks.next().unwrap_or_else(Block::zero);
+        let block = blocks.fold(first, |output, block| {
+            output.pad(gap, self.gap).join(alignment, block)
+        });
+        [
+            Alignment::LEFT,
+            Alignment::RIGHT,
+            Alignment::TOP,
+            Alignment::BOTTOM,
+        ]
+        .iter()
+        .fold(block, |block, side| block.pad(*side, self.padding))
+    }
+}
+
+/// Blocks placed relative to one another and overlayed.
+///
+/// Each block is placed at a position in the scene and blocks placed later are drawn in front of
+/// blocks placed earlier. Positions may be negative, in which case the scene is translated so that
+/// its leftmost and topmost blocks are drawn at the left and top.
+#[derive(Clone, Debug)]
+pub struct Scene<C>
+where
+    C: Content,
+{
+    layers: Vec<(AxisVector, Block<C>)>,
+}
+
+impl<C> Scene<C>
+where
+    C: Content,
+{
+    pub fn new() -> Self {
+        Scene { layers: vec![] }
+    }
+
+    /// Places a block with its top left corner at the given position.
+    #[must_use]
+    pub fn place(mut self, position: AxisVector, block: Block<C>) -> Self {
+        self.layers.push((position, block));
+        self
+    }
+
+    /// Places a block relative to the bottom left corner of the previously placed block.
+    ///
+    /// For example, `AxisVector::toward::<Bottom>(2)` places a block two cells below the
+    /// previously placed block, aligned at its left. The first block is placed relative to the
+    /// origin.
+    #[must_use]
+    pub fn place_along(self, vector: AxisVector, block: Block<C>) -> Self {
+        let origin = self
+            .layers
+            .last()
+            .map_or(AxisVector::ZERO, |(position, block)| {
+                *position + AxisVector::new(0, block.height() as isize)
+            });
+        self.place(origin + vector, block)
+    }
+
+    pub fn into_block(self) -> Block<C> {
+        let origin = self
+            .layers
+            .iter()
+            .fold(AxisVector::ZERO, |origin, (position, _)| {
+                AxisVector::new(
+                    cmp::min(origin.horizontal, position.horizontal),
+                    cmp::min(origin.vertical, position.vertical),
+                )
+            });
+        Block::overlay_all(
+            self.layers
+                .into_iter()
+                .rev()
+                .map(|(position, block)| block.translate(position - origin)),
+        )
+    }
+}
+
+impl<C> Default for Scene<C>
+where
+    C: Content,
+{
+    fn default() -> Self {
+        Scene::new()
+    }
+}
+
+/// Builds nested horizontal (`h`) and vertical (`v`) stacks of blocks.
+///
+/// Attributes follow the blocks of a stack after a semicolon: `gap` and `pad` take lengths and
+/// `align` takes one of `left`, `right`, `top`, or `bottom`.
+///
+/// ```rust
+/// use hako::{hako, Block};
+///
+/// let (a, b) = (<Block>::with_content("a"), <Block>::with_content("b"));
+/// let footer = <Block>::with_content("footer");
+/// let block = hako!(v [h [a, b; gap = 1], footer; align = right]);
+/// ```
+#[macro_export]
+macro_rules! hako {
+    (h [$($body:tt)*]) => {
+        $crate::__hako_stack!(@items horizontal [] [] $($body)*)
+    };
+    (v [$($body:tt)*]) => {
+        $crate::__hako_stack!(@items vertical [] [] $($body)*)
+    };
+    ($block:expr) => {
+        $block
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __hako_stack {
+    (@items $axis:ident [$($items:tt)*] []) => {
+        $crate::__hako_stack!(@build $axis [$($items)*] [])
+    };
+    (@items $axis:ident [$($items:tt)*] [] ; $($attributes:tt)*) => {
+        $crate::__hako_stack!(@build $axis [$($items)*] [$($attributes)*])
+    };
+    (@items $axis:ident [$($items:tt)*] [$($item:tt)+]) => {
+        $crate::__hako_stack!(@build $axis [$($items)* [$($item)+]] [])
+    };
+    (@items $axis:ident [$($items:tt)*] [$($item:tt)+] ; $($attributes:tt)*) => {
+        $crate::__hako_stack!(@build $axis [$($items)* [$($item)+]] [$($attributes)*])
+    };
+    (@items $axis:ident [$($items:tt)*] [$($item:tt)+] , $($rest:tt)*) => {
+        $crate::__hako_stack!(@items $axis [$($items)* [$($item)+]] [] $($rest)*)
+    };
+    (@items $axis:ident [$($items:tt)*] [$($item:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__hako_stack!(@items $axis [$($items)*] [$($item)* $next] $($rest)*)
+    };
+    (@build $axis:ident [$([$($item:tt)+])*] [$($key:ident = $value:tt),* $(,)?]) => {{
+        let stack = $crate::layout::Stack::$axis();
+        $(let stack = $crate::__hako_stack!(@attribute stack $key = $value);)*
+        stack.join(::std::vec![$($crate::hako!($($item)+)),*])
+    }};
+    (@attribute $stack:ident align = left) => {
+        $stack.align($crate::align::valued::Alignment::LEFT)
+    };
+    (@attribute $stack:ident align = right) => {
+        $stack.align($crate::align::valued::Alignment::RIGHT)
+    };
+    (@attribute $stack:ident align = top) => {
+        $stack.align($crate::align::valued::Alignment::TOP)
+    };
+    (@attribute $stack:ident align = bottom) => {
+        $stack.align($crate::align::valued::Alignment::BOTTOM)
+    };
+    (@attribute $stack:ident gap = $gap:tt) => {
+        $stack.gap($gap)
+    };
+    (@attribute $stack:ident pad = $padding:tt) => {
+        $stack.pad($padding)
+    };
+}
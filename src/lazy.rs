@@ -0,0 +1,316 @@
+use std::borrow::Cow;
+use std::cmp;
+use std::io::{self, Write};
+
+use crate::block::Block;
+use crate::content::Content;
+use crate::{Render, RenderContext};
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+struct Padding {
+    left: usize,
+    right: usize,
+    top: usize,
+    bottom: usize,
+}
+
+#[derive(Clone, Debug)]
+enum Node<C>
+where
+    C: Content,
+{
+    Block(Block<C>),
+    // Padding is fused: consecutive pads are accumulated into a single node.
+    Pad(Box<LazyBlock<C>>, Padding),
+    // Extends a block to a width and height like `pad_to_width_at_right` and
+    // `pad_to_height_at_bottom`. Consecutive extensions are accumulated into a single node.
+    Extend(Box<LazyBlock<C>>),
+    JoinLeftToRight(Box<LazyBlock<C>>, Box<LazyBlock<C>>),
+    JoinTopToBottom(Box<LazyBlock<C>>, Box<LazyBlock<C>>),
+    Overlay(Box<LazyBlock<C>>, Box<LazyBlock<C>>),
+}
+
+/// A block that defers composition until it is evaluated or rendered.
+///
+/// Operations on a `LazyBlock` build a tree of operations rather than lines. The dimensions of
+/// the block are known without evaluation. When evaluated, consecutive pads are applied as one
+/// pad and blocks that are overlaid behind an opaque block that covers them are never evaluated.
+/// Lines are allocated by the evaluated operations as they are by `Block`.
+#[derive(Clone, Debug)]
+pub struct LazyBlock<C = String>
+where
+    C: Content,
+{
+    node: Node<C>,
+    width: usize,
+    height: usize,
+}
+
+impl<C> LazyBlock<C>
+where
+    C: Content,
+{
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn evaluate(self) -> Block<C> {
+        let LazyBlock {
+            node,
+            width,
+            height,
+        } = self;
+        match node {
+            Node::Block(block) => block,
+            Node::Pad(block, padding) => block
+                .evaluate()
+                .pad_at_left(padding.left)
+                .pad_at_right(padding.right)
+                .pad_at_top(padding.top)
+                .pad_at_bottom(padding.bottom),
+            Node::Extend(block) => block
+                .evaluate()
+                .pad_to_width_at_right(width)
+                .pad_to_height_at_bottom(height),
+            Node::JoinLeftToRight(left, right) => {
+                left.evaluate().join_left_to_right_at_top(right.evaluate())
+            }
+            Node::JoinTopToBottom(top, bottom) => {
+                top.evaluate().join_top_to_bottom_at_left(bottom.evaluate())
+            }
+            Node::Overlay(front, back) => {
+                let front = front.evaluate();
+                // The back block is skipped if the front block occludes all of its cells.
+                if front.width() >= back.width && front.height() >= back.height && front.is_opaque()
+                {
+                    front
+                }
+                else {
+                    front.overlay(back.evaluate())
+                }
+            }
+        }
+    }
+
+    fn is_empty_leaf(&self) -> bool {
+        matches!(self.node, Node::Block(ref block) if block.is_empty())
+    }
+
+    fn pad(self, padding: Padding) -> Self {
+        let width = self.width + padding.left + padding.right;
+        let height = self.height + padding.top + padding.bottom;
+        let node = match self.node {
+            Node::Pad(block, inner) => Node::Pad(
+                block,
+                Padding {
+                    left: inner.left + padding.left,
+                    right: inner.right + padding.right,
+                    top: inner.top + padding.top,
+                    bottom: inner.bottom + padding.bottom,
+                },
+            ),
+            node => Node::Pad(
+                Box::new(LazyBlock {
+                    node,
+                    width: self.width,
+                    height: self.height,
+                }),
+                padding,
+            ),
+        };
+        LazyBlock {
+            node,
+            width,
+            height,
+        }
+    }
+
+    fn extend(self, width: usize, height: usize) -> Self {
+        let width = cmp::max(self.width, width);
+        let height = cmp::max(self.height, height);
+        if width == self.width && height == self.height {
+            return self;
+        }
+        let node = match self.node {
+            Node::Extend(block) => Node::Extend(block),
+            node => Node::Extend(Box::new(LazyBlock {
+                node,
+                width: self.width,
+                height: self.height,
+            })),
+        };
+        LazyBlock {
+            node,
+            width,
+            height,
+        }
+    }
+}
+
+/// Fundamental operations.
+impl<C> LazyBlock<C>
+where
+    C: Content,
+{
+    #[must_use]
+    pub fn pad_to_width_at_right(self, width: usize) -> Self {
+        let height = self.height;
+        self.extend(width, height)
+    }
+
+    #[must_use]
+    pub fn pad_to_height_at_bottom(self, height: usize) -> Self {
+        let width = self.width;
+        self.extend(width, height)
+    }
+
+    #[must_use]
+    pub fn join_left_to_right_at_top(self, right: Self) -> Self {
+        let width = self.width + right.width;
+        let height = cmp::max(self.height, right.height);
+        LazyBlock {
+            node: Node::JoinLeftToRight(Box::new(self), Box::new(right)),
+            width,
+            height,
+        }
+    }
+
+    #[must_use]
+    pub fn join_top_to_bottom_at_left(self, bottom: Self) -> Self {
+        let width = cmp::max(self.width, bottom.width);
+        let height = self.height + bottom.height;
+        LazyBlock {
+            node: Node::JoinTopToBottom(Box::new(self), Box::new(bottom)),
+            width,
+            height,
+        }
+    }
+
+    #[must_use]
+    pub fn overlay(self, back: Self) -> Self {
+        let width = cmp::max(self.width, back.width);
+        let height = cmp::max(self.height, back.height);
+        // Empty blocks are transparent, so overlays involving them are reduced to extensions and
+        // the empty block is never evaluated.
+        if self.is_empty_leaf() {
+            back.extend(width, height)
+        }
+        else if back.is_empty_leaf() {
+            self.extend(width, height)
+        }
+        else {
+            LazyBlock {
+                node: Node::Overlay(Box::new(self), Box::new(back)),
+                width,
+                height,
+            }
+        }
+    }
+}
+
+impl<C> LazyBlock<C>
+where
+    C: Content,
+{
+    #[must_use]
+    pub fn pad_at_left(self, width: usize) -> Self {
+        self.pad(Padding {
+            left: width,
+            ..Default::default()
+        })
+    }
+
+    #[must_use]
+    pub fn pad_at_right(self, width: usize) -> Self {
+        self.pad(Padding {
+            right: width,
+            ..Default::default()
+        })
+    }
+
+    #[must_use]
+    pub fn pad_at_top(self, height: usize) -> Self {
+        self.pad(Padding {
+            top: height,
+            ..Default::default()
+        })
+    }
+
+    #[must_use]
+    pub fn pad_at_bottom(self, height: usize) -> Self {
+        self.pad(Padding {
+            bottom: height,
+            ..Default::default()
+        })
+    }
+
+    #[must_use]
+    pub fn pad_to_width_at_left(self, width: usize) -> Self {
+        let width = width.saturating_sub(self.width);
+        self.pad_at_left(width)
+    }
+
+    #[must_use]
+    pub fn pad_to_height_at_top(self, height: usize) -> Self {
+        let height = height.saturating_sub(self.height);
+        self.pad_at_top(height)
+    }
+
+    #[must_use]
+    pub fn join_left_to_right_at_bottom(self, right: Self) -> Self {
+        let height = cmp::max(self.height, right.height);
+        self.pad_to_height_at_top(height)
+            .join_left_to_right_at_top(right.pad_to_height_at_top(height))
+    }
+
+    #[must_use]
+    pub fn join_top_to_bottom_at_right(self, bottom: Self) -> Self {
+        let width = cmp::max(self.width, bottom.width);
+        self.pad_to_width_at_left(width)
+            .join_top_to_bottom_at_left(bottom.pad_to_width_at_left(width))
+    }
+}
+
+impl<C> From<Block<C>> for LazyBlock<C>
+where
+    C: Content,
+{
+    fn from(block: Block<C>) -> Self {
+        let width = block.width();
+        let height = block.height();
+        LazyBlock {
+            node: Node::Block(block),
+            width,
+            height,
+        }
+    }
+}
+
+impl<C> Render for LazyBlock<C>
+where
+    C: Content,
+{
+    fn render_into(&self, target: &mut impl Write) -> io::Result<()> {
+        self.clone().evaluate().render_into(target)
+    }
+
+    fn render(&self) -> Cow<'_, str> {
+        self.clone().evaluate().render().into_owned().into()
+    }
+
+    fn render_into_with(&self, target: &mut impl Write, context: &RenderContext) -> io::Result<()> {
+        self.clone().evaluate().render_into_with(target, context)
+    }
+
+    fn render_with(&self, context: &RenderContext) -> Cow<'_, str> {
+        self.clone()
+            .evaluate()
+            .render_with(context)
+            .into_owned()
+            .into()
+    }
+}
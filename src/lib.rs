@@ -1,9 +1,33 @@
 pub mod align;
+pub mod animation;
+pub mod ansi;
+pub mod baseline;
 pub mod block;
 pub mod content;
+pub mod error;
+pub mod layout;
+pub mod lazy;
+pub mod markdown;
+pub mod mask;
+pub mod primitive;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "syntect")]
+pub mod syntect;
+pub mod table;
+#[cfg(feature = "terminal")]
+pub mod terminal;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod wrap;
 
 use std::borrow::Cow;
+use std::env;
 use std::io::{self, Write};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::content::CellBuffer;
 
 pub use crate::block::Block;
 pub use crate::content::{Style, Styled};
@@ -14,22 +38,122 @@ pub mod prelude {
     pub use crate::Render as _;
 }
 
+/// The colors supported by the target of rendering.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ColorDepth {
+    /// The 16 colors of the basic ANSI palette.
+    Ansi16,
+    /// The 256 colors of the extended ANSI palette.
+    Ansi256,
+    /// 24-bit colors.
+    #[default]
+    TrueColor,
+}
+
+/// Describes the target of rendering to styles.
+///
+/// By default, styles are written with 24-bit colors, which is how content is rendered by
+/// [`Render::render`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RenderContext {
+    /// Whether or not styles write their prefixes and suffixes. Text transformed by styles is
+    /// written regardless.
+    pub is_styled: bool,
+    pub color_depth: ColorDepth,
+}
+
+impl RenderContext {
+    pub const PLAIN: Self = RenderContext {
+        is_styled: false,
+        color_depth: ColorDepth::Ansi16,
+    };
+
+    /// Gets the context of a target from the environment.
+    ///
+    /// Styles are enabled only if the target is a terminal, `NO_COLOR` is unset or empty, and
+    /// `TERM` is not `dumb`. The color depth is read from `COLORTERM` and `TERM`.
+    pub fn from_environment(is_terminal: bool) -> Self {
+        let term = env::var("TERM").unwrap_or_default();
+        let is_colorless = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        let color_depth = match env::var("COLORTERM").as_deref() {
+            Ok("truecolor") | Ok("24bit") => ColorDepth::TrueColor,
+            _ if term.contains("256color") => ColorDepth::Ansi256,
+            _ => ColorDepth::Ansi16,
+        };
+        RenderContext {
+            is_styled: is_terminal && !is_colorless && term != "dumb",
+            color_depth,
+        }
+    }
+
+    /// Gets the context of standard output from the environment.
+    ///
+    /// See [`RenderContext::from_environment`].
+    #[cfg(feature = "terminal")]
+    pub fn detect() -> Self {
+        RenderContext::from_environment(terminal::is_terminal())
+    }
+}
+
+impl Default for RenderContext {
+    fn default() -> Self {
+        RenderContext {
+            is_styled: true,
+            color_depth: ColorDepth::default(),
+        }
+    }
+}
+
 pub trait Render {
     fn render_into(&self, target: &mut impl Write) -> io::Result<()> {
         target.write_all(self.render().as_bytes())
     }
 
-    fn render(&self) -> Cow<str>;
+    fn render(&self) -> Cow<'_, str>;
+
+    /// Renders into the target in the given context.
+    ///
+    /// By default, the context is ignored.
+    fn render_into_with(&self, target: &mut impl Write, context: &RenderContext) -> io::Result<()> {
+        let _ = context;
+        self.render_into(target)
+    }
+
+    /// Renders in the given context.
+    ///
+    /// By default, the context is ignored.
+    fn render_with(&self, context: &RenderContext) -> Cow<'_, str> {
+        let _ = context;
+        self.render()
+    }
 }
 
 impl<'t> Render for Cow<'t, str> {
-    fn render(&self) -> Cow<str> {
+    fn render(&self) -> Cow<'_, str> {
         self.clone()
     }
 }
 
 impl Render for String {
-    fn render(&self) -> Cow<str> {
+    fn render(&self) -> Cow<'_, str> {
         self.into()
     }
 }
+
+impl Render for Rc<str> {
+    fn render(&self) -> Cow<'_, str> {
+        self.as_ref().into()
+    }
+}
+
+impl Render for Arc<str> {
+    fn render(&self) -> Cow<'_, str> {
+        self.as_ref().into()
+    }
+}
+
+impl Render for CellBuffer {
+    fn render(&self) -> Cow<'_, str> {
+        self.as_str().into()
+    }
+}
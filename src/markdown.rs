@@ -0,0 +1,73 @@
+use std::borrow::Cow;
+
+use crate::block::Block;
+use crate::content::Content;
+
+/// Presentation as GitHub-flavored Markdown.
+///
+/// Markdown is emitted without styles, so the same definition can be drawn to a terminal or
+/// written to a document.
+pub trait ToMarkdown {
+    fn to_markdown(&self) -> String;
+}
+
+/// Escapes characters that Markdown interprets as markup or table delimiters.
+///
+/// Markers that begin a heading, list item, or thematic break, such as `#`, `-`, and the period
+/// in `1.`, are escaped only where they begin the text.
+pub fn escape(text: &str) -> Cow<'_, str> {
+    const SPECIAL: &[char] = &['\\', '`', '*', '_', '[', ']', '<', '>', '|'];
+
+    // Markers are followed by a space or the end of the text, except for thematic breaks.
+    fn is_delimited(text: &str) -> bool {
+        text.is_empty() || text.starts_with(' ')
+    }
+
+    let content = text.trim_start_matches(' ');
+    let indent = text.len() - content.len();
+    let digits = content.len()
+        - content
+            .trim_start_matches(|point: char| point.is_ascii_digit())
+            .len();
+    let leading = match content.chars().next() {
+        Some('#') => is_delimited(content.trim_start_matches('#')).then_some(indent),
+        Some(marker @ ('+' | '-')) => {
+            (is_delimited(&content[1..]) || content[1..].starts_with(marker)).then_some(indent)
+        }
+        _ if digits > 0 && content[digits..].starts_with(['.', ')']) => {
+            is_delimited(&content[digits + 1..]).then_some(indent + digits)
+        }
+        _ => None,
+    };
+    if leading.is_some() || text.contains(SPECIAL) {
+        let mut output = String::with_capacity(text.len() + 1);
+        for (index, point) in text.char_indices() {
+            if SPECIAL.contains(&point) || leading == Some(index) {
+                output.push('\\');
+            }
+            output.push(point);
+        }
+        output.into()
+    }
+    else {
+        text.into()
+    }
+}
+
+// Gets the unstyled lines of a block without trailing spaces.
+pub(crate) fn lines<C>(block: &Block<C>) -> Vec<String>
+where
+    C: Content,
+{
+    block
+        .cells()
+        .into_iter()
+        .map(|row| {
+            row.iter()
+                .map(|glyph| glyph.get())
+                .collect::<String>()
+                .trim_end()
+                .to_owned()
+        })
+        .collect()
+}
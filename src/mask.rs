@@ -0,0 +1,146 @@
+use std::{cmp, iter};
+
+use crate::block::Block;
+use crate::content::{Content, Grapheme};
+
+/// A grid of flags that selects cells of a block by column and row.
+///
+/// Cells beyond the dimensions of a mask are not selected.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Mask {
+    cells: Vec<bool>,
+    width: usize,
+    height: usize,
+}
+
+impl Mask {
+    pub fn with_dimensions(width: usize, height: usize) -> Self {
+        Mask {
+            cells: vec![false; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> bool) -> Self {
+        Mask {
+            cells: (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| f(x, y))
+                .collect(),
+            width,
+            height,
+        }
+    }
+
+    /// Constructs a mask with the dimensions of a block that selects the graphemes for which the
+    /// given predicate is `true`.
+    ///
+    /// All columns of a selected wide grapheme are selected.
+    pub fn from_predicate<C>(block: &Block<C>, mut f: impl FnMut(&Grapheme) -> bool) -> Self
+    where
+        C: Content,
+    {
+        let mut mask = Mask::with_dimensions(block.width(), block.height());
+        for (y, row) in block.cells().iter().enumerate() {
+            let mut x = 0usize;
+            for glyph in row {
+                let is_selected = f(glyph);
+                for _ in 0..glyph.width() {
+                    mask.set(x, y, is_selected);
+                    x += 1;
+                }
+            }
+        }
+        mask
+    }
+
+    /// Constructs a mask with the dimensions of a block that selects the region of identical
+    /// graphemes that is connected to the given column and row.
+    ///
+    /// Cells are connected to their horizontal and vertical neighbors. If the column and row lie
+    /// outside of the block, then no cells are selected.
+    pub fn flood<C>(block: &Block<C>, x: usize, y: usize) -> Self
+    where
+        C: Content,
+    {
+        // Expand wide graphemes so that each column is associated with a grapheme.
+        let columns: Vec<Vec<_>> = block
+            .cells()
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .flat_map(|glyph| iter::repeat_n(glyph.clone(), glyph.width()))
+                    .collect()
+            })
+            .collect();
+        let get = |x: usize, y: usize| columns.get(y).and_then(|row| row.get(x));
+        let mut mask = Mask::with_dimensions(block.width(), block.height());
+        let target = match get(x, y) {
+            Some(target) => target,
+            _ => return mask,
+        };
+        let mut cells = vec![(x, y)];
+        while let Some((x, y)) = cells.pop() {
+            if mask.get(x, y) || get(x, y) != Some(target) {
+                continue;
+            }
+            mask.set(x, y, true);
+            cells.push((x + 1, y));
+            cells.push((x, y + 1));
+            if x > 0 {
+                cells.push((x - 1, y));
+            }
+            if y > 0 {
+                cells.push((x, y - 1));
+            }
+        }
+        mask
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.index(x, y).is_some_and(|index| self.cells[index])
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, is_selected: bool) {
+        if let Some(index) = self.index(x, y) {
+            self.cells[index] = is_selected;
+        }
+    }
+
+    #[must_use]
+    pub fn invert(self) -> Self {
+        Mask {
+            cells: self.cells.into_iter().map(|cell| !cell).collect(),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn union(self, other: &Self) -> Self {
+        self.zip_with(other, |left, right| left || right)
+    }
+
+    #[must_use]
+    pub fn intersection(self, other: &Self) -> Self {
+        self.zip_with(other, |left, right| left && right)
+    }
+
+    fn zip_with(self, other: &Self, mut f: impl FnMut(bool, bool) -> bool) -> Self {
+        let width = cmp::max(self.width, other.width);
+        let height = cmp::max(self.height, other.height);
+        Mask::from_fn(width, height, |x, y| f(self.get(x, y), other.get(x, y)))
+    }
+
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| (y * self.width) + x)
+    }
+}
@@ -0,0 +1,1821 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Add, Neg, Sub};
+use std::{cmp, iter};
+
+use crate::align::{typed, valued, Axial, Corners, Rotate};
+use crate::block::{self, AxialBlock, Block, Fill};
+use crate::content::{
+    str_width, Content, FromCell, Grapheme, OverflowPolicy, SanitizePolicy, Style, Styled,
+};
+#[cfg(feature = "figlet")]
+use crate::error::FontError;
+use crate::error::ThresholdError;
+use crate::markdown::{self, ToMarkdown};
+use crate::mask::Mask;
+use crate::wrap::{self, WrapPolicy};
+
+/// A displacement in cells along both axes.
+///
+/// Positive components displace toward the right and bottom and negative components displace
+/// toward the left and top.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct AxisVector {
+    pub horizontal: isize,
+    pub vertical: isize,
+}
+
+impl AxisVector {
+    pub const ZERO: Self = AxisVector::new(0, 0);
+
+    pub const fn new(horizontal: isize, vertical: isize) -> Self {
+        AxisVector {
+            horizontal,
+            vertical,
+        }
+    }
+
+    /// Constructs a vector with the given distance toward the alignment `L`.
+    ///
+    /// For example, `AxisVector::toward::<Bottom>(2)` displaces two cells down.
+    pub fn toward<L>(distance: usize) -> Self
+    where
+        L: typed::Alignment,
+    {
+        let distance = distance as isize;
+        match L::VALUE {
+            valued::Alignment::Horizontal(valued::HorizontalAlignment::Left) => {
+                AxisVector::new(-distance, 0)
+            }
+            valued::Alignment::Horizontal(valued::HorizontalAlignment::Right) => {
+                AxisVector::new(distance, 0)
+            }
+            valued::Alignment::Vertical(valued::VerticalAlignment::Top) => {
+                AxisVector::new(0, -distance)
+            }
+            valued::Alignment::Vertical(valued::VerticalAlignment::Bottom) => {
+                AxisVector::new(0, distance)
+            }
+        }
+    }
+
+    /// Gets the component of the vector along the axis `A`.
+    pub fn get<A>(&self) -> isize
+    where
+        A: typed::Axis,
+    {
+        match A::VALUE {
+            valued::Axis::LeftRight => self.horizontal,
+            valued::Axis::TopBottom => self.vertical,
+        }
+    }
+
+    /// Rotates the vector a quarter turn clockwise, such that right becomes down.
+    #[must_use]
+    pub const fn rotate_clockwise(self) -> Self {
+        AxisVector::new(-self.vertical, self.horizontal)
+    }
+
+    /// Rotates the vector a quarter turn counterclockwise, such that right becomes up.
+    #[must_use]
+    pub const fn rotate_counterclockwise(self) -> Self {
+        AxisVector::new(self.vertical, -self.horizontal)
+    }
+
+    /// Rotates the vector a half turn.
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        AxisVector::new(-self.horizontal, -self.vertical)
+    }
+}
+
+impl Add for AxisVector {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        AxisVector::new(
+            self.horizontal + other.horizontal,
+            self.vertical + other.vertical,
+        )
+    }
+}
+
+impl Neg for AxisVector {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.reverse()
+    }
+}
+
+impl Sub for AxisVector {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + (-other)
+    }
+}
+
+impl Rotate for AxisVector {
+    fn rotate_clockwise(self) -> Self {
+        AxisVector::rotate_clockwise(self)
+    }
+
+    fn rotate_counterclockwise(self) -> Self {
+        AxisVector::rotate_counterclockwise(self)
+    }
+}
+
+impl From<Axial<usize>> for AxisVector {
+    fn from(offset: Axial<usize>) -> Self {
+        AxisVector::new(offset.horizontal as isize, offset.vertical as isize)
+    }
+}
+
+/// Glyphs used to draw line segments.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Palette {
+    pub horizontal: char,
+    pub vertical: char,
+    pub rising: char,
+    pub falling: char,
+    pub crossing: char,
+}
+
+impl Palette {
+    pub const ASCII: Palette = Palette {
+        horizontal: '-',
+        vertical: '|',
+        rising: '/',
+        falling: '\\',
+        crossing: 'X',
+    };
+    pub const UNICODE: Palette = Palette {
+        horizontal: '\u{2500}',
+        vertical: '\u{2502}',
+        rising: '\u{2571}',
+        falling: '\u{2572}',
+        crossing: '\u{2573}',
+    };
+
+    fn segment(&self, dx: isize, dy: isize) -> char {
+        match (dx, dy) {
+            (_, 0) => self.horizontal,
+            (0, _) => self.vertical,
+            (dx, dy) if (dx > 0) == (dy > 0) => self.falling,
+            _ => self.rising,
+        }
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::UNICODE
+    }
+}
+
+/// Glyphs used to draw the edges and corners of boxes.
+///
+/// Tees are drawn where rules meet an edge and are named by the edge on which they lie.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Stroke {
+    pub horizontal: char,
+    pub vertical: char,
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub top_tee: char,
+    pub bottom_tee: char,
+    pub left_tee: char,
+    pub right_tee: char,
+    pub cross: char,
+}
+
+impl Stroke {
+    pub const ASCII: Stroke = Stroke {
+        horizontal: '-',
+        vertical: '|',
+        top_left: '+',
+        top_right: '+',
+        bottom_left: '+',
+        bottom_right: '+',
+        top_tee: '+',
+        bottom_tee: '+',
+        left_tee: '+',
+        right_tee: '+',
+        cross: '+',
+    };
+    pub const LIGHT: Stroke = Stroke {
+        horizontal: '\u{2500}',
+        vertical: '\u{2502}',
+        top_left: '\u{250C}',
+        top_right: '\u{2510}',
+        bottom_left: '\u{2514}',
+        bottom_right: '\u{2518}',
+        top_tee: '\u{252C}',
+        bottom_tee: '\u{2534}',
+        left_tee: '\u{251C}',
+        right_tee: '\u{2524}',
+        cross: '\u{253C}',
+    };
+    pub const DOUBLE: Stroke = Stroke {
+        horizontal: '\u{2550}',
+        vertical: '\u{2551}',
+        top_left: '\u{2554}',
+        top_right: '\u{2557}',
+        bottom_left: '\u{255A}',
+        bottom_right: '\u{255D}',
+        top_tee: '\u{2566}',
+        bottom_tee: '\u{2569}',
+        left_tee: '\u{2560}',
+        right_tee: '\u{2563}',
+        cross: '\u{256C}',
+    };
+}
+
+impl Stroke {
+    pub fn corners(&self) -> Corners<char> {
+        Corners {
+            top_left: self.top_left,
+            top_right: self.top_right,
+            bottom_left: self.bottom_left,
+            bottom_right: self.bottom_right,
+        }
+    }
+
+    /// Gets the glyph drawn where rules meet given whether rules extend up, down, left, and right.
+    pub fn junction(&self, up: bool, down: bool, left: bool, right: bool) -> char {
+        match (up, down, left, right) {
+            (true, true, true, true) => self.cross,
+            (false, true, true, true) => self.top_tee,
+            (true, false, true, true) => self.bottom_tee,
+            (true, true, false, true) => self.left_tee,
+            (true, true, true, false) => self.right_tee,
+            (false, true, false, true) => self.top_left,
+            (false, true, true, false) => self.top_right,
+            (true, false, false, true) => self.bottom_left,
+            (true, false, true, false) => self.bottom_right,
+            (true, _, false, false) | (_, true, false, false) => self.vertical,
+            _ => self.horizontal,
+        }
+    }
+}
+
+impl Default for Stroke {
+    fn default() -> Self {
+        Stroke::LIGHT
+    }
+}
+
+/// Paints the glyphs of strokes and palettes as content.
+///
+/// Brushes are used by lines, rectangles, and arrows to draw their glyphs. Any [`Style`] is a
+/// brush for [`Styled`] content, so frames and connectors can be styled independently of the
+/// content that they surround.
+pub trait Brush<C>
+where
+    C: Content,
+{
+    fn paint(&self, glyph: char) -> C;
+}
+
+/// A brush that paints glyphs without a style.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Plain;
+
+impl<C> Brush<C> for Plain
+where
+    C: Content,
+{
+    fn paint(&self, glyph: char) -> C {
+        C::from_cell(glyph)
+    }
+}
+
+impl<C, S> Brush<Styled<C, S>> for S
+where
+    C: AsRef<str> + Content + From<String>,
+    S: PartialEq + Style,
+{
+    fn paint(&self, glyph: char) -> Styled<C, S> {
+        Styled::new(self.clone(), C::from_cell(glyph))
+    }
+}
+
+// Paints glyphs into a block with one cell per glyph along the axis `A`.
+pub(crate) fn paint_along<A, C, B>(glyphs: &[char], brush: &B) -> Block<C>
+where
+    Block<C>: AxialBlock<A>,
+    A: typed::Axis,
+    B: Brush<C>,
+    C: Content,
+{
+    glyphs
+        .iter()
+        .map(|glyph| Block::with_content(brush.paint(*glyph)))
+        .fold(Block::with_length_at::<A>(0, 1), Block::join_at_origin::<A>)
+}
+
+/// The look of frames, tables, and lists.
+///
+/// A theme groups the glyphs, content, and styles used by primitives, so the look of a layout can
+/// be changed by swapping one value. Not every primitive uses every field:
+///
+/// - `Rect` draws its border with `stroke` and `border` and fills an unset interior with `fill`,
+///   if any.
+/// - `Table` draws its border and rules with `stroke` and `border`, styles its header with
+///   `title`, and styles the padding on either side of its cells with `padding`.
+/// - `List` paints its markers with `emphasis`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Theme<C = String, S = ()> {
+    pub stroke: Stroke,
+    pub border: S,
+    pub fill: Option<C>,
+    pub padding: S,
+    pub title: S,
+    pub emphasis: S,
+}
+
+impl<C, S> Default for Theme<C, S>
+where
+    S: Default,
+{
+    fn default() -> Self {
+        Theme {
+            stroke: Stroke::default(),
+            border: S::default(),
+            fill: None,
+            padding: S::default(),
+            title: S::default(),
+            emphasis: S::default(),
+        }
+    }
+}
+
+/// Glyphs used to draw a straight line along an axis.
+///
+/// A line is drawn with `start` at its origin end (the left or top), `end` at the opposite end,
+/// and `middle` between them. Lines with a length of one have no distinct ends and are drawn
+/// with `middle` only.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct LinePalette {
+    pub start: char,
+    pub middle: char,
+    pub end: char,
+}
+
+impl LinePalette {
+    /// Constructs a palette that draws every cell of a line with the same glyph.
+    pub const fn uniform(glyph: char) -> Self {
+        LinePalette {
+            start: glyph,
+            middle: glyph,
+            end: glyph,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_terminals(self, start: char, end: char) -> Self {
+        LinePalette { start, end, ..self }
+    }
+
+    /// Gets the glyphs of a line with the given length from its start to its end.
+    pub fn glyphs(&self, length: usize) -> Vec<char> {
+        match length {
+            0 => vec![],
+            1 => vec![self.middle],
+            _ => iter::once(self.start)
+                .chain(iter::repeat_n(self.middle, length - 2))
+                .chain(iter::once(self.end))
+                .collect(),
+        }
+    }
+}
+
+/// Glyphs used to draw straight lines along either axis.
+///
+/// The palette of a line is resolved by its axis: horizontal lines are drawn from left to right
+/// with `horizontal` and vertical lines are drawn from top to bottom with `vertical`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AxialPalette {
+    pub horizontal: LinePalette,
+    pub vertical: LinePalette,
+}
+
+impl AxialPalette {
+    pub const fn uniform(horizontal: char, vertical: char) -> Self {
+        AxialPalette {
+            horizontal: LinePalette::uniform(horizontal),
+            vertical: LinePalette::uniform(vertical),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_horizontal(self, horizontal: LinePalette) -> Self {
+        AxialPalette { horizontal, ..self }
+    }
+
+    #[must_use]
+    pub const fn with_vertical(self, vertical: LinePalette) -> Self {
+        AxialPalette { vertical, ..self }
+    }
+
+    pub fn get(&self, axis: valued::Axis) -> &LinePalette {
+        match axis {
+            valued::Axis::LeftRight => &self.horizontal,
+            valued::Axis::TopBottom => &self.vertical,
+        }
+    }
+
+    pub fn get_at<A>(&self) -> &LinePalette
+    where
+        A: typed::Axis,
+    {
+        self.get(A::VALUE)
+    }
+}
+
+impl Default for AxialPalette {
+    fn default() -> Self {
+        Palette::default().into()
+    }
+}
+
+impl From<Palette> for AxialPalette {
+    fn from(palette: Palette) -> Self {
+        AxialPalette::uniform(palette.horizontal, palette.vertical)
+    }
+}
+
+impl From<Stroke> for AxialPalette {
+    fn from(stroke: Stroke) -> Self {
+        AxialPalette::uniform(stroke.horizontal, stroke.vertical)
+    }
+}
+
+/// A straight line along the axis `A`.
+///
+/// The middle of a line can be drawn with a repeating pattern rather than the `middle` glyph of
+/// its palette. Each character of a pattern occupies one cell. The pattern is indexed by the
+/// position of a cell in the line offset by its phase, so segments that are joined end to end
+/// stay aligned when each phase is the total length of the preceding segments.
+pub struct Line<A, B = Plain>
+where
+    A: typed::Axis,
+{
+    length: usize,
+    palette: LinePalette,
+    pattern: Vec<char>,
+    phase: usize,
+    brush: B,
+    phantom: PhantomData<fn() -> A>,
+}
+
+impl<A> Line<A>
+where
+    A: typed::Axis,
+{
+    pub fn new(length: usize) -> Self {
+        Line {
+            length,
+            palette: *AxialPalette::default().get_at::<A>(),
+            pattern: vec![],
+            phase: 0,
+            brush: Plain,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<A, B> Line<A, B>
+where
+    A: typed::Axis,
+{
+    #[must_use]
+    pub fn brush<T>(self, brush: T) -> Line<A, T> {
+        let Line {
+            length,
+            palette,
+            pattern,
+            phase,
+            ..
+        } = self;
+        Line {
+            length,
+            palette,
+            pattern,
+            phase,
+            brush,
+            phantom: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn palette(self, palette: impl Into<AxialPalette>) -> Self {
+        Line {
+            palette: *palette.into().get_at::<A>(),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn line_palette(self, palette: LinePalette) -> Self {
+        Line { palette, ..self }
+    }
+
+    /// Sets the repeating pattern drawn in the middle of the line.
+    ///
+    /// An empty pattern draws the `middle` glyph of the palette.
+    #[must_use]
+    pub fn pattern(self, pattern: impl AsRef<str>) -> Self {
+        Line {
+            pattern: pattern.as_ref().chars().collect(),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn phase(self, phase: usize) -> Self {
+        Line { phase, ..self }
+    }
+
+    pub fn glyphs(&self) -> Vec<char> {
+        let mut glyphs = self.palette.glyphs(self.length);
+        if !self.pattern.is_empty() {
+            let middle = match self.length {
+                0 => 0..0,
+                1 => 0..1,
+                n => 1..(n - 1),
+            };
+            for index in middle {
+                glyphs[index] = self.pattern[(index + self.phase) % self.pattern.len()];
+            }
+        }
+        glyphs
+    }
+
+    pub fn into_block<C>(self) -> Block<C>
+    where
+        Block<C>: AxialBlock<A>,
+        B: Brush<C>,
+        C: Content,
+    {
+        paint_along::<A, _, _>(&self.glyphs(), &self.brush)
+    }
+}
+
+impl<A, B> Clone for Line<A, B>
+where
+    A: typed::Axis,
+    B: Clone,
+{
+    fn clone(&self) -> Self {
+        Line {
+            length: self.length,
+            palette: self.palette,
+            pattern: self.pattern.clone(),
+            phase: self.phase,
+            brush: self.brush.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<A, B> Debug for Line<A, B>
+where
+    A: typed::Axis,
+    B: Debug,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Line")
+            .field("axis", &A::VALUE)
+            .field("length", &self.length)
+            .field("palette", &self.palette)
+            .field("pattern", &self.pattern)
+            .field("phase", &self.phase)
+            .field("brush", &self.brush)
+            .finish()
+    }
+}
+
+/// A bordered rectangle with an optional interior.
+///
+/// The width and height of a rectangle include its border. Rectangles with a height of one are
+/// drawn as a horizontal line and rectangles with a width of one are drawn as a vertical line.
+#[derive(Clone, Debug)]
+pub struct Rect<C = String, B = Plain>
+where
+    C: Content,
+{
+    width: usize,
+    height: usize,
+    stroke: Stroke,
+    brush: B,
+    interior: Option<Block<C>>,
+}
+
+impl<C> Rect<C>
+where
+    C: Content,
+{
+    pub fn new(width: usize, height: usize) -> Self {
+        Rect {
+            width,
+            height,
+            stroke: Stroke::default(),
+            brush: Plain,
+            interior: None,
+        }
+    }
+}
+
+impl<C, B> Rect<C, B>
+where
+    C: Content,
+    B: Brush<C>,
+{
+    #[must_use]
+    pub fn stroke(self, stroke: Stroke) -> Self {
+        Rect { stroke, ..self }
+    }
+
+    /// Sets the brush with which the border is painted.
+    ///
+    /// The interior is not painted by the brush.
+    #[must_use]
+    pub fn brush<T>(self, brush: T) -> Rect<C, T>
+    where
+        T: Brush<C>,
+    {
+        let Rect {
+            width,
+            height,
+            stroke,
+            interior,
+            ..
+        } = self;
+        Rect {
+            width,
+            height,
+            stroke,
+            brush,
+            interior,
+        }
+    }
+
+    /// Fills the interior of the rectangle.
+    #[must_use]
+    pub fn fill<T>(self, filler: T) -> Self
+    where
+        Block<C>: Fill<C, T, Output = Block<C>>,
+    {
+        let interior =
+            Block::with_dimensions(self.width.saturating_sub(2), self.height.saturating_sub(2))
+                .fill(filler);
+        Rect {
+            interior: Some(interior),
+            ..self
+        }
+    }
+
+    pub fn into_block(self) -> Block<C> {
+        let Rect {
+            width,
+            height,
+            stroke,
+            brush,
+            interior,
+        } = self;
+        let horizontal = LinePalette::uniform(stroke.horizontal);
+        let vertical = LinePalette::uniform(stroke.vertical);
+        let horizontal_line = |palette: LinePalette, length| {
+            paint_along::<typed::LeftRight, _, _>(&palette.glyphs(length), &brush)
+        };
+        let vertical_line = |palette: LinePalette, length| {
+            paint_along::<typed::TopBottom, _, _>(&palette.glyphs(length), &brush)
+        };
+        if width == 0 || height == 0 {
+            Block::with_dimensions(width, height)
+        }
+        else if height == 1 {
+            horizontal_line(horizontal, width)
+        }
+        else if width == 1 {
+            vertical_line(vertical, height)
+        }
+        else {
+            let (width, height) = (width - 2, height - 2);
+            let corners = stroke.corners();
+            let top = horizontal.with_terminals(corners.top_left, corners.top_right);
+            let bottom = horizontal.with_terminals(corners.bottom_left, corners.bottom_right);
+            let interior = interior.unwrap_or_else(|| Block::with_dimensions(width, height));
+            horizontal_line(top, width + 2)
+                .join_top_to_bottom_at_left(
+                    vertical_line(vertical, height)
+                        .join_left_to_right_at_top(interior)
+                        .join_left_to_right_at_top(vertical_line(vertical, height)),
+                )
+                .join_top_to_bottom_at_left(horizontal_line(bottom, width + 2))
+        }
+    }
+}
+
+impl<C, S, B> Rect<Styled<C, S>, B>
+where
+    C: AsRef<str> + Content + From<String>,
+    S: PartialEq + Style,
+    B: Brush<Styled<C, S>>,
+{
+    /// Draws the border with the stroke and style of a theme and fills an unset interior with the
+    /// fill of the theme.
+    #[must_use]
+    pub fn theme(self, theme: &Theme<C, S>) -> Rect<Styled<C, S>, S> {
+        let rect = self.stroke(theme.stroke).brush(theme.border.clone());
+        match theme.fill.clone() {
+            Some(fill) if rect.interior.is_none() => rect.fill(Styled::plain(fill)),
+            _ => rect,
+        }
+    }
+}
+
+/// An arrangement of blocks in rows and columns.
+///
+/// Columns are as wide as their widest block and rows are as tall as their tallest block unless
+/// sized explicitly, in which case blocks are truncated to fit. Blocks are aligned at the top and
+/// left of their cells and missing cells in ragged rows are blank.
+#[derive(Clone, Debug)]
+pub struct Grid<C = String>
+where
+    C: Content,
+{
+    cells: Vec<Vec<Block<C>>>,
+    widths: Vec<Option<usize>>,
+    heights: Vec<Option<usize>>,
+    gutter: Axial<usize>,
+    rules: Option<Stroke>,
+}
+
+impl<C> Grid<C>
+where
+    C: Content + FromCell<char>,
+{
+    pub fn new<R, I>(rows: R) -> Self
+    where
+        R: IntoIterator<Item = I>,
+        I: IntoIterator<Item = Block<C>>,
+    {
+        let cells: Vec<Vec<_>> = rows
+            .into_iter()
+            .map(|row| row.into_iter().collect())
+            .collect();
+        let n = cells.iter().map(Vec::len).max().unwrap_or(0);
+        Grid {
+            widths: vec![None; n],
+            heights: vec![None; cells.len()],
+            cells,
+            gutter: Axial {
+                horizontal: 1,
+                vertical: 0,
+            },
+            rules: None,
+        }
+    }
+
+    #[must_use]
+    pub fn column_width(mut self, column: usize, width: usize) -> Self {
+        if let Some(size) = self.widths.get_mut(column) {
+            *size = Some(width);
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn row_height(mut self, row: usize, height: usize) -> Self {
+        if let Some(size) = self.heights.get_mut(row) {
+            *size = Some(height);
+        }
+        self
+    }
+
+    /// Sets the space between columns and rows.
+    ///
+    /// If rules are drawn, then the gutter is placed on both sides of each rule.
+    #[must_use]
+    pub fn gutter(self, gutter: Axial<usize>) -> Self {
+        Grid { gutter, ..self }
+    }
+
+    /// Draws rules between rows and columns with the given stroke.
+    #[must_use]
+    pub fn rules(self, stroke: Stroke) -> Self {
+        Grid {
+            rules: Some(stroke),
+            ..self
+        }
+    }
+
+    pub fn into_block(self) -> Block<C> {
+        let Grid {
+            cells,
+            widths,
+            heights,
+            gutter,
+            rules,
+        } = self;
+        let widths: Vec<_> = widths
+            .into_iter()
+            .enumerate()
+            .map(|(x, width)| {
+                width.unwrap_or_else(|| {
+                    cells
+                        .iter()
+                        .filter_map(|row| row.get(x))
+                        .map(Block::width)
+                        .max()
+                        .unwrap_or(0)
+                })
+            })
+            .collect();
+        let heights: Vec<_> = heights
+            .into_iter()
+            .zip(cells.iter())
+            .map(|(height, row)| {
+                height.unwrap_or_else(|| row.iter().map(Block::height).max().unwrap_or(0))
+            })
+            .collect();
+        // Separators consist of the gutter on both sides of a rule or only the gutter.
+        let separator = |gutter: usize| match rules {
+            Some(_) => (gutter * 2) + 1,
+            None => gutter,
+        };
+        let crossings: Vec<_> = widths
+            .iter()
+            .scan(0usize, |x, width| {
+                *x += width + separator(gutter.horizontal);
+                Some(*x - gutter.horizontal - 1)
+            })
+            .collect();
+        let rows: Vec<Block<C>> = cells
+            .into_iter()
+            .zip(heights.iter())
+            .map(|(row, &height)| {
+                let mut row = row.into_iter();
+                let rule = Block::with_dimensions(separator(gutter.horizontal), height).fill(
+                    block::from_fn(|x, _| match rules {
+                        Some(stroke) if x == gutter.horizontal => stroke.vertical,
+                        _ => ' ',
+                    }),
+                );
+                widths
+                    .iter()
+                    .map(|&width| {
+                        row.next()
+                            .unwrap_or_else(Block::zero)
+                            .truncate_to_width_at_right(width, None)
+                            .truncate_to_height_at_bottom(height, None)
+                            .pad_to_width_at_right(width)
+                            .pad_to_height_at_bottom(height)
+                    })
+                    .reduce(|left, right| {
+                        left.join_left_to_right_at_top(rule.clone())
+                            .join_left_to_right_at_top(right)
+                    })
+                    .unwrap_or_else(|| Block::with_height(height))
+            })
+            .collect();
+        let width = rows.first().map_or(0, Block::width);
+        let rule = Block::with_dimensions(width, separator(gutter.vertical)).fill(block::from_fn(
+            |x, y| match rules {
+                Some(stroke) if y == gutter.vertical => {
+                    if crossings.contains(&x) {
+                        stroke.cross
+                    }
+                    else {
+                        stroke.horizontal
+                    }
+                }
+                _ => ' ',
+            },
+        ));
+        rows.into_iter()
+            .reduce(|top, bottom| {
+                top.join_top_to_bottom_at_left(rule.clone())
+                    .join_top_to_bottom_at_left(bottom)
+            })
+            .unwrap_or_else(Block::zero)
+    }
+}
+
+/// Glyphs drawn at the ends of arrows keyed by the direction in which the arrow points.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Heads {
+    pub left: char,
+    pub right: char,
+    pub top: char,
+    pub bottom: char,
+}
+
+impl Heads {
+    pub const ASCII: Heads = Heads {
+        left: '<',
+        right: '>',
+        top: '^',
+        bottom: 'v',
+    };
+    pub const UNICODE: Heads = Heads {
+        left: '\u{25C0}',
+        right: '\u{25B6}',
+        top: '\u{25B2}',
+        bottom: '\u{25BC}',
+    };
+
+    fn get(&self, alignment: valued::Alignment) -> char {
+        match alignment {
+            valued::Alignment::Horizontal(valued::HorizontalAlignment::Left) => self.left,
+            valued::Alignment::Horizontal(valued::HorizontalAlignment::Right) => self.right,
+            valued::Alignment::Vertical(valued::VerticalAlignment::Top) => self.top,
+            valued::Alignment::Vertical(valued::VerticalAlignment::Bottom) => self.bottom,
+        }
+    }
+}
+
+impl Default for Heads {
+    fn default() -> Self {
+        Heads::UNICODE
+    }
+}
+
+/// An axis-aligned arrow that points toward the alignment `L`.
+///
+/// The length of an arrow includes its head and tail. The head is drawn at the end of the arrow
+/// toward `L` and the tail, if any, is drawn at the opposite end.
+pub struct Arrow<L, B = Plain>
+where
+    L: typed::Alignment,
+{
+    length: usize,
+    palette: Palette,
+    head: Heads,
+    tail: Option<Heads>,
+    brush: B,
+    phantom: PhantomData<fn() -> L>,
+}
+
+impl<L> Arrow<L>
+where
+    L: typed::Alignment,
+{
+    pub fn new(length: usize) -> Self {
+        Arrow {
+            length,
+            palette: Palette::default(),
+            head: Heads::default(),
+            tail: None,
+            brush: Plain,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<L, B> Arrow<L, B>
+where
+    L: typed::Alignment,
+{
+    #[must_use]
+    pub fn brush<T>(self, brush: T) -> Arrow<L, T> {
+        let Arrow {
+            length,
+            palette,
+            head,
+            tail,
+            ..
+        } = self;
+        Arrow {
+            length,
+            palette,
+            head,
+            tail,
+            brush,
+            phantom: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn palette(self, palette: Palette) -> Self {
+        Arrow { palette, ..self }
+    }
+
+    #[must_use]
+    pub fn head(self, head: Heads) -> Self {
+        Arrow { head, ..self }
+    }
+
+    /// Sets the glyphs of the tail.
+    ///
+    /// The tail glyph is chosen by the direction in which the arrow points, not the direction of
+    /// the end at which it is drawn.
+    #[must_use]
+    pub fn tail(self, tail: Option<Heads>) -> Self {
+        Arrow { tail, ..self }
+    }
+
+    pub fn into_block<C>(self) -> Block<C>
+    where
+        Block<C>: AxialBlock<L::Axis>,
+        B: Brush<C>,
+        C: Content,
+    {
+        let direction = L::VALUE;
+        let (body, is_reversed) = match direction {
+            valued::Alignment::Horizontal(alignment) => (
+                self.palette.horizontal,
+                alignment == valued::HorizontalAlignment::Left,
+            ),
+            valued::Alignment::Vertical(alignment) => (
+                self.palette.vertical,
+                alignment == valued::VerticalAlignment::Top,
+            ),
+        };
+        let mut glyphs = vec![body; self.length];
+        if let Some(tail) = self.tail.filter(|_| self.length > 1) {
+            glyphs[0] = tail.get(direction);
+        }
+        if let Some(head) = glyphs.last_mut() {
+            *head = self.head.get(direction);
+        }
+        if is_reversed {
+            glyphs.reverse();
+        }
+        paint_along::<L::Axis, _, _>(&glyphs, &self.brush)
+    }
+}
+
+/// Severity of a gauge as determined by its thresholds.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Level {
+    Ok,
+    Warn,
+    Crit,
+}
+
+/// Values associated with each level of a gauge, such as brushes.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Levels<T> {
+    pub ok: T,
+    pub warn: T,
+    pub crit: T,
+}
+
+impl<T> Levels<T> {
+    pub fn get(&self, level: Level) -> &T {
+        match level {
+            Level::Ok => &self.ok,
+            Level::Warn => &self.warn,
+            Level::Crit => &self.crit,
+        }
+    }
+}
+
+/// A horizontal bar that is filled in proportion to a ratio and labeled with a percentage.
+///
+/// The filled portion is drawn with the horizontal glyph of a [`Palette`] and the remainder is
+/// blank. The filled portion and label are painted with the brush of the level of the gauge.
+/// Ratios are clamped to the unit interval. The width of a gauge excludes its label.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gauge<B = Plain> {
+    ratio: f64,
+    width: usize,
+    palette: Palette,
+    brushes: Levels<B>,
+    warn: f64,
+    crit: f64,
+    is_labeled: bool,
+}
+
+impl Gauge {
+    pub fn new(ratio: f64, width: usize) -> Self {
+        Gauge {
+            ratio: if ratio.is_nan() {
+                0.0
+            }
+            else {
+                ratio.clamp(0.0, 1.0)
+            },
+            width,
+            palette: Palette::default(),
+            brushes: Levels::default(),
+            warn: 0.75,
+            crit: 0.9,
+            is_labeled: true,
+        }
+    }
+}
+
+impl<B> Gauge<B> {
+    #[must_use]
+    pub fn palette(self, palette: Palette) -> Self {
+        Gauge { palette, ..self }
+    }
+
+    /// Sets the brushes with which the filled portion and label are painted at each level.
+    #[must_use]
+    pub fn brush<T>(self, brushes: Levels<T>) -> Gauge<T> {
+        let Gauge {
+            ratio,
+            width,
+            palette,
+            warn,
+            crit,
+            is_labeled,
+            ..
+        } = self;
+        Gauge {
+            ratio,
+            width,
+            palette,
+            brushes,
+            warn,
+            crit,
+            is_labeled,
+        }
+    }
+
+    /// Sets the ratios at and above which the gauge is at the warn and crit levels.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either ratio is outside of the unit interval or if the warn ratio
+    /// exceeds the crit ratio.
+    pub fn thresholds(self, warn: f64, crit: f64) -> Result<Self, ThresholdError> {
+        if !((0.0..=1.0).contains(&warn) && (0.0..=1.0).contains(&crit)) {
+            Err(ThresholdError::OutOfRange)
+        }
+        else if warn > crit {
+            Err(ThresholdError::Unordered)
+        }
+        else {
+            Ok(Gauge { warn, crit, ..self })
+        }
+    }
+
+    #[must_use]
+    pub fn labeled(self, is_labeled: bool) -> Self {
+        Gauge { is_labeled, ..self }
+    }
+
+    pub fn level(&self) -> Level {
+        if self.ratio >= self.crit {
+            Level::Crit
+        }
+        else if self.ratio >= self.warn {
+            Level::Warn
+        }
+        else {
+            Level::Ok
+        }
+    }
+
+    pub fn into_block<C>(self) -> Block<C>
+    where
+        B: Brush<C>,
+        C: Content,
+    {
+        let brush = self.brushes.get(self.level());
+        let filled = cmp::min(
+            (self.ratio * self.width as f64).round() as usize,
+            self.width,
+        );
+        let bar =
+            paint_along::<typed::LeftRight, _, _>(&vec![self.palette.horizontal; filled], brush)
+                .pad_at_right(self.width - filled);
+        if self.is_labeled {
+            let label: Vec<_> = format!(" {:>3}%", (self.ratio * 100.0).round() as usize)
+                .chars()
+                .collect();
+            bar.join_left_to_right_at_top(paint_along::<typed::LeftRight, _, _>(&label, brush))
+        }
+        else {
+            bar
+        }
+    }
+}
+
+/// Markers drawn before the items of a list.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Marker {
+    Bullet(char),
+    /// Numbers items in ascending order beginning with the given number.
+    Numbered(usize),
+}
+
+impl Default for Marker {
+    fn default() -> Self {
+        Marker::Bullet('\u{2022}')
+    }
+}
+
+/// Blocks drawn after markers from top to bottom.
+///
+/// Markers are aligned at the right and subsequent lines of an item are indented beneath the
+/// first line of the item.
+#[derive(Clone, Debug)]
+pub struct List<C = String, B = Plain>
+where
+    C: Content,
+{
+    items: Vec<Block<C>>,
+    marker: Marker,
+    brush: B,
+}
+
+impl<C> List<C>
+where
+    C: Content,
+{
+    pub fn new<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = Block<C>>,
+    {
+        List {
+            items: items.into_iter().collect(),
+            marker: Marker::default(),
+            brush: Plain,
+        }
+    }
+}
+
+impl<C, B> List<C, B>
+where
+    C: Content,
+{
+    #[must_use]
+    pub fn marker(self, marker: Marker) -> Self {
+        List { marker, ..self }
+    }
+
+    /// Sets the brush with which markers are painted.
+    #[must_use]
+    pub fn brush<T>(self, brush: T) -> List<C, T> {
+        let List { items, marker, .. } = self;
+        List {
+            items,
+            marker,
+            brush,
+        }
+    }
+
+    pub fn into_block(self) -> Block<C>
+    where
+        B: Brush<C>,
+    {
+        let markers = self.markers();
+        let width = markers
+            .iter()
+            .map(|marker| str_width(marker))
+            .max()
+            .unwrap_or(0);
+        let brush = self.brush;
+        self.items
+            .into_iter()
+            .zip(markers)
+            .map(|(item, marker)| {
+                let glyphs: Vec<_> = marker.chars().collect();
+                paint_along::<typed::LeftRight, _, _>(&glyphs, &brush)
+                    .pad_to_width_at_left(width)
+                    .pad_at_right(1)
+                    .join_left_to_right_at_top(item)
+            })
+            .reduce(Block::join_top_to_bottom_at_left)
+            .unwrap_or_else(Block::zero)
+    }
+
+    fn markers(&self) -> Vec<String> {
+        (0..self.items.len())
+            .map(|index| match self.marker {
+                Marker::Bullet(bullet) => bullet.to_string(),
+                Marker::Numbered(start) => format!("{}.", start + index),
+            })
+            .collect()
+    }
+}
+
+impl<C, S, B> List<Styled<C, S>, B>
+where
+    C: AsRef<str> + Content + From<String>,
+    S: PartialEq + Style,
+{
+    /// Paints markers with the emphasis style of a theme.
+    #[must_use]
+    pub fn theme(self, theme: &Theme<C, S>) -> List<Styled<C, S>, S> {
+        self.brush(theme.emphasis.clone())
+    }
+}
+
+impl<C, B> ToMarkdown for List<C, B>
+where
+    C: Content,
+{
+    fn to_markdown(&self) -> String {
+        let mut output = String::new();
+        for (item, marker) in self.items.iter().zip(self.markers()) {
+            let marker = match self.marker {
+                Marker::Bullet(_) => "-".to_owned(),
+                Marker::Numbered(_) => marker,
+            };
+            for (index, line) in markdown::lines(item).iter().enumerate() {
+                if index == 0 {
+                    output.push_str(&marker);
+                }
+                else {
+                    output.extend(iter::repeat_n(' ', str_width(&marker)));
+                }
+                output.push(' ');
+                output.push_str(&markdown::escape(line));
+                output.push('\n');
+            }
+        }
+        output
+    }
+}
+
+/// A font of multi-line glyphs used to draw banners.
+///
+/// Glyphs are looked up by character. Characters without a glyph are drawn using the glyph for
+/// their uppercase form or, failing that, the glyph for `?`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Font {
+    glyphs: HashMap<char, Vec<String>>,
+    height: usize,
+    spacing: usize,
+}
+
+impl Font {
+    /// Constructs the built-in font of block letters.
+    ///
+    /// Glyphs are five rows tall and separated by one column.
+    pub fn block() -> Self {
+        Font {
+            glyphs: BLOCK_GLYPHS
+                .iter()
+                .map(|(point, rows)| {
+                    (
+                        *point,
+                        rows.iter()
+                            .map(|row| row.replace('#', "\u{2588}"))
+                            .collect(),
+                    )
+                })
+                .collect(),
+            height: 5,
+            spacing: 1,
+        }
+    }
+
+    /// Parses a FIGlet font from the contents of a `.flf` file.
+    ///
+    /// Hard blanks are drawn as spaces and glyphs are drawn at their full width (smushing and
+    /// kerning are not supported).
+    #[cfg(feature = "figlet")]
+    pub fn from_figlet(text: &str) -> Result<Self, FontError> {
+        const GERMAN: [char; 7] = ['Ä', 'Ö', 'Ü', 'ä', 'ö', 'ü', 'ß'];
+
+        let mut lines = text.lines();
+        let header = lines.next().ok_or(FontError::InvalidHeader)?;
+        let mut parameters = header
+            .strip_prefix("flf2a")
+            .ok_or(FontError::InvalidHeader)?
+            .split_whitespace();
+        let hardblank = parameters
+            .next()
+            .and_then(|hardblank| hardblank.chars().next())
+            .ok_or(FontError::InvalidHeader)?;
+        let mut parameter = || -> Result<usize, FontError> {
+            parameters
+                .next()
+                .and_then(|parameter| parameter.parse().ok())
+                .ok_or(FontError::InvalidHeader)
+        };
+        let height = parameter()?;
+        let _baseline = parameter()?;
+        let _max_length = parameter()?;
+        let _layout = parameter()?;
+        let comments = parameter()?;
+        let mut lines = lines.skip(comments);
+        let mut glyph = |point: char| -> Result<Vec<String>, FontError> {
+            (0..height)
+                .map(|_| {
+                    let line = lines.next().ok_or(FontError::MissingGlyph(point))?;
+                    let line = match line.chars().last() {
+                        Some(endmark) => line.trim_end_matches(endmark),
+                        _ => line,
+                    };
+                    Ok(line.replace(hardblank, " "))
+                })
+                .collect()
+        };
+        let mut glyphs = HashMap::new();
+        for point in (' '..='~').chain(GERMAN.iter().copied()) {
+            match glyph(point) {
+                Ok(rows) => {
+                    glyphs.insert(point, rows);
+                }
+                // The German glyphs are optional.
+                Err(_) if GERMAN.contains(&point) => break,
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(Font {
+            glyphs,
+            height,
+            spacing: 0,
+        })
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn glyph(&self, point: char) -> Option<&[String]> {
+        self.glyphs
+            .get(&point)
+            .or_else(|| {
+                point
+                    .to_uppercase()
+                    .next()
+                    .and_then(|point| self.glyphs.get(&point))
+            })
+            .or_else(|| self.glyphs.get(&'?'))
+            .map(Vec::as_slice)
+    }
+}
+
+impl Default for Font {
+    fn default() -> Self {
+        Font::block()
+    }
+}
+
+/// Draws text with the glyphs of a font.
+///
+/// Each line of the text is drawn as a row of glyphs and rows are joined from top to bottom.
+/// Glyphs are expected to consist of characters that occupy a single column.
+pub fn banner<C>(text: &str, font: &Font) -> Block<C>
+where
+    C: Content + FromCell<char>,
+{
+    let rows: Vec<Vec<char>> = text
+        .lines()
+        .flat_map(|line| {
+            (0..font.height).map(move |y| {
+                let mut row = vec![];
+                for (index, glyph) in line
+                    .chars()
+                    .filter_map(|point| font.glyph(point))
+                    .enumerate()
+                {
+                    if index > 0 {
+                        row.extend(iter::repeat_n(' ', font.spacing));
+                    }
+                    let width = glyph
+                        .iter()
+                        .map(|row| row.chars().count())
+                        .max()
+                        .unwrap_or(0);
+                    let cells = glyph.get(y).map(String::as_str).unwrap_or("");
+                    row.extend(cells.chars().chain(iter::repeat(' ')).take(width));
+                }
+                row
+            })
+        })
+        .collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if width == 0 {
+        return Block::zero();
+    }
+    Block::with_dimensions(width, rows.len()).fill(block::from_fn(|x, y| {
+        rows[y].get(x).copied().unwrap_or(' ')
+    }))
+}
+
+/// Draws line segments between consecutive points onto an empty block.
+///
+/// Points are given as columns and rows and the block is just large enough to contain them.
+/// Segments may be diagonal and cells where segments with differing glyphs intersect are drawn
+/// with the crossing glyph of the palette.
+pub fn polyline<C, I>(points: I, palette: &Palette) -> Block<C>
+where
+    C: Content + FromCell<char>,
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    let points: Vec<_> = points.into_iter().collect();
+    let width = points.iter().map(|(x, _)| x + 1).max().unwrap_or(0);
+    let height = points.iter().map(|(_, y)| y + 1).max().unwrap_or(0);
+    let mut cells = vec![None; width * height];
+    for (n, segment) in points.windows(2).enumerate() {
+        let (start, end) = (segment[0], segment[1]);
+        // The first point of subsequent segments is the last point of the previous segment.
+        for (x, y, glyph) in rasterize(start, end, palette).skip(if n == 0 { 0 } else { 1 }) {
+            let cell = &mut cells[(y * width) + x];
+            *cell = match *cell {
+                Some(previous) if previous != glyph => Some(palette.crossing),
+                _ => Some(glyph),
+            };
+        }
+    }
+    Block::with_dimensions(width, height)
+        .fill(block::from_fn(|x, y| cells[(y * width) + x].unwrap_or(' ')))
+}
+
+// Rasterizes a line segment using Bresenham's algorithm. Each cell is drawn with the glyph for
+// the step that reaches it. The first cell is drawn with the glyph of the first step.
+fn rasterize(
+    start: (usize, usize),
+    end: (usize, usize),
+    palette: &Palette,
+) -> impl '_ + Iterator<Item = (usize, usize, char)> {
+    let (x0, y0) = (start.0 as isize, start.1 as isize);
+    let (x1, y1) = (end.0 as isize, end.1 as isize);
+    let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+    let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+    let n = cmp::max(dx, -dy) as usize;
+    let mut error = dx + dy;
+    let (mut x, mut y) = (x0, y0);
+    let mut steps: Vec<_> = (0..n)
+        .map(|_| {
+            let (mut stepx, mut stepy) = (0, 0);
+            let twice = 2 * error;
+            if twice >= dy {
+                error += dy;
+                x += sx;
+                stepx = sx;
+            }
+            if twice <= dx {
+                error += dx;
+                y += sy;
+                stepy = sy;
+            }
+            (x as usize, y as usize, palette.segment(stepx, stepy))
+        })
+        .collect();
+    let first = steps
+        .first()
+        .map_or(palette.segment(sx, sy), |&(_, _, glyph)| glyph);
+    steps.insert(0, (start.0, start.1, first));
+    steps.into_iter()
+}
+
+/// Places two blocks side by side with a gutter of change markers between them.
+///
+/// Both blocks are truncated or padded to `width` columns and padded to the same height. Lines
+/// that differ are marked with `|` and lines that are only present in the left or right block are
+/// marked with `<` or `>`, respectively.
+pub fn diff_side_by_side<C>(left: Block<C>, right: Block<C>, width: usize) -> Block<C>
+where
+    C: Content,
+{
+    let (left, right, gutter, _) = diff_columns(left, right, width);
+    left.join_left_to_right_at_top(gutter)
+        .join_left_to_right_at_top(right)
+}
+
+/// Places two blocks side by side like `diff_side_by_side` and restyles the cells that differ.
+pub fn diff_side_by_side_styled<C, S>(
+    left: Block<Styled<C, S>>,
+    right: Block<Styled<C, S>>,
+    width: usize,
+    style: S,
+) -> Block<Styled<C, S>>
+where
+    C: AsRef<str> + Content + From<String>,
+    S: PartialEq + Style,
+{
+    let (left, right, gutter, mask) = diff_columns(left, right, width);
+    left.restyle_masked(&mask, style.clone())
+        .join_left_to_right_at_top(gutter)
+        .join_left_to_right_at_top(right.restyle_masked(&mask, style))
+}
+
+// Truncates and pads blocks to the given width and height and compares them.
+//
+// Lines are compared after they are truncated, so differences beyond the width are not marked.
+// Returns the blocks, the gutter of change markers, and a mask of the cells that differ.
+fn diff_columns<C>(
+    left: Block<C>,
+    right: Block<C>,
+    width: usize,
+) -> (Block<C>, Block<C>, Block<C>, Mask)
+where
+    C: Content,
+{
+    let heights = (left.height(), right.height());
+    let height = cmp::max(heights.0, heights.1);
+    let [left, right] = [left, right].map(|block| {
+        block
+            .truncate_to_width_at_right(width, None)
+            .pad_to_width_at_right(width)
+            .pad_to_height_at_bottom(height)
+    });
+    let mut mask = Mask::with_dimensions(width, height);
+    let (front, back) = (left.cells(), right.cells());
+    let markers: Vec<_> = front
+        .iter()
+        .zip(back.iter())
+        .enumerate()
+        .map(|(y, (front, back))| {
+            let mut is_different = false;
+            for (column, n, _) in block::diff_cells(front, back).filter(|cell| cell.2) {
+                is_different = true;
+                for x in column..cmp::min(column + n, width) {
+                    mask.set(x, y, true);
+                }
+            }
+            if y >= heights.1 {
+                '<'
+            }
+            else if y >= heights.0 {
+                '>'
+            }
+            else if is_different {
+                '|'
+            }
+            else {
+                ' '
+            }
+        })
+        .collect();
+    let gutter = Block::with_dimensions(3, height).fill(block::from_fn(|x, y| {
+        if x == 1 {
+            markers[y]
+        }
+        else {
+            ' '
+        }
+    }));
+    (left, right, gutter, mask)
+}
+
+/// Presentation of lines of code that are wider than a code block.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CodeOverflow {
+    /// Truncates lines and ends them with the given indicator.
+    Truncate(char),
+    /// Wraps lines and marks continuation lines in the gutter with the given indicator.
+    Wrap(char),
+}
+
+impl Default for CodeOverflow {
+    fn default() -> Self {
+        CodeOverflow::Truncate('\u{2026}')
+    }
+}
+
+/// Source code with a gutter of line numbers.
+///
+/// Tabs are expanded before lines are styled, such as by a syntax highlighter, so styling
+/// functions receive text with the same widths as the code block.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CodeBlock<'t> {
+    source: &'t str,
+    width: Option<usize>,
+    overflow: CodeOverflow,
+    tab_width: usize,
+    start: usize,
+}
+
+impl<'t> CodeBlock<'t> {
+    pub fn new(source: &'t str) -> Self {
+        CodeBlock {
+            source,
+            width: None,
+            overflow: CodeOverflow::default(),
+            tab_width: SanitizePolicy::default().tab_width,
+            start: 1,
+        }
+    }
+
+    /// Sets the width of lines of code, excluding the gutter.
+    #[must_use]
+    pub fn width(self, width: usize) -> Self {
+        CodeBlock {
+            width: Some(width),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn overflow(self, overflow: CodeOverflow) -> Self {
+        CodeBlock { overflow, ..self }
+    }
+
+    #[must_use]
+    pub fn tab_width(self, tab_width: usize) -> Self {
+        CodeBlock { tab_width, ..self }
+    }
+
+    /// Sets the number of the first line.
+    #[must_use]
+    pub fn start(self, start: usize) -> Self {
+        CodeBlock { start, ..self }
+    }
+
+    pub fn into_block_with<C, S>(
+        self,
+        mut f: impl FnMut(&str) -> Styled<C, S>,
+    ) -> Block<Styled<C, S>>
+    where
+        C: AsRef<str> + Content + From<String>,
+        S: PartialEq + Style,
+    {
+        let policy = SanitizePolicy {
+            tab_width: self.tab_width,
+            ..Default::default()
+        };
+        let lines: Vec<_> = self.source.lines().collect();
+        let digits = (self.start + lines.len().saturating_sub(1))
+            .to_string()
+            .len();
+        lines
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, line)| {
+                let line = f(&String::from(line).sanitize(&policy));
+                let (rows, indicator) = match (self.width, self.overflow) {
+                    (Some(width), CodeOverflow::Wrap(indicator)) => (
+                        wrap::wrap_by(line, width, WrapPolicy::BreakAnywhere),
+                        indicator,
+                    ),
+                    (Some(width), CodeOverflow::Truncate(indicator)) => {
+                        let policy = OverflowPolicy::Ellipsis(Grapheme::from(indicator));
+                        let line = match line.truncate_to_width_with(width, policy) {
+                            Ok((line, _)) | Err(line) => line,
+                        };
+                        (vec![line], ' ')
+                    }
+                    (None, _) => (vec![line], ' '),
+                };
+                let number = self.start + index;
+                rows.into_iter().enumerate().map(move |(row, line)| {
+                    let gutter = if row == 0 {
+                        format!("{:>digits$} ", number, digits = digits)
+                    }
+                    else {
+                        format!("{:>digits$} ", indicator, digits = digits)
+                    };
+                    Content::concatenate(Styled::plain(gutter), line)
+                })
+            })
+            .collect()
+    }
+}
+
+#[rustfmt::skip]
+const BLOCK_GLYPHS: &[(char, [&str; 5])] = &[
+    ('A', [" # ", "# #", "###", "# #", "# #"]),
+    ('B', ["## ", "# #", "## ", "# #", "## "]),
+    ('C', [" ##", "#  ", "#  ", "#  ", " ##"]),
+    ('D', ["## ", "# #", "# #", "# #", "## "]),
+    ('E', ["###", "#  ", "## ", "#  ", "###"]),
+    ('F', ["###", "#  ", "## ", "#  ", "#  "]),
+    ('G', [" ##", "#  ", "# #", "# #", " ##"]),
+    ('H', ["# #", "# #", "###", "# #", "# #"]),
+    ('I', ["###", " # ", " # ", " # ", "###"]),
+    ('J', ["  #", "  #", "  #", "# #", " # "]),
+    ('K', ["# #", "# #", "## ", "# #", "# #"]),
+    ('L', ["#  ", "#  ", "#  ", "#  ", "###"]),
+    ('M', ["#   #", "## ##", "# # #", "#   #", "#   #"]),
+    ('N', ["#  #", "## #", "# ##", "#  #", "#  #"]),
+    ('O', [" # ", "# #", "# #", "# #", " # "]),
+    ('P', ["## ", "# #", "## ", "#  ", "#  "]),
+    ('Q', [" # ", "# #", "# #", "## ", " ##"]),
+    ('R', ["## ", "# #", "## ", "# #", "# #"]),
+    ('S', [" ##", "#  ", " # ", "  #", "## "]),
+    ('T', ["###", " # ", " # ", " # ", " # "]),
+    ('U', ["# #", "# #", "# #", "# #", "###"]),
+    ('V', ["# #", "# #", "# #", "# #", " # "]),
+    ('W', ["#   #", "#   #", "# # #", "## ##", "#   #"]),
+    ('X', ["# #", "# #", " # ", "# #", "# #"]),
+    ('Y', ["# #", "# #", " # ", " # ", " # "]),
+    ('Z', ["###", "  #", " # ", "#  ", "###"]),
+    ('0', ["###", "# #", "# #", "# #", "###"]),
+    ('1', [" # ", "## ", " # ", " # ", "###"]),
+    ('2', ["## ", "  #", " # ", "#  ", "###"]),
+    ('3', ["## ", "  #", " # ", "  #", "## "]),
+    ('4', ["# #", "# #", "###", "  #", "  #"]),
+    ('5', ["###", "#  ", "## ", "  #", "## "]),
+    ('6', [" ##", "#  ", "###", "# #", "###"]),
+    ('7', ["###", "  #", " # ", " # ", " # "]),
+    ('8', ["###", "# #", "###", "# #", "###"]),
+    ('9', ["###", "# #", "###", "  #", "## "]),
+    (' ', ["  ", "  ", "  ", "  ", "  "]),
+    ('!', ["#", "#", "#", " ", "#"]),
+    ('?', ["## ", "  #", " # ", "   ", " # "]),
+    ('.', [" ", " ", " ", " ", "#"]),
+    (',', [" ", " ", " ", "#", "#"]),
+    ('-', ["   ", "   ", "###", "   ", "   "]),
+    (':', [" ", "#", " ", "#", " "]),
+    ('\'', ["#", "#", " ", " ", " "]),
+    ('"', ["# #", "# #", "   ", "   ", "   "]),
+];
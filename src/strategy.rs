@@ -0,0 +1,79 @@
+use std::cmp;
+
+use proptest::collection;
+use proptest::prelude::*;
+
+use crate::block::Block;
+use crate::content::{Content, Grapheme, Style, Styled};
+
+/// Generates graphemes of narrow, wide, and combining text.
+pub fn grapheme() -> impl Strategy<Value = Grapheme<'static>> {
+    prop_oneof![
+        4 => (b' '..=b'~').prop_map(|byte| Grapheme::from(char::from(byte))),
+        1 => proptest::char::range('\u{4E00}', '\u{9FFF}').prop_map(Grapheme::from),
+        1 => (
+            proptest::char::range('a', 'z'),
+            proptest::char::range('\u{300}', '\u{36F}'),
+        )
+            .prop_map(|(base, mark)| {
+                Grapheme::try_from(format!("{}{}", base, mark).as_str())
+                    .unwrap()
+                    .into_owned()
+            }),
+    ]
+}
+
+/// Generates text with at most `lines` lines of at most `graphemes` graphemes each.
+pub fn text(lines: usize, graphemes: usize) -> impl Strategy<Value = String> {
+    collection::vec(
+        collection::vec(grapheme(), 0..=graphemes),
+        1..=cmp::max(lines, 1),
+    )
+    .prop_map(|lines| {
+        lines
+            .into_iter()
+            .map(|line| line.iter().map(Grapheme::get).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+}
+
+pub fn content<C>(lines: usize, graphemes: usize) -> impl Strategy<Value = C>
+where
+    C: Content + From<String>,
+{
+    text(lines, graphemes).prop_map(C::from)
+}
+
+/// Generates styled content from fragments with styles drawn from the given strategy.
+pub fn styled<C, S>(
+    style: impl Strategy<Value = S>,
+    lines: usize,
+    graphemes: usize,
+) -> impl Strategy<Value = Styled<C, S>>
+where
+    C: AsRef<str> + Content + From<String>,
+    S: PartialEq + Style,
+{
+    collection::vec((style, text(lines, graphemes)), 1..=4).prop_map(|fragments| {
+        fragments
+            .into_iter()
+            .map(|(style, text)| Styled::new(style, text))
+            .reduce(Styled::concatenate)
+            .unwrap()
+    })
+}
+
+/// Generates blocks with at most `height` lines of at most `width` graphemes each.
+///
+/// Both empty blocks and blocks with content are generated.
+pub fn block<C>(width: usize, height: usize) -> impl Strategy<Value = Block<C>>
+where
+    C: Content + From<String>,
+{
+    prop_oneof![
+        1 => (0..=width, 0..=height)
+            .prop_map(|(width, height)| Block::with_dimensions(width, height)),
+        4 => content::<C>(height, width).prop_map(Block::with_content),
+    ]
+}
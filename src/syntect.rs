@@ -0,0 +1,39 @@
+use syntect::highlighting::{self, Color, FontStyle};
+
+use crate::ansi::AnsiStyle;
+use crate::content::{Content, Styled};
+
+impl From<highlighting::Style> for AnsiStyle {
+    fn from(style: highlighting::Style) -> Self {
+        fn rgb(color: Color) -> Option<[u8; 3]> {
+            (color.a != 0).then_some([color.r, color.g, color.b])
+        }
+
+        AnsiStyle {
+            foreground: rgb(style.foreground),
+            background: rgb(style.background),
+            is_bold: style.font_style.contains(FontStyle::BOLD),
+            is_italic: style.font_style.contains(FontStyle::ITALIC),
+            is_underlined: style.font_style.contains(FontStyle::UNDERLINE),
+        }
+    }
+}
+
+/// Converts the highlighted ranges of a line into styled content.
+///
+/// Line endings are removed from the ranges, so the ranges produced by highlighting lines with
+/// their endings can be used directly.
+pub fn styled<C>(ranges: &[(highlighting::Style, &str)]) -> Styled<C, AnsiStyle>
+where
+    C: AsRef<str> + Content + From<String>,
+{
+    ranges
+        .iter()
+        .map(|(style, text)| {
+            Styled::new(
+                AnsiStyle::from(*style),
+                text.trim_end_matches(['\n', '\r']).to_owned(),
+            )
+        })
+        .fold(Content::empty(), Content::concatenate)
+}
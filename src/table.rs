@@ -0,0 +1,1054 @@
+use std::cmp::{self, Ordering};
+#[cfg(feature = "delimited")]
+use std::io::{self, Read};
+use std::iter;
+use std::mem;
+
+use itertools::Itertools as _;
+
+use crate::align::typed::{LeftRight, TopBottom};
+use crate::align::valued::HorizontalAlignment;
+use crate::block::{Block, DynamicallyAligned as _, Fill};
+use crate::content::{Content, FromCell, Style, Styled};
+use crate::markdown::{self, ToMarkdown};
+use crate::primitive::{self, Brush, Plain, Stroke, Theme};
+use crate::wrap::WrapPolicy;
+
+/// Presentation of a column of a table.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Column {
+    pub header: String,
+    pub width: Option<usize>,
+    pub alignment: HorizontalAlignment,
+}
+
+impl Column {
+    pub fn new(header: impl Into<String>) -> Self {
+        Column {
+            header: header.into(),
+            width: None,
+            alignment: HorizontalAlignment::Left,
+        }
+    }
+
+    #[must_use]
+    pub fn width(self, width: usize) -> Self {
+        Column {
+            width: Some(width),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn align(self, alignment: HorizontalAlignment) -> Self {
+        Column { alignment, ..self }
+    }
+}
+
+/// Types that can be presented as rows of a table.
+///
+/// See the `tabulate` macro for implementing this trait for structs with fields that implement
+/// `Display`.
+pub trait Tabulate {
+    fn columns() -> Vec<Column>;
+
+    fn cells(&self) -> Vec<String>;
+}
+
+/// Implements `Tabulate` for a struct with a column for each of the given fields.
+///
+/// Each field is given a header and, optionally, a column width and alignment. Cells are
+/// formatted using `Display`.
+///
+/// # Examples
+///
+/// ```rust
+/// use hako::align::valued::HorizontalAlignment;
+/// use hako::tabulate;
+///
+/// struct Process {
+///     name: String,
+///     id: u32,
+/// }
+///
+/// tabulate!(Process {
+///     name: "Name",
+///     id: "PID" => (None, HorizontalAlignment::Right),
+/// });
+/// ```
+#[macro_export]
+macro_rules! tabulate {
+    ($t:ty { $($field:ident : $header:expr $(=> ($width:expr, $alignment:expr))?),* $(,)? }) => {
+        impl $crate::table::Tabulate for $t {
+            fn columns() -> ::std::vec::Vec<$crate::table::Column> {
+                ::std::vec![$({
+                    #[allow(unused_mut)]
+                    let mut column = $crate::table::Column::new($header);
+                    $(
+                        column.width = $width;
+                        column.alignment = $alignment;
+                    )?
+                    column
+                }),*]
+            }
+
+            fn cells(&self) -> ::std::vec::Vec<::std::string::String> {
+                ::std::vec![$(::std::string::ToString::to_string(&self.$field)),*]
+            }
+        }
+    };
+}
+
+/// Vertical alignment of cells within their rows.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum RowAlignment {
+    #[default]
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// A block in a table that may span multiple columns and rows.
+#[derive(Clone, Debug)]
+pub struct Cell<C = String>
+where
+    C: Content,
+{
+    block: Block<C>,
+    columns: usize,
+    rows: usize,
+    vertical: Option<RowAlignment>,
+}
+
+impl<C> Cell<C>
+where
+    C: Content,
+{
+    pub fn new(block: Block<C>) -> Self {
+        Cell {
+            block,
+            columns: 1,
+            rows: 1,
+            vertical: None,
+        }
+    }
+
+    /// Sets the number of columns spanned by the cell.
+    #[must_use]
+    pub fn span_columns(self, columns: usize) -> Self {
+        Cell {
+            columns: cmp::max(columns, 1),
+            ..self
+        }
+    }
+
+    /// Sets the number of rows spanned by the cell.
+    ///
+    /// Cells do not span rows beyond the header or body in which they are placed.
+    #[must_use]
+    pub fn span_rows(self, rows: usize) -> Self {
+        Cell {
+            rows: cmp::max(rows, 1),
+            ..self
+        }
+    }
+
+    /// Sets the vertical alignment of the cell, overriding the alignment of the table.
+    #[must_use]
+    pub fn align_vertical(self, vertical: RowAlignment) -> Self {
+        Cell {
+            vertical: Some(vertical),
+            ..self
+        }
+    }
+
+    pub fn get(&self) -> &Block<C> {
+        &self.block
+    }
+
+    pub fn into_block(self) -> Block<C> {
+        self.block
+    }
+}
+
+impl<C> From<Block<C>> for Cell<C>
+where
+    C: Content,
+{
+    fn from(block: Block<C>) -> Self {
+        Cell::new(block)
+    }
+}
+
+/// Rows of cells drawn within a border with rules between columns and below the header.
+///
+/// Columns are as wide as their widest cell and rows are as tall as their tallest cell. Cells
+/// that span columns or rows widen or heighten the columns or rows that they span as evenly as
+/// possible. Cells are placed from left to right in the first slots that are not occupied by
+/// cells spanning from previous rows and missing cells in ragged rows are blank.
+#[derive(Clone, Debug)]
+pub struct Table<C = String, B = Plain>
+where
+    C: Content,
+{
+    header: Vec<Vec<Cell<C>>>,
+    body: Vec<Vec<Cell<C>>>,
+    alignments: Vec<HorizontalAlignment>,
+    widths: Vec<Option<usize>>,
+    max_width: Option<usize>,
+    wrap: Option<WrapPolicy>,
+    vertical: RowAlignment,
+    stroke: Stroke,
+    brush: B,
+    padding: usize,
+    filler: Option<C>,
+}
+
+impl<C> Table<C>
+where
+    C: Content,
+{
+    pub fn new<R, I>(rows: R) -> Self
+    where
+        R: IntoIterator<Item = I>,
+        I: IntoIterator,
+        I::Item: Into<Cell<C>>,
+    {
+        Table {
+            header: vec![],
+            body: rows
+                .into_iter()
+                .map(|row| row.into_iter().map(Into::into).collect())
+                .collect(),
+            alignments: vec![],
+            widths: vec![],
+            max_width: None,
+            wrap: None,
+            vertical: RowAlignment::Top,
+            stroke: Stroke::default(),
+            brush: Plain,
+            padding: 1,
+            filler: None,
+        }
+    }
+
+    /// Constructs a table with a header and a row for each item.
+    pub fn from_rows<T>(rows: &[T]) -> Self
+    where
+        C: From<String>,
+        T: Tabulate,
+    {
+        let columns = T::columns();
+        let mut table = Table::new(
+            rows.iter()
+                .map(|row| row.cells().into_iter().map(Block::with_content)),
+        )
+        .header(
+            columns
+                .iter()
+                .map(|column| Block::with_content(column.header.clone())),
+        );
+        for (x, column) in columns.into_iter().enumerate() {
+            table = table.align(x, column.alignment);
+            if let Some(width) = column.width {
+                table = table.column_width(x, width);
+            }
+        }
+        table
+    }
+
+    /// Parses delimited text, such as CSV or TSV, into a table.
+    ///
+    /// The first row forms the header. Fields may be quoted with `"` to include delimiters, line
+    /// breaks, and escaped quotes (`""`). Columns in which every non-empty field is numeric are
+    /// aligned at the right.
+    #[cfg(feature = "delimited")]
+    pub fn from_delimited(mut reader: impl Read, delimiter: char) -> io::Result<Self>
+    where
+        C: From<String>,
+    {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        let mut rows = parse_delimited(&text, delimiter).into_iter();
+        let header = rows.next().unwrap_or_default();
+        let body: Vec<_> = rows.collect();
+        let n = body.iter().map(Vec::len).fold(header.len(), cmp::max);
+        let mut table = Table::new(
+            body.iter()
+                .map(|row| row.iter().cloned().map(Block::with_content)),
+        )
+        .header(header.into_iter().map(Block::with_content));
+        for column in 0..n {
+            let mut fields = body
+                .iter()
+                .filter_map(|row| row.get(column))
+                .map(|field| field.trim())
+                .filter(|field| !field.is_empty())
+                .peekable();
+            let is_numeric = fields.peek().is_some()
+                && fields.all(|field| field.trim_end_matches('%').parse::<f64>().is_ok());
+            if is_numeric {
+                table = table.align(column, HorizontalAlignment::Right);
+            }
+        }
+        Ok(table)
+    }
+}
+
+impl<C, B> Table<C, B>
+where
+    C: Content,
+    B: Brush<C>,
+{
+    /// Appends a row to the header.
+    #[must_use]
+    pub fn header<I>(mut self, row: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Cell<C>>,
+    {
+        self.header.push(row.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the horizontal alignment of the cells in a column.
+    ///
+    /// Columns are aligned at the left by default.
+    #[must_use]
+    pub fn align(mut self, column: usize, alignment: HorizontalAlignment) -> Self {
+        if self.alignments.len() <= column {
+            self.alignments
+                .resize(column + 1, HorizontalAlignment::Left);
+        }
+        self.alignments[column] = alignment;
+        self
+    }
+
+    /// Sets the width of a column, excluding padding.
+    ///
+    /// Cells that are wider than the column are wrapped or truncated.
+    #[must_use]
+    pub fn column_width(mut self, column: usize, width: usize) -> Self {
+        if self.widths.len() <= column {
+            self.widths.resize(column + 1, None);
+        }
+        self.widths[column] = Some(width);
+        self
+    }
+
+    /// Limits the width of the table, including its border and rules.
+    ///
+    /// The widest columns that are not sized explicitly are narrowed until the table fits or no
+    /// column can be narrowed further. Cells that are wider than their columns are wrapped or
+    /// truncated.
+    #[must_use]
+    pub fn max_width(self, width: usize) -> Self {
+        Table {
+            max_width: Some(width),
+            ..self
+        }
+    }
+
+    /// Wraps cells that are wider than their columns using the given policy.
+    ///
+    /// Cells are truncated by default.
+    #[must_use]
+    pub fn wrap(self, policy: WrapPolicy) -> Self {
+        Table {
+            wrap: Some(policy),
+            ..self
+        }
+    }
+
+    /// Sets the vertical alignment of cells that are shorter than their rows.
+    ///
+    /// Cells are aligned at the top by default.
+    #[must_use]
+    pub fn align_rows(self, vertical: RowAlignment) -> Self {
+        Table { vertical, ..self }
+    }
+
+    #[must_use]
+    pub fn stroke(self, stroke: Stroke) -> Self {
+        Table { stroke, ..self }
+    }
+
+    /// Sets the brush with which the border and rules are painted.
+    #[must_use]
+    pub fn brush<T>(self, brush: T) -> Table<C, T>
+    where
+        T: Brush<C>,
+    {
+        let Table {
+            header,
+            body,
+            alignments,
+            widths,
+            max_width,
+            wrap,
+            vertical,
+            stroke,
+            padding,
+            filler,
+            ..
+        } = self;
+        Table {
+            header,
+            body,
+            alignments,
+            widths,
+            max_width,
+            wrap,
+            vertical,
+            stroke,
+            brush,
+            padding,
+            filler,
+        }
+    }
+
+    /// Sets the number of blank columns on either side of each cell.
+    #[must_use]
+    pub fn padding(self, padding: usize) -> Self {
+        Table { padding, ..self }
+    }
+
+    /// Fills the padding on either side of each cell with the given content.
+    ///
+    /// Padding is blank by default.
+    #[must_use]
+    pub fn fill_padding(self, filler: C) -> Self {
+        Table {
+            filler: Some(filler),
+            ..self
+        }
+    }
+
+    /// Draws the table.
+    ///
+    /// Spanning cells are aligned like the first column that they span.
+    pub fn into_block(self) -> Block<C> {
+        match self.layout() {
+            Some(mut layout) => {
+                let rows = mem::take(&mut layout.rows);
+                layout.page(rows.into_iter().enumerate())
+            }
+            _ => Block::zero(),
+        }
+    }
+
+    /// Draws the table onto pages that are at most `height` rows tall.
+    ///
+    /// The border and header are drawn on every page. Pages contain as many rows of the body as
+    /// fit, but always contain at least one row, so pages may be taller than `height` if rows are
+    /// tall.
+    pub fn into_pages(self, height: usize) -> impl Iterator<Item = Block<C>> {
+        let mut pages = vec![];
+        if let Some(mut layout) = self.layout() {
+            let split = layout.split;
+            let mut body = layout
+                .rows
+                .split_off(split)
+                .into_iter()
+                .enumerate()
+                .peekable();
+            let header: Vec<_> = mem::take(&mut layout.rows)
+                .into_iter()
+                .enumerate()
+                .collect();
+            let frame = header.iter().map(|(_, row)| row.height()).sum::<usize>()
+                + if split > 0 { 3 } else { 2 };
+            let available = height.saturating_sub(frame);
+            loop {
+                let mut rows = header.clone();
+                let mut length = 0;
+                while let Some((y, row)) = body
+                    .next_if(|(_, row)| rows.len() == split || (length + row.height()) <= available)
+                {
+                    length += row.height();
+                    rows.push((y + split, row));
+                }
+                pages.push(layout.page(rows.into_iter()));
+                if body.peek().is_none() {
+                    break;
+                }
+            }
+        }
+        pages.into_iter()
+    }
+
+    fn layout(self) -> Option<Layout<C, B>> {
+        let Table {
+            header,
+            body,
+            alignments,
+            widths: fixed,
+            max_width,
+            wrap,
+            vertical,
+            stroke,
+            brush,
+            padding,
+            filler,
+        } = self;
+        let (split, m) = (header.len(), header.len() + body.len());
+        let placements = place_sections(&header, &body);
+        let n = placements
+            .iter()
+            .map(|placement| placement.x + placement.columns)
+            .max()
+            .unwrap_or(0);
+        if n == 0 {
+            return None;
+        }
+        let cells: Vec<_> = header.into_iter().chain(body).flatten().collect();
+        let gap = (padding * 2) + 1;
+        let is_fixed: Vec<_> = (0..n)
+            .map(|x| fixed.get(x).copied().flatten().is_some())
+            .collect();
+        // Widths and heights exclude padding and rules.
+        let mut widths: Vec<_> = (0..n)
+            .map(|x| fixed.get(x).copied().flatten().unwrap_or(0))
+            .collect();
+        let mut spans: Vec<_> = placements.iter().zip(cells.iter()).collect();
+        spans.sort_by_key(|(placement, _)| placement.columns);
+        for (placement, cell) in spans {
+            let columns = placement.x..(placement.x + placement.columns);
+            distribute(
+                &mut widths[columns.clone()],
+                &is_fixed[columns],
+                (placement.columns - 1) * gap,
+                cell.block.width(),
+            );
+        }
+        if let Some(width) = max_width {
+            shrink(&mut widths, &is_fixed, width.saturating_sub((n * gap) + 1));
+        }
+        let span_width = |placement: &Placement| {
+            widths[placement.x..(placement.x + placement.columns)]
+                .iter()
+                .sum::<usize>()
+                + ((placement.columns - 1) * gap)
+        };
+        let cells: Vec<_> = cells
+            .into_iter()
+            .zip(placements.iter())
+            .map(|(cell, placement)| {
+                let width = span_width(placement);
+                let block = match wrap {
+                    Some(policy) if cell.block.width() > width => {
+                        cell.block.reflow_by(width, policy)
+                    }
+                    _ => cell.block,
+                };
+                Cell {
+                    block: block.truncate_to_width_at_right(width, None),
+                    ..cell
+                }
+            })
+            .collect();
+        let mut heights = vec![1; m];
+        let mut spans: Vec<_> = placements.iter().zip(cells.iter()).collect();
+        spans.sort_by_key(|(placement, _)| placement.rows);
+        for (placement, cell) in spans {
+            distribute(
+                &mut heights[placement.y..(placement.y + placement.rows)],
+                &vec![false; placement.rows],
+                0,
+                cell.block.height(),
+            );
+        }
+        // Rules are interrupted between the columns of a spanning cell.
+        let mut is_spanned = vec![vec![false; n + 1]; m];
+        for placement in placements.iter() {
+            for row in &mut is_spanned[placement.y..(placement.y + placement.rows)] {
+                for boundary in &mut row[(placement.x + 1)..(placement.x + placement.columns)] {
+                    *boundary = true;
+                }
+            }
+        }
+        let pad = |height| {
+            let block = Block::with_dimensions(padding, height);
+            match filler.clone() {
+                Some(filler) => block.fill(filler),
+                _ => block,
+            }
+        };
+        let mut cells = placements.into_iter().zip(cells).peekable();
+        let mut active: Vec<Option<(usize, Block<C>)>> = vec![None; n];
+        let mut rows = Vec::with_capacity(m);
+        for (y, &height) in heights.iter().enumerate() {
+            while let Some((placement, cell)) = cells.next_if(|(placement, _)| placement.y == y) {
+                let width = span_width(&placement);
+                let height = heights[y..(y + placement.rows)].iter().sum();
+                let alignment = alignments
+                    .get(placement.x)
+                    .copied()
+                    .unwrap_or(HorizontalAlignment::Left);
+                let block = cell.block.pad_to_length(alignment.opposite(), width);
+                let block = match cell.vertical.unwrap_or(vertical) {
+                    RowAlignment::Top => block.pad_to_height_at_bottom(height),
+                    RowAlignment::Middle => block.pad_to_length_centered_at::<TopBottom>(height),
+                    RowAlignment::Bottom => block.pad_to_height_at_top(height),
+                };
+                let block = pad(height)
+                    .join_left_to_right_at_top(block)
+                    .join_left_to_right_at_top(pad(height));
+                active[placement.x] = Some((placement.columns, block));
+            }
+            let vertical =
+                primitive::paint_along::<TopBottom, _, _>(&vec![stroke.vertical; height], &brush);
+            let mut row = vertical.clone();
+            let mut x = 0;
+            while x < n {
+                let (columns, piece) = match active[x].take() {
+                    Some((columns, block)) => {
+                        let (piece, remainder) = block.split_at_height(height);
+                        if remainder.height() > 0 {
+                            active[x] = Some((columns, remainder));
+                        }
+                        (columns, piece)
+                    }
+                    _ => (1, Block::with_dimensions(widths[x] + (padding * 2), height)),
+                };
+                // Discard any cells that overlap a spanning cell.
+                for slot in active.iter_mut().take(x + columns).skip(x + 1) {
+                    *slot = None;
+                }
+                row = row
+                    .join_left_to_right_at_top(piece)
+                    .join_left_to_right_at_top(vertical.clone());
+                x += columns;
+            }
+            rows.push(row);
+        }
+        Some(Layout {
+            rows,
+            split,
+            widths,
+            is_spanned,
+            stroke,
+            brush,
+            padding,
+        })
+    }
+}
+
+/// Typed rows that are presented as a table.
+///
+/// The cells of each row are formatted once when the row is inserted, so sorting, filtering, and
+/// selecting columns only rearranges existing cells when the table is rebuilt.
+#[derive(Clone, Debug)]
+pub struct Records<T>
+where
+    T: Tabulate,
+{
+    rows: Vec<(T, Vec<String>)>,
+    columns: Vec<Column>,
+    order: Vec<usize>,
+    is_visible: Vec<bool>,
+    selection: Vec<usize>,
+}
+
+impl<T> Records<T>
+where
+    T: Tabulate,
+{
+    pub fn new<I>(rows: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let rows: Vec<_> = rows
+            .into_iter()
+            .map(|row| {
+                let cells = row.cells();
+                (row, cells)
+            })
+            .collect();
+        let columns = T::columns();
+        Records {
+            order: (0..rows.len()).collect(),
+            is_visible: vec![true; rows.len()],
+            selection: (0..columns.len()).collect(),
+            rows,
+            columns,
+        }
+    }
+
+    /// Sorts rows by the cells in a column.
+    ///
+    /// Numeric cells are ordered before other cells and are compared as numbers (with
+    /// [`f64::total_cmp`]). Other cells and ties are compared as text. The sort is stable.
+    pub fn sort_by_column(&mut self, column: usize) {
+        let rows = &self.rows;
+        self.order.sort_by(|&left, &right| {
+            let (left, right) = (rows[left].1.get(column), rows[right].1.get(column));
+            let number =
+                |cell: Option<&String>| cell.and_then(|cell| cell.trim().parse::<f64>().ok());
+            match (number(left), number(right)) {
+                (Some(number), Some(other)) => number.total_cmp(&other),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+            .then_with(|| left.cmp(&right))
+        });
+    }
+
+    /// Sorts rows with a comparison function. The sort is stable.
+    pub fn sort_by(&mut self, mut f: impl FnMut(&T, &T) -> Ordering) {
+        let rows = &self.rows;
+        self.order
+            .sort_by(|&left, &right| f(&rows[left].0, &rows[right].0));
+    }
+
+    pub fn reverse(&mut self) {
+        self.order.reverse();
+    }
+
+    /// Shows only the rows for which the given predicate is `true`.
+    ///
+    /// The predicate is applied to all rows, including those hidden by a previous filter.
+    pub fn filter_rows(&mut self, mut f: impl FnMut(&T) -> bool) {
+        for ((row, _), is_visible) in self.rows.iter().zip(self.is_visible.iter_mut()) {
+            *is_visible = f(row);
+        }
+    }
+
+    /// Shows only the given columns in the given order.
+    ///
+    /// Columns that do not exist are ignored.
+    pub fn select_columns<I>(&mut self, columns: I)
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let n = self.columns.len();
+        self.selection = columns.into_iter().filter(|&column| column < n).collect();
+    }
+
+    /// Gets the visible rows in order.
+    pub fn rows(&self) -> impl '_ + Iterator<Item = &T> {
+        self.visible().map(|(row, _)| row)
+    }
+
+    pub fn into_rows(self) -> Vec<T> {
+        self.rows.into_iter().map(|(row, _)| row).collect()
+    }
+
+    /// Constructs a table with a header and the visible rows and columns.
+    pub fn to_table<C>(&self) -> Table<C>
+    where
+        C: Content + FromCell<char> + From<String>,
+    {
+        let mut table = Table::new(self.visible().map(|(_, cells)| {
+            self.selection.iter().map(move |&column| {
+                Block::with_content(cells.get(column).cloned().unwrap_or_default())
+            })
+        }))
+        .header(
+            self.selection
+                .iter()
+                .map(|&column| Block::with_content(self.columns[column].header.clone())),
+        );
+        for (x, &column) in self.selection.iter().enumerate() {
+            let column = &self.columns[column];
+            table = table.align(x, column.alignment);
+            if let Some(width) = column.width {
+                table = table.column_width(x, width);
+            }
+        }
+        table
+    }
+
+    fn visible(&self) -> impl '_ + Iterator<Item = &(T, Vec<String>)> {
+        self.order
+            .iter()
+            .filter(move |&&index| self.is_visible[index])
+            .map(move |&index| &self.rows[index])
+    }
+}
+
+// Rows of a table that have been drawn between vertical rules. The rows of the header precede
+// the rows of the body.
+struct Layout<C, B>
+where
+    C: Content,
+{
+    rows: Vec<Block<C>>,
+    split: usize,
+    widths: Vec<usize>,
+    is_spanned: Vec<Vec<bool>>,
+    stroke: Stroke,
+    brush: B,
+    padding: usize,
+}
+
+impl<C, B> Layout<C, B>
+where
+    C: Content,
+    B: Brush<C>,
+{
+    // Joins the given rows with horizontal rules at the top, bottom, and below the header.
+    fn page(&self, rows: impl Iterator<Item = (usize, Block<C>)>) -> Block<C> {
+        let mut rows = rows.peekable();
+        let mut output = self.rule(None, rows.peek().map(|(y, _)| *y));
+        while let Some((y, row)) = rows.next() {
+            output = output.join_top_to_bottom_at_left(row);
+            if y + 1 == self.split {
+                output = output
+                    .join_top_to_bottom_at_left(self.rule(Some(y), rows.peek().map(|(y, _)| *y)));
+            }
+            else if rows.peek().is_none() {
+                return output.join_top_to_bottom_at_left(self.rule(Some(y), None));
+            }
+        }
+        output
+    }
+
+    // Draws a horizontal rule between the given rows. Rules are interrupted between the columns of
+    // spanning cells.
+    fn rule(&self, above: Option<usize>, below: Option<usize>) -> Block<C> {
+        let n = self.widths.len();
+        let is_ruled =
+            |y: Option<usize>, boundary: usize| y.is_some_and(|y| !self.is_spanned[y][boundary]);
+        let mut glyphs = vec![];
+        for boundary in 0..=n {
+            let (up, down) = (is_ruled(above, boundary), is_ruled(below, boundary));
+            glyphs.push(self.stroke.junction(up, down, boundary > 0, boundary < n));
+            if let Some(width) = self.widths.get(boundary) {
+                glyphs.extend(iter::repeat_n(
+                    self.stroke.horizontal,
+                    width + (self.padding * 2),
+                ));
+            }
+        }
+        primitive::paint_along::<LeftRight, _, _>(&glyphs, &self.brush)
+    }
+}
+
+impl<C, S, B> Table<Styled<C, S>, B>
+where
+    C: AsRef<str> + Content + From<String>,
+    S: PartialEq + Style,
+    B: Brush<Styled<C, S>>,
+{
+    /// Restyles the cells of the header.
+    #[must_use]
+    pub fn header_style(self, style: S) -> Self {
+        Table {
+            header: self
+                .header
+                .into_iter()
+                .map(|row| {
+                    row.into_iter()
+                        .map(|cell| Cell {
+                            block: cell.block.restyle(style.clone()),
+                            ..cell
+                        })
+                        .collect()
+                })
+                .collect(),
+            ..self
+        }
+    }
+
+    /// Draws the border and rules with the stroke and style of a theme, styles the header with
+    /// the title style of the theme, and styles the padding of cells with the padding style of
+    /// the theme.
+    #[must_use]
+    pub fn theme(self, theme: &Theme<C, S>) -> Table<Styled<C, S>, S> {
+        self.stroke(theme.stroke)
+            .fill_padding(Styled::new(theme.padding.clone(), C::from(" ".to_owned())))
+            .header_style(theme.title.clone())
+            .brush(theme.border.clone())
+    }
+}
+
+impl<C, B> ToMarkdown for Table<C, B>
+where
+    C: Content,
+{
+    /// Emits the table as a GitHub-flavored Markdown table.
+    ///
+    /// Markdown tables have exactly one header row, so subsequent header rows are emitted as the
+    /// first rows of the body and tables without a header are given a blank header. Lines within
+    /// a cell are separated by `<br>`.
+    fn to_markdown(&self) -> String {
+        let placements = place_sections(&self.header, &self.body);
+        let n = placements
+            .iter()
+            .map(|placement| placement.x + placement.columns)
+            .max()
+            .unwrap_or(0);
+        let mut rows = vec![vec![None; n]; self.header.len() + self.body.len()];
+        for (placement, cell) in placements
+            .into_iter()
+            .zip(self.header.iter().chain(self.body.iter()).flatten())
+        {
+            rows[placement.y][placement.x] = Some(cell);
+        }
+        let row = |cells: &[Option<&Cell<C>>]| -> String {
+            let cells = cells.iter().map(|cell| {
+                cell.map_or_else(String::new, |cell| {
+                    markdown::lines(&cell.block)
+                        .iter()
+                        .map(|line| markdown::escape(line.trim_start()))
+                        .filter(|line| !line.is_empty())
+                        .join("<br>")
+                })
+            });
+            format!("| {} |\n", cells.format(" | "))
+        };
+        let (header, rows) = match (self.header.is_empty(), rows.split_first()) {
+            (false, Some((header, rows))) => (row(header), rows),
+            _ => (row(&vec![None; n]), &rows[..]),
+        };
+        let rule = (0..n)
+            .map(|x| match self.alignments.get(x) {
+                Some(HorizontalAlignment::Right) => "---:",
+                _ => "---",
+            })
+            .join(" | ");
+        let mut output = header;
+        output.push_str(&format!("| {} |\n", rule));
+        for cells in rows {
+            output.push_str(&row(cells));
+        }
+        output
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Placement {
+    x: usize,
+    y: usize,
+    columns: usize,
+    rows: usize,
+}
+
+// Places the cells of the header and body. The placements are ordered by row and then column.
+fn place_sections<C>(header: &[Vec<Cell<C>>], body: &[Vec<Cell<C>>]) -> Vec<Placement>
+where
+    C: Content,
+{
+    let split = header.len();
+    place(header)
+        .into_iter()
+        .chain(place(body).into_iter().map(|placement| Placement {
+            y: placement.y + split,
+            ..placement
+        }))
+        .collect()
+}
+
+// Places cells from left to right in their rows, skipping slots that are occupied by cells
+// spanning from previous rows. Row spans are limited to the given rows.
+fn place<C>(rows: &[Vec<Cell<C>>]) -> Vec<Placement>
+where
+    C: Content,
+{
+    let mut occupied = vec![vec![]; rows.len()];
+    let mut placements = vec![];
+    for (y, row) in rows.iter().enumerate() {
+        let mut x = 0;
+        for cell in row {
+            while occupied[y].get(x).copied().unwrap_or(false) {
+                x += 1;
+            }
+            let n = cmp::min(cell.rows, rows.len() - y);
+            for slots in &mut occupied[y..(y + n)] {
+                if slots.len() < x + cell.columns {
+                    slots.resize(x + cell.columns, false);
+                }
+                for slot in &mut slots[x..(x + cell.columns)] {
+                    *slot = true;
+                }
+            }
+            placements.push(Placement {
+                x,
+                y,
+                columns: cell.columns,
+                rows: n,
+            });
+            x += cell.columns;
+        }
+    }
+    placements
+}
+
+// Narrows the widest lengths that are not fixed until their sum is at most the given length or no
+// length can be narrowed further.
+fn shrink(lengths: &mut [usize], is_fixed: &[bool], length: usize) {
+    while lengths.iter().sum::<usize>() > length {
+        match lengths
+            .iter_mut()
+            .zip(is_fixed)
+            .filter(|(length, is_fixed)| !**is_fixed && **length > 1)
+            .map(|(length, _)| length)
+            .rev()
+            .max_by_key(|length| **length)
+        {
+            Some(length) => *length -= 1,
+            _ => break,
+        }
+    }
+}
+
+// Lengthens the given lengths such that their sum with the given separation is at least the given
+// length. Excess length is distributed as evenly as possible among lengths that are not fixed.
+fn distribute(lengths: &mut [usize], is_fixed: &[bool], separation: usize, length: usize) {
+    let available = lengths.iter().sum::<usize>() + separation;
+    let n = is_fixed.iter().filter(|is_fixed| !**is_fixed).count();
+    if length <= available || n == 0 {
+        return;
+    }
+    let excess = length - available;
+    for (index, (length, _)) in lengths
+        .iter_mut()
+        .zip(is_fixed)
+        .filter(|(_, is_fixed)| !**is_fixed)
+        .enumerate()
+    {
+        *length += (excess / n) + usize::from(index < excess % n);
+    }
+}
+
+#[cfg(feature = "delimited")]
+fn parse_delimited(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut is_quoted = false;
+    let mut points = text.chars().peekable();
+    while let Some(point) = points.next() {
+        if is_quoted {
+            match point {
+                '"' if points.peek() == Some(&'"') => {
+                    points.next();
+                    field.push('"');
+                }
+                '"' => is_quoted = false,
+                _ => field.push(point),
+            }
+        }
+        else {
+            match point {
+                '"' if field.is_empty() => is_quoted = true,
+                '\r' if points.peek() == Some(&'\n') => {}
+                '\n' => {
+                    row.push(mem::take(&mut field));
+                    rows.push(mem::take(&mut row));
+                }
+                _ if point == delimiter => row.push(mem::take(&mut field)),
+                _ => field.push(point),
+            }
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    // Blank lines are not rows.
+    rows.retain(|row: &Vec<String>| !(row.len() == 1 && row[0].is_empty()));
+    rows
+}
@@ -0,0 +1,85 @@
+use std::env;
+
+use crate::align::Extent;
+use crate::content::Grapheme;
+use crate::wrap::WrapPolicy;
+
+/// Determines how blocks are fit to the width of a terminal.
+///
+/// Regardless of policy, blocks that are narrower than the terminal are padded at the right.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum FitPolicy {
+    /// Truncates lines that are wider than the terminal at the right, ending them with the given
+    /// ellipsis (if any).
+    Truncate(Option<Grapheme<'static>>),
+    /// Reflows paragraphs that are wider than the terminal. Any words that still overflow the
+    /// terminal are truncated.
+    Reflow(WrapPolicy),
+}
+
+impl Default for FitPolicy {
+    fn default() -> Self {
+        FitPolicy::Truncate(Some(Grapheme::from('\u{2026}')))
+    }
+}
+
+/// Gets the extent of the terminal.
+///
+/// The extent of the terminal attached to standard output, error, or input (in that order) is
+/// queried first. Otherwise, the extent is read from the `COLUMNS` and `LINES` environment
+/// variables. Returns `None` if no extent can be determined.
+pub fn terminal_extent() -> Option<Extent> {
+    query().or_else(|| {
+        let read = |name| {
+            env::var(name)
+                .ok()
+                .and_then(|value| value.trim().parse().ok())
+                .filter(|&length: &usize| length > 0)
+        };
+        Some(Extent {
+            width: read("COLUMNS")?,
+            height: read("LINES").unwrap_or(0),
+        })
+    })
+}
+
+/// Determines whether or not standard output is a terminal.
+///
+/// Always returns `false` on platforms other than Unix.
+pub fn is_terminal() -> bool {
+    #[cfg(unix)]
+    {
+        // SAFETY: `isatty` only inspects the given descriptor.
+        unsafe { libc::isatty(libc::STDOUT_FILENO) == 1 }
+    }
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+#[cfg(unix)]
+fn query() -> Option<Extent> {
+    [libc::STDOUT_FILENO, libc::STDERR_FILENO, libc::STDIN_FILENO]
+        .iter()
+        .find_map(|&descriptor| {
+            let mut size = libc::winsize {
+                ws_row: 0,
+                ws_col: 0,
+                ws_xpixel: 0,
+                ws_ypixel: 0,
+            };
+            // SAFETY: `TIOCGWINSZ` only writes to the given `winsize`, which outlives the call.
+            //         Descriptors that are not terminals fail the request without side effects.
+            let result = unsafe { libc::ioctl(descriptor, libc::TIOCGWINSZ, &mut size) };
+            (result == 0 && size.ws_col > 0).then(|| Extent {
+                width: size.ws_col.into(),
+                height: size.ws_row.into(),
+            })
+        })
+}
+
+#[cfg(not(unix))]
+fn query() -> Option<Extent> {
+    None
+}
@@ -0,0 +1,67 @@
+use std::cmp;
+
+use crate::block::{self, Block};
+use crate::content::{Content, Grapheme};
+
+#[macro_export]
+macro_rules! assert_block_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        if let Some(diff) = $crate::testing::diff(&$left, &$right) {
+            panic!("assertion failed: blocks are not equal\n{}", diff);
+        }
+    };
+}
+
+/// Compares the graphemes of two blocks cell by cell.
+///
+/// Returns `None` if the blocks are equal. Otherwise, returns a description of the blocks side by
+/// side followed by carets beneath the cells that differ.
+pub fn diff<C, D>(left: &Block<C>, right: &Block<D>) -> Option<String>
+where
+    C: Content,
+    D: Content,
+{
+    fn row_width(row: &[Grapheme]) -> usize {
+        row.iter().map(Grapheme::width).sum()
+    }
+
+    fn pad(row: &[Grapheme], width: usize) -> String {
+        let mut text: String = row.iter().map(Grapheme::get).collect();
+        text.push_str(&" ".repeat(width.saturating_sub(row_width(row))));
+        text
+    }
+
+    let (front, back) = (left.cells(), right.cells());
+    if (left.width(), left.height()) == (right.width(), right.height()) && front == back {
+        return None;
+    }
+    let width = cmp::max(left.width(), right.width());
+    let height = cmp::max(left.height(), right.height());
+    let margin = height.saturating_sub(1).to_string().len();
+    let mut output = format!(
+        "left: {}x{}, right: {}x{}\n",
+        left.width(),
+        left.height(),
+        right.width(),
+        right.height(),
+    );
+    for y in 0..height {
+        let empty = vec![];
+        let front = front.get(y).unwrap_or(&empty);
+        let back = back.get(y).unwrap_or(&empty);
+        let carets: String = block::diff_cells(front, back)
+            .map(|(_, n, is_different)| if is_different { "^" } else { " " }.repeat(n))
+            .collect();
+        let line = format!(
+            "{:>margin$} |{}|{}| {}",
+            y,
+            pad(front, width),
+            pad(back, width),
+            carets,
+            margin = margin,
+        );
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    Some(output)
+}
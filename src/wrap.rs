@@ -0,0 +1,250 @@
+use crate::content::{Content, Grapheme};
+
+const ZERO_WIDTH_SPACE: &str = "\u{200B}";
+const SOFT_HYPHEN: &str = "\u{AD}";
+
+#[derive(Clone, Copy, Debug)]
+struct Word {
+    start: usize,
+    end: usize,
+    column: usize,
+    width: usize,
+    // The word is not separated from the previous word by spaces.
+    is_joined: bool,
+    // The word ends with a soft hyphen.
+    is_hyphenated: bool,
+}
+
+/// Determines where lines are broken when wrapping.
+///
+/// Regardless of policy, lines are never broken at no-break spaces (U+00A0). Except when breaking
+/// anywhere, lines may also be broken at zero-width spaces (U+200B) and soft hyphens (U+00AD).
+/// Lines broken at a soft hyphen end with a visible hyphen.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum WrapPolicy {
+    /// Breaks lines between words and breaks words that are wider than a line.
+    WordBoundary,
+    /// Breaks lines between any graphemes such that each line fills the width.
+    BreakAnywhere,
+    /// Breaks lines between words. Words that are wider than a line overflow the width.
+    #[default]
+    NoBreakOverflow,
+}
+
+pub fn wrap<C>(content: C, width: usize) -> Vec<C>
+where
+    C: Content,
+{
+    wrap_by(content, width, WrapPolicy::default())
+}
+
+pub fn wrap_by<C>(content: C, width: usize, policy: WrapPolicy) -> Vec<C>
+where
+    C: Content,
+{
+    content
+        .into_lines()
+        .into_iter()
+        .flat_map(|line| wrap_line(line, width, policy))
+        .collect()
+}
+
+/// Wraps content and distributes spaces between words such that each wrapped line fills the
+/// width.
+///
+/// The last line wrapped from each line of the content is not justified. Lines with only one
+/// word are not justified either.
+pub fn justify<C>(content: C, width: usize) -> Vec<C>
+where
+    C: Content,
+{
+    justify_by(content, width, WrapPolicy::default())
+}
+
+pub fn justify_by<C>(content: C, width: usize, policy: WrapPolicy) -> Vec<C>
+where
+    C: Content,
+{
+    content
+        .into_lines()
+        .into_iter()
+        .flat_map(|line| {
+            let mut lines = wrap_line(line, width, policy);
+            let last = lines.pop();
+            lines
+                .into_iter()
+                .map(|line| justify_line(line, width))
+                .chain(last)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn justify_line<C>(line: C, width: usize) -> C
+where
+    C: Content,
+{
+    let words = words(&line, None);
+    // Only spaces between words are widened.
+    let n = words.iter().skip(1).filter(|word| !word.is_joined).count();
+    let extra = width.saturating_sub(line.width());
+    if n == 0 || extra == 0 {
+        return line;
+    }
+    // Gaps toward the start of the line receive any remaining spaces.
+    let (quotient, remainder) = (extra / n, extra % n);
+    let mut index = 0usize;
+    words
+        .windows(2)
+        .fold(line.clone().truncate(words[0].end), |output, pair| {
+            let (previous, word) = (pair[0], pair[1]);
+            let gap = line
+                .clone()
+                .skip(previous.end)
+                .truncate(word.start - previous.end);
+            let gap = if word.is_joined {
+                gap
+            }
+            else {
+                index += 1;
+                C::concatenate(
+                    gap,
+                    C::space().repeat(quotient + usize::from(index <= remainder)),
+                )
+            };
+            let word = line
+                .clone()
+                .skip(word.start)
+                .truncate(word.end - word.start);
+            C::concatenate(C::concatenate(output, gap), word)
+        })
+}
+
+fn wrap_line<C>(line: C, width: usize, policy: WrapPolicy) -> Vec<C>
+where
+    C: Content,
+{
+    let words = match policy {
+        WrapPolicy::WordBoundary => words(&line, Some(width)),
+        WrapPolicy::BreakAnywhere => {
+            return break_line(line, width);
+        }
+        WrapPolicy::NoBreakOverflow => words(&line, None),
+    };
+    let mut lines = vec![];
+    let mut words = words.into_iter();
+    if let Some(first) = words.next() {
+        // The first line begins at the start of the content, so leading spaces are preserved.
+        let (mut start, mut end, mut column) = (0, first.end, 0);
+        let mut is_hyphenated = first.is_hyphenated;
+        for word in words {
+            if word.column + word.width - column <= width {
+                end = word.end;
+            }
+            else {
+                // The soft hyphen at the end of a broken line is replaced with a visible hyphen.
+                let content = line.clone().skip(start);
+                lines.push(if is_hyphenated {
+                    C::concatenate(
+                        content.truncate(end - start - 1),
+                        C::grapheme(Grapheme::from('-')),
+                    )
+                }
+                else {
+                    content.truncate(end - start)
+                });
+                start = word.start;
+                end = word.end;
+                column = word.column;
+            }
+            is_hyphenated = word.is_hyphenated;
+        }
+        lines.push(line.skip(start).truncate(end - start));
+    }
+    else {
+        lines.push(C::empty());
+    }
+    lines
+}
+
+fn break_line<C>(line: C, width: usize) -> Vec<C>
+where
+    C: Content,
+{
+    let mut lines = vec![];
+    let (mut start, mut end, mut column) = (0usize, 0usize, 0usize);
+    for glyph in line.to_graphemes() {
+        if column + glyph.width() > width && end > start {
+            lines.push(line.clone().skip(start).truncate(end - start));
+            start = end;
+            column = 0;
+        }
+        end += 1;
+        column += glyph.width();
+    }
+    lines.push(line.skip(start).truncate(end - start));
+    lines
+}
+
+// If a limit is given, then words are split such that no part of a word is wider than the limit
+// unless it consists of a single grapheme.
+fn words<C>(line: &C, limit: Option<usize>) -> Vec<Word>
+where
+    C: Content,
+{
+    let mut words: Vec<Word> = vec![];
+    let mut column = 0usize;
+    let (mut is_word, mut is_joined) = (false, false);
+    for (index, glyph) in line.to_graphemes().iter().enumerate() {
+        let width = glyph.width();
+        if *glyph == Grapheme::SPACE {
+            is_word = false;
+            is_joined = false;
+        }
+        else if glyph.get() == ZERO_WIDTH_SPACE {
+            is_joined = is_joined || is_word;
+            is_word = false;
+        }
+        else if is_word && limit.is_none_or(|limit| words.last().unwrap().width + width <= limit)
+        {
+            let word = words.last_mut().unwrap();
+            word.end = index + 1;
+            word.width += width;
+            if glyph.get() == SOFT_HYPHEN {
+                word.is_hyphenated = true;
+                is_word = false;
+                is_joined = true;
+            }
+        }
+        else {
+            words.push(Word {
+                start: index,
+                end: index + 1,
+                column,
+                width,
+                is_joined: is_joined || is_word,
+                is_hyphenated: false,
+            });
+            is_word = true;
+            is_joined = false;
+        }
+        column += width;
+    }
+    words
+}
+
+pub(crate) fn trim_end<C>(content: C) -> C
+where
+    C: Content,
+{
+    let n = {
+        let graphemes = content.to_graphemes();
+        let n = graphemes
+            .iter()
+            .rev()
+            .take_while(|glyph| **glyph == Grapheme::SPACE)
+            .count();
+        graphemes.len() - n
+    };
+    content.truncate(n)
+}